@@ -0,0 +1,126 @@
+//! Benchmarks of the time taken to merge & render Scenes and of the memory they hold
+//!
+//! Only the public Scene API is used so that the same benchmarks can be run on other revisions to
+//! compare against, e.g. by running `cargo bench -p libpixylene -- --save-baseline before` on one
+//! and `cargo bench -p libpixylene -- --baseline before` on the other. The memory held by each
+//! Scene is printed while benchmarking.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use libpixylene::{
+    project::{Layer, Scene},
+    types::{BlendMode, Coord, PCoord, TruePixel, UCoord},
+};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering::Relaxed},
+};
+
+/// Allocator that keeps count of the number of bytes currently allocated
+struct Counting;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            ALLOCATED.fetch_add(layout.size(), Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        ALLOCATED.fetch_sub(layout.size(), Relaxed);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// (name, dimensions, one in how many pixels are filled)
+const CASES: [(&str, (u16, u16), usize); 4] = [
+    ("dense_64", (64, 64), 1),
+    ("dense_512", (512, 512), 1),
+    ("sparse_512", (512, 512), 100),
+    ("sparse_4096", (4096, 4096), 10_000),
+];
+
+fn layer(dim: PCoord, every: usize) -> Layer<TruePixel> {
+    let mut layer = Layer::new_with_solid_color(dim, None);
+    let mut i = 0;
+    for x in 0..dim.x() {
+        for y in 0..dim.y() {
+            if i % every == 0 {
+                let pixel = TruePixel {
+                    r: x as u8,
+                    g: y as u8,
+                    b: (x ^ y) as u8,
+                    a: 200,
+                };
+                layer.scene.set_pixel(UCoord { x, y }, Some(pixel)).unwrap();
+            }
+            i += 1;
+        }
+    }
+    layer
+}
+
+fn memory(c: &mut Criterion) {
+    let mut group = c.benchmark_group("memory");
+    for (name, (x, y), every) in CASES {
+        let before = ALLOCATED.load(Relaxed);
+        let layer = layer(PCoord::new(x, y).unwrap(), every);
+        println!(
+            "memory/{}: {} bytes held by the scene",
+            name,
+            ALLOCATED.load(Relaxed) - before,
+        );
+        group.bench_function(BenchmarkId::new("get_pixel", name), |b| {
+            b.iter(|| {
+                layer
+                    .scene
+                    .get_pixel(black_box(UCoord { x: x / 2, y: y / 2 }))
+            })
+        });
+    }
+    group.finish();
+}
+
+fn merge(c: &mut Criterion) {
+    let mut group = c.benchmark_group("merge");
+    group.sample_size(10);
+    for (name, (x, y), every) in CASES {
+        let dim = PCoord::new(x, y).unwrap();
+        let top = layer(dim, every);
+        let bottom = layer(dim, every * 3);
+        group.bench_function(name, |b| {
+            b.iter(|| Layer::merge(dim, black_box(&top), black_box(&bottom), BlendMode::Normal))
+        });
+    }
+    group.finish();
+}
+
+fn render(c: &mut Criterion) {
+    let mut group = c.benchmark_group("render");
+    for (name, (x, y), every) in CASES {
+        let scene: Scene<TruePixel> = layer(PCoord::new(x, y).unwrap(), every).scene;
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                scene.render(
+                    PCoord::new(60, 200).unwrap(),
+                    black_box(1),
+                    PCoord::new(1, 2).unwrap(),
+                    Coord {
+                        x: i32::from(x / 2),
+                        y: i32::from(y / 2),
+                    },
+                )
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, memory, merge, render);
+criterion_main!(benches);
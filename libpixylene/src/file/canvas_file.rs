@@ -1,15 +1,85 @@
-use crate::project::Canvas;
+use crate::{
+    project::{Canvas, Layers, LayersType, Scene},
+    types::{IndexedPixel, Pixel, TruePixel},
+};
 
-use serde_json::{from_str, to_string};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use serde_json::{from_str, json, to_string, Value};
 use std::{
     fmt, fs, io,
     path::{Path, PathBuf},
+    str::FromStr,
 };
 
 impl Canvas {
     pub fn to_json(&self) -> Result<String, serde_json::Error> {
         to_string(self)
     }
+
+    /// Serializes the Canvas to JSON with its Scenes encoded using the given [`SceneEncoding`]
+    pub fn to_json_encoded(&self, encoding: SceneEncoding) -> Result<String, serde_json::Error> {
+        if encoding == SceneEncoding::Verbose {
+            return self.to_json();
+        }
+        to_string(&encode_canvas(self, encoding)?)
+    }
+}
+
+/// The encoding of the [`Scenes`](crate::project::Scene) in a Canvas file
+///
+/// Compact encodings are tagged with an `"encoding"` field on the Scene object, while verbose
+/// Scenes have none. [`CanvasFile::read`] accepts all encodings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum SceneEncoding {
+    /// The `grid` of the Scene as an array of every pixel, `null` where empty
+    #[default]
+    #[serde(alias = "verbose")]
+    Verbose,
+
+    /// The `rows` of the Scene as an array of strings, one per row, each consisting of
+    /// space-separated runs of `<count>*<pixel>` (or just `<pixel>` if the count is 1), where
+    /// `<pixel>` is `_` if empty, `#rrggbbaa` for true pixels or the index for indexed pixels
+    #[serde(alias = "rle")]
+    Rle,
+
+    /// The `data` of the Scene as a base64 string of a bitmap of which pixels are present (most
+    /// significant bit first), followed by the RGBA bytes (true) or the index (indexed) of each
+    /// present pixel
+    #[serde(alias = "base64")]
+    Base64,
+}
+
+impl SceneEncoding {
+    fn tag(&self) -> Option<&'static str> {
+        match self {
+            SceneEncoding::Verbose => None,
+            SceneEncoding::Rle => Some("rle"),
+            SceneEncoding::Base64 => Some("base64"),
+        }
+    }
+}
+
+impl fmt::Display for SceneEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.tag().unwrap_or("verbose"))
+    }
+}
+
+impl FromStr for SceneEncoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "verbose" => Ok(SceneEncoding::Verbose),
+            "rle" => Ok(SceneEncoding::Rle),
+            "base64" => Ok(SceneEncoding::Base64),
+            _ => Err(format!(
+                "unknown scene encoding '{}', expected one of: verbose, rle, base64",
+                s
+            )),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -17,22 +87,28 @@ pub struct CanvasFile;
 
 impl CanvasFile {
     pub fn read(path: &PathBuf) -> Result<Canvas, CanvasFileError> {
-        use CanvasFileError::{DeserializeError, ReadError};
+        use CanvasFileError::{DecodeError, DeserializeError, ReadError};
 
-        Ok(
+        let mut value: Value =
             from_str(&fs::read_to_string(&path).map_err(|err| ReadError(path.clone(), err))?)
-                .map_err(|err| DeserializeError(path.clone(), err))?,
-        )
+                .map_err(|err| DeserializeError(path.clone(), err))?;
+        decode_scenes(&mut value).map_err(|err| DecodeError(path.clone(), err))?;
+        serde_json::from_value(value).map_err(|err| DeserializeError(path.clone(), err))
     }
 
-    pub fn write(path: &PathBuf, canvas: &Canvas) -> Result<(), CanvasFileError> {
+    pub fn write(
+        path: &PathBuf,
+        canvas: &Canvas,
+        encoding: SceneEncoding,
+    ) -> Result<(), CanvasFileError> {
         use std::io::Write;
         use CanvasFileError::{SerializeError, WriteError};
 
         Ok(fs::File::create(Path::new(&path))
             .map_err(|err| WriteError(path.clone(), err))?
             .write_all(
-                to_string(canvas)
+                canvas
+                    .to_json_encoded(encoding)
                     .map_err(|err| SerializeError(path.clone(), err))?
                     .as_bytes(),
             )
@@ -40,12 +116,270 @@ impl CanvasFile {
     }
 }
 
+/// Returns the Scene objects of a serialized Canvas, along with whether they are true-color
+fn scenes_mut(canvas: &mut Value) -> Vec<(&mut Value, bool)> {
+    let mut scenes = Vec::new();
+    if let Some(layers_type) = canvas.get_mut("layers").and_then(Value::as_object_mut) {
+        for (kind, layers) in layers_type.iter_mut() {
+            let is_true = kind == "True";
+            if let Some(layers) = layers.get_mut("layers").and_then(Value::as_array_mut) {
+                for layer in layers.iter_mut() {
+                    if let Some(scene) = layer.get_mut("scene") {
+                        scenes.push((scene, is_true));
+                    }
+                }
+            }
+        }
+    }
+    scenes
+}
+
+/// Serializes a Canvas with its Scenes in the given encoding, encoding each straight from its grid
+pub(crate) fn encode_canvas(
+    canvas: &Canvas,
+    encoding: SceneEncoding,
+) -> Result<Value, serde_json::Error> {
+    let layers = match &canvas.layers {
+        LayersType::True(layers) => json!({ "True": encode_layers(layers, encoding)? }),
+        LayersType::Indexed(layers) => json!({ "Indexed": encode_layers(layers, encoding)? }),
+    };
+    Ok(json!({
+        "layers": layers,
+        "palette": serde_json::to_value(&canvas.palette)?,
+    }))
+}
+
+fn encode_layers<T: RlePixel>(
+    layers: &Layers<T>,
+    encoding: SceneEncoding,
+) -> Result<Value, serde_json::Error> {
+    let mut encoded = Vec::new();
+    for index in 0..layers.len() {
+        let layer = &layers[index];
+        encoded.push(json!({
+            "scene": encode_scene(&layer.scene, encoding)?,
+            "opacity": layer.opacity,
+            "mute": layer.mute,
+            "blend_mode": serde_json::to_value(layer.blend_mode)?,
+        }));
+    }
+    Ok(json!({ "layers": encoded, "dimensions": layers.dim() }))
+}
+
+fn encode_scene<T: RlePixel>(
+    scene: &Scene<T>,
+    encoding: SceneEncoding,
+) -> Result<Value, serde_json::Error> {
+    let mut object = json!({ "dim": scene.dim(), "encoding": encoding.tag() });
+    match encoding {
+        SceneEncoding::Verbose => return serde_json::to_value(scene),
+        SceneEncoding::Rle => object["rows"] = json!(encode_rle(scene)),
+        SceneEncoding::Base64 => object["data"] = json!(encode_base64(scene)),
+    }
+    Ok(object)
+}
+
+pub(crate) fn decode_scenes(canvas: &mut Value) -> Result<(), String> {
+    for (scene, is_true) in scenes_mut(canvas) {
+        let encoding = match scene.get("encoding") {
+            None | Some(Value::Null) => continue,
+            Some(encoding) => encoding
+                .as_str()
+                .ok_or(format!("invalid scene encoding {}", encoding))?
+                .parse::<SceneEncoding>()?,
+        };
+        let side = |side: &str| {
+            scene["dim"][side]
+                .as_u64()
+                .and_then(|side| u16::try_from(side).ok())
+                .map(usize::from)
+                .ok_or(format!(
+                    "{} scene has invalid dimensions {}",
+                    encoding, scene["dim"]
+                ))
+        };
+        let area = side("x")? * side("y")?;
+
+        let grid = match encoding {
+            SceneEncoding::Verbose => continue,
+            SceneEncoding::Rle => decode_rle(
+                scene
+                    .get("rows")
+                    .and_then(Value::as_array)
+                    .ok_or("rle scene is missing its 'rows'")?,
+                area,
+            )?,
+            SceneEncoding::Base64 => decode_base64(
+                scene
+                    .get("data")
+                    .and_then(Value::as_str)
+                    .ok_or("base64 scene is missing its 'data'")?,
+                area,
+                is_true,
+            )?,
+        };
+        if grid.len() != area {
+            return Err(format!(
+                "{} scene has {} pixels but its dimensions need {}",
+                encoding,
+                grid.len(),
+                area,
+            ));
+        }
+
+        let object = scene.as_object_mut().unwrap(); //wont fail because it had an encoding
+        object.remove("encoding");
+        object.remove("rows");
+        object.remove("data");
+        object.insert("grid".to_owned(), Value::Array(grid));
+    }
+    Ok(())
+}
+
+/// A pixel that can be written as a token of an [`Rle`](SceneEncoding::Rle) scene
+trait RlePixel: Pixel + PartialEq + Serialize {
+    fn token(&self) -> String;
+}
+
+impl RlePixel for TruePixel {
+    fn token(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl RlePixel for IndexedPixel {
+    fn token(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+fn decode_pixel(token: &str) -> Result<Value, String> {
+    if token == "_" {
+        Ok(Value::Null)
+    } else if token.starts_with('#') {
+        TruePixel::from_hex(token)
+            .map(|pixel| json!(pixel))
+            .map_err(|err| err.to_string())
+    } else {
+        token
+            .parse::<u8>()
+            .map(|index| json!(index))
+            .map_err(|_| format!("invalid pixel '{}' in rle scene", token))
+    }
+}
+
+fn encode_rle<T: RlePixel>(scene: &Scene<T>) -> Vec<String> {
+    let width = usize::from(scene.dim().y());
+    let mut grid = scene.grid();
+    (0..scene.dim().x())
+        .map(|_| {
+            let mut runs: Vec<(usize, Option<T>)> = Vec::new();
+            for pixel in grid.by_ref().take(width) {
+                match runs.last_mut() {
+                    Some((count, last)) if *last == pixel => *count += 1,
+                    _ => runs.push((1, pixel)),
+                }
+            }
+            runs.into_iter()
+                .map(|(count, pixel)| {
+                    let pixel = pixel.map_or("_".to_owned(), |pixel| pixel.token());
+                    match count {
+                        1 => pixel,
+                        count => format!("{}*{}", count, pixel),
+                    }
+                })
+                .collect::<Vec<String>>()
+                .join(" ")
+        })
+        .collect()
+}
+
+fn decode_rle(rows: &[Value], area: usize) -> Result<Vec<Value>, String> {
+    let mut grid = Vec::new();
+    for row in rows {
+        let row = row
+            .as_str()
+            .ok_or(format!("invalid row {} in rle scene", row))?;
+        for run in row.split_whitespace() {
+            let (count, pixel) = match run.split_once('*') {
+                Some((count, pixel)) => (
+                    count
+                        .parse::<usize>()
+                        .map_err(|_| format!("invalid run '{}' in rle scene", run))?,
+                    pixel,
+                ),
+                None => (1, run),
+            };
+            //checked before extending so that a corrupt count can't exhaust the memory
+            grid.len()
+                .checked_add(count)
+                .filter(|total| *total <= area)
+                .ok_or(format!(
+                    "rle scene has more pixels than the {} its dimensions need",
+                    area
+                ))?;
+            let pixel = decode_pixel(pixel)?;
+            grid.extend(std::iter::repeat_n(pixel, count));
+        }
+    }
+    Ok(grid)
+}
+
+fn encode_base64<T: Pixel>(scene: &Scene<T>) -> String {
+    let area = usize::from(scene.dim().x()) * usize::from(scene.dim().y());
+    let mut bytes = vec![0u8; area.div_ceil(8)];
+    let mut packed = vec![0u8; T::PACKED_LEN];
+    for (i, pixel) in scene.grid().enumerate() {
+        if let Some(pixel) = pixel {
+            bytes[i / 8] |= 0b1000_0000 >> (i % 8);
+            pixel.pack(&mut packed);
+            bytes.extend_from_slice(&packed);
+        }
+    }
+    STANDARD.encode(bytes)
+}
+
+fn decode_base64(data: &str, area: usize, is_true: bool) -> Result<Vec<Value>, String> {
+    let bytes = STANDARD
+        .decode(data)
+        .map_err(|err| format!("invalid base64 scene: {}", err))?;
+    let bitmap_len = area.div_ceil(8);
+    let bitmap = bytes
+        .get(..bitmap_len)
+        .ok_or("base64 scene is too short for its dimensions")?;
+    let mut packed = bytes[bitmap_len..].chunks(if is_true { 4 } else { 1 });
+
+    let mut grid = Vec::with_capacity(area);
+    for i in 0..area {
+        if bitmap[i / 8] & (0b1000_0000 >> (i % 8)) == 0 {
+            grid.push(Value::Null);
+            continue;
+        }
+        let pixel = packed
+            .next()
+            .filter(|pixel| pixel.len() == if is_true { 4 } else { 1 })
+            .ok_or("base64 scene is too short for its pixels")?;
+        grid.push(if is_true {
+            json!(TruePixel {
+                r: pixel[0],
+                g: pixel[1],
+                b: pixel[2],
+                a: pixel[3],
+            })
+        } else {
+            json!(pixel[0])
+        });
+    }
+    Ok(grid)
+}
+
 #[derive(Debug)]
 pub enum CanvasFileError {
     ReadError(PathBuf, io::Error),
     WriteError(PathBuf, io::Error),
     DeserializeError(PathBuf, serde_json::Error),
     SerializeError(PathBuf, serde_json::Error),
+    DecodeError(PathBuf, String),
 }
 
 impl fmt::Display for CanvasFileError {
@@ -76,6 +410,12 @@ impl fmt::Display for CanvasFileError {
                 path.display(),
                 err,
             ),
+            DecodeError(path, err) => write!(
+                f,
+                "error decoding scenes reading from '{}':\n{}",
+                path.display(),
+                err,
+            ),
         }
     }
 }
@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fmt, hash::Hash, str::FromStr};
+
+/// Palette-aware downsampling modes that can be applied to a [`PngFile`](super::PngFile)
+///
+/// Each output pixel is picked from the block of source pixels it covers rather than blended from
+/// them, so the downsampled image only contains colors that were present in the source. Used on an
+/// upscaled pixel-art image along with [`PngFile::detect_pixel_grid`][d], they recover the
+/// original losslessly.
+///
+/// [d]: super::PngFile::detect_pixel_grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Downsampler {
+    /// Picks the most frequent color in the block, ties going to the color found first
+    #[serde(alias = "mode")]
+    Mode,
+
+    /// Picks the color at the center of the block
+    #[serde(alias = "center")]
+    Center,
+}
+
+impl Downsampler {
+    /// Downsamples a row-major `height`x`width` grid of pixels to `new_height`x`new_width`
+    ///
+    /// The new dimensions must not be larger than the old ones.
+    pub(crate) fn downsample<T: Copy + Eq + Hash>(
+        &self,
+        pixels: &[T],
+        (height, width): (usize, usize),
+        (new_height, new_width): (usize, usize),
+    ) -> Vec<T> {
+        let mut out = Vec::with_capacity(new_height * new_width);
+        let mut counts: HashMap<T, (usize, usize)> = HashMap::new();
+        for i in 0..new_height {
+            let rows = (i * height / new_height)..((i + 1) * height / new_height);
+            for j in 0..new_width {
+                let cols = (j * width / new_width)..((j + 1) * width / new_width);
+                match self {
+                    Downsampler::Mode => {
+                        counts.clear();
+                        for (order, x) in rows.clone().enumerate() {
+                            for y in cols.clone() {
+                                let entry = counts
+                                    .entry(pixels[x * width + y])
+                                    .or_insert((0, order * width + y));
+                                entry.0 += 1;
+                            }
+                        }
+                        out.push(
+                            *counts
+                                .iter()
+                                //most frequent, then first found
+                                .max_by(|(_, a), (_, b)| a.0.cmp(&b.0).then(b.1.cmp(&a.1)))
+                                .unwrap() //wont fail because blocks are never empty
+                                .0,
+                        );
+                    }
+                    Downsampler::Center => {
+                        let x = (rows.start + rows.end - 1) / 2;
+                        let y = (cols.start + cols.end - 1) / 2;
+                        out.push(pixels[x * width + y]);
+                    }
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Detects the size in source pixels of a single art-pixel in a row-major `height`x`width` grid
+/// of pixels, as the largest (rows, columns) block size that every run of same-colored pixels is
+/// a multiple of
+pub(crate) fn detect_pixel_size<T: PartialEq>(
+    pixels: &[T],
+    height: usize,
+    width: usize,
+) -> (usize, usize) {
+    fn gcd(a: usize, b: usize) -> usize {
+        if b == 0 {
+            a
+        } else {
+            gcd(b, a % b)
+        }
+    }
+
+    let mut rows = height;
+    let mut cols = width;
+    for x in 0..height {
+        let mut run = 1;
+        for y in 1..width {
+            if pixels[x * width + y] == pixels[x * width + y - 1] {
+                run += 1;
+            } else {
+                cols = gcd(cols, run);
+                run = 1;
+            }
+        }
+    }
+    for y in 0..width {
+        let mut run = 1;
+        for x in 1..height {
+            if pixels[x * width + y] == pixels[(x - 1) * width + y] {
+                run += 1;
+            } else {
+                rows = gcd(rows, run);
+                run = 1;
+            }
+        }
+    }
+    (rows, cols)
+}
+
+impl fmt::Display for Downsampler {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Downsampler::Mode => "mode",
+                Downsampler::Center => "center",
+            }
+        )
+    }
+}
+
+impl FromStr for Downsampler {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "mode" | "majority" => Ok(Downsampler::Mode),
+            "center" | "centre" => Ok(Downsampler::Center),
+            _ => Err(format!(
+                "unknown downsampler '{}', expected one of: mode, center",
+                s
+            )),
+        }
+    }
+}
@@ -0,0 +1,675 @@
+//! Lookup tables of the hq2x & hq3x upscalers
+//!
+//! Every pixel is classified by which of its 8 neighbours differ from it, giving one of 256
+//! patterns. The tables map each pattern to the [`Rule`] of every cell of its output block, row
+//! by row. Neighbours are numbered 1 to 9 row by row, 5 being the pixel itself:
+//!
+//! ```text
+//! 1 2 3
+//! 4 5 6
+//! 7 8 9
+//! ```
+
+/// How a cell of an output block is mixed from `(neighbour, weight)` pairs
+#[derive(Debug, Clone, Copy)]
+pub(super) enum Rule {
+    Mix(&'static [(usize, u32)]),
+
+    /// The first mix if the two neighbours differ, otherwise the second
+    IfDiffers(
+        usize,
+        usize,
+        &'static [(usize, u32)],
+        &'static [(usize, u32)],
+    ),
+}
+use Rule::*;
+
+/// The distinct rules of hq2x, indexed by [`HQ2X`]
+pub(super) const HQ2X_RULES: [Rule; 52] = [
+    Mix(&[(5, 2), (1, 1), (2, 1)]),
+    Mix(&[(5, 2), (1, 1), (4, 1)]),
+    Mix(&[(5, 2), (2, 1), (6, 1)]),
+    Mix(&[(5, 2), (3, 1), (2, 1)]),
+    Mix(&[(5, 2), (3, 1), (6, 1)]),
+    Mix(&[(5, 2), (4, 1), (2, 1)]),
+    Mix(&[(5, 2), (6, 1), (8, 1)]),
+    Mix(&[(5, 2), (7, 1), (4, 1)]),
+    Mix(&[(5, 2), (7, 1), (8, 1)]),
+    Mix(&[(5, 2), (8, 1), (4, 1)]),
+    Mix(&[(5, 2), (9, 1), (6, 1)]),
+    Mix(&[(5, 2), (9, 1), (8, 1)]),
+    Mix(&[(5, 3), (1, 1)]),
+    Mix(&[(5, 3), (2, 1)]),
+    Mix(&[(5, 3), (3, 1)]),
+    Mix(&[(5, 3), (4, 1)]),
+    Mix(&[(5, 3), (6, 1)]),
+    Mix(&[(5, 3), (7, 1)]),
+    Mix(&[(5, 3), (8, 1)]),
+    Mix(&[(5, 3), (9, 1)]),
+    IfDiffers(2, 6, &[(5, 1)], &[(5, 2), (2, 1), (6, 1)]),
+    IfDiffers(2, 6, &[(5, 1)], &[(5, 2), (2, 3), (6, 3)]),
+    IfDiffers(2, 6, &[(5, 1)], &[(5, 14), (2, 1), (6, 1)]),
+    IfDiffers(2, 6, &[(5, 3), (3, 1)], &[(5, 2), (2, 1), (6, 1)]),
+    IfDiffers(2, 6, &[(5, 3), (3, 1)], &[(5, 2), (2, 3), (6, 3)]),
+    IfDiffers(2, 6, &[(5, 3), (3, 1)], &[(5, 6), (2, 1), (6, 1)]),
+    IfDiffers(2, 6, &[(5, 3), (4, 1)], &[(5, 5), (2, 2), (4, 1)]),
+    IfDiffers(2, 6, &[(5, 3), (8, 1)], &[(5, 5), (6, 2), (8, 1)]),
+    IfDiffers(4, 2, &[(5, 1)], &[(5, 2), (4, 1), (2, 1)]),
+    IfDiffers(4, 2, &[(5, 1)], &[(5, 2), (4, 3), (2, 3)]),
+    IfDiffers(4, 2, &[(5, 1)], &[(5, 14), (4, 1), (2, 1)]),
+    IfDiffers(4, 2, &[(5, 3), (1, 1)], &[(5, 2), (4, 1), (2, 1)]),
+    IfDiffers(4, 2, &[(5, 3), (1, 1)], &[(5, 2), (4, 3), (2, 3)]),
+    IfDiffers(4, 2, &[(5, 3), (1, 1)], &[(5, 6), (4, 1), (2, 1)]),
+    IfDiffers(4, 2, &[(5, 3), (6, 1)], &[(5, 5), (2, 2), (6, 1)]),
+    IfDiffers(4, 2, &[(5, 3), (8, 1)], &[(5, 5), (4, 2), (8, 1)]),
+    IfDiffers(6, 8, &[(5, 1)], &[(5, 2), (6, 1), (8, 1)]),
+    IfDiffers(6, 8, &[(5, 1)], &[(5, 2), (6, 3), (8, 3)]),
+    IfDiffers(6, 8, &[(5, 1)], &[(5, 14), (6, 1), (8, 1)]),
+    IfDiffers(6, 8, &[(5, 3), (2, 1)], &[(5, 5), (6, 2), (2, 1)]),
+    IfDiffers(6, 8, &[(5, 3), (4, 1)], &[(5, 5), (8, 2), (4, 1)]),
+    IfDiffers(6, 8, &[(5, 3), (9, 1)], &[(5, 2), (6, 1), (8, 1)]),
+    IfDiffers(6, 8, &[(5, 3), (9, 1)], &[(5, 2), (6, 3), (8, 3)]),
+    IfDiffers(6, 8, &[(5, 3), (9, 1)], &[(5, 6), (6, 1), (8, 1)]),
+    IfDiffers(8, 4, &[(5, 1)], &[(5, 2), (8, 1), (4, 1)]),
+    IfDiffers(8, 4, &[(5, 1)], &[(5, 2), (8, 3), (4, 3)]),
+    IfDiffers(8, 4, &[(5, 1)], &[(5, 14), (8, 1), (4, 1)]),
+    IfDiffers(8, 4, &[(5, 3), (2, 1)], &[(5, 5), (4, 2), (2, 1)]),
+    IfDiffers(8, 4, &[(5, 3), (6, 1)], &[(5, 5), (8, 2), (6, 1)]),
+    IfDiffers(8, 4, &[(5, 3), (7, 1)], &[(5, 2), (8, 1), (4, 1)]),
+    IfDiffers(8, 4, &[(5, 3), (7, 1)], &[(5, 2), (8, 3), (4, 3)]),
+    IfDiffers(8, 4, &[(5, 3), (7, 1)], &[(5, 6), (8, 1), (4, 1)]),
+];
+
+/// The indices into [`HQ2X_RULES`] of the 4 cells of the block of each pattern
+pub(super) const HQ2X: [[u8; 4]; 256] = [
+    [5, 2, 9, 6],
+    [5, 2, 9, 6],
+    [1, 4, 9, 6],
+    [15, 4, 9, 6],
+    [5, 2, 9, 6],
+    [5, 2, 9, 6],
+    [1, 16, 9, 6],
+    [15, 16, 9, 6],
+    [0, 2, 8, 6],
+    [13, 2, 8, 6],
+    [31, 4, 8, 6],
+    [28, 4, 8, 6],
+    [0, 2, 8, 6],
+    [13, 2, 8, 6],
+    [32, 34, 8, 6],
+    [29, 34, 8, 6],
+    [5, 3, 9, 11],
+    [5, 3, 9, 11],
+    [1, 23, 9, 11],
+    [26, 24, 9, 11],
+    [5, 13, 9, 11],
+    [5, 13, 9, 11],
+    [1, 20, 9, 11],
+    [26, 21, 9, 11],
+    [0, 3, 8, 11],
+    [13, 3, 8, 11],
+    [28, 20, 8, 11],
+    [28, 14, 8, 11],
+    [0, 13, 8, 11],
+    [13, 13, 8, 11],
+    [12, 20, 8, 11],
+    [28, 20, 8, 11],
+    [5, 2, 9, 6],
+    [5, 2, 9, 6],
+    [1, 4, 9, 6],
+    [15, 4, 9, 6],
+    [5, 2, 9, 6],
+    [5, 2, 9, 6],
+    [1, 16, 9, 6],
+    [15, 16, 9, 6],
+    [0, 2, 18, 6],
+    [13, 2, 18, 6],
+    [32, 4, 35, 6],
+    [29, 4, 35, 6],
+    [0, 2, 18, 6],
+    [13, 2, 18, 6],
+    [33, 16, 18, 6],
+    [30, 16, 18, 6],
+    [5, 3, 9, 11],
+    [5, 3, 9, 11],
+    [1, 23, 9, 11],
+    [26, 24, 9, 11],
+    [5, 13, 9, 11],
+    [5, 13, 9, 11],
+    [1, 20, 9, 11],
+    [26, 21, 9, 11],
+    [0, 3, 18, 11],
+    [13, 3, 18, 11],
+    [33, 25, 18, 11],
+    [28, 25, 18, 11],
+    [0, 13, 18, 11],
+    [13, 13, 18, 11],
+    [12, 20, 18, 11],
+    [30, 20, 18, 11],
+    [5, 2, 7, 10],
+    [5, 2, 7, 10],
+    [1, 4, 7, 10],
+    [15, 4, 7, 10],
+    [5, 2, 7, 10],
+    [5, 2, 7, 10],
+    [1, 16, 7, 10],
+    [15, 16, 7, 10],
+    [0, 2, 49, 10],
+    [47, 2, 50, 10],
+    [28, 4, 44, 10],
+    [28, 4, 17, 10],
+    [0, 2, 49, 10],
+    [47, 2, 50, 10],
+    [33, 16, 51, 10],
+    [28, 16, 51, 10],
+    [5, 3, 7, 41],
+    [5, 3, 7, 41],
+    [1, 20, 7, 36],
+    [15, 25, 7, 43],
+    [5, 39, 7, 42],
+    [5, 39, 7, 42],
+    [1, 20, 7, 19],
+    [15, 20, 7, 43],
+    [0, 3, 44, 36],
+    [13, 3, 51, 43],
+    [33, 25, 51, 43],
+    [28, 25, 51, 43],
+    [0, 13, 51, 43],
+    [13, 13, 51, 43],
+    [33, 20, 51, 43],
+    [28, 20, 17, 19],
+    [5, 2, 15, 10],
+    [5, 2, 15, 10],
+    [1, 4, 15, 10],
+    [15, 4, 15, 10],
+    [5, 2, 15, 10],
+    [5, 2, 15, 10],
+    [1, 16, 15, 10],
+    [15, 16, 15, 10],
+    [0, 2, 44, 10],
+    [47, 2, 45, 10],
+    [12, 4, 44, 10],
+    [28, 4, 44, 10],
+    [0, 2, 44, 10],
+    [47, 2, 45, 10],
+    [12, 16, 44, 10],
+    [30, 16, 44, 10],
+    [5, 3, 40, 42],
+    [5, 3, 40, 42],
+    [1, 25, 15, 43],
+    [15, 25, 15, 43],
+    [5, 13, 15, 43],
+    [5, 13, 15, 43],
+    [1, 20, 15, 19],
+    [26, 21, 15, 19],
+    [0, 3, 44, 19],
+    [13, 3, 44, 43],
+    [33, 25, 44, 43],
+    [28, 14, 44, 19],
+    [0, 13, 44, 19],
+    [47, 13, 45, 19],
+    [12, 20, 44, 19],
+    [30, 20, 44, 19],
+    [5, 2, 9, 6],
+    [5, 2, 9, 6],
+    [1, 4, 9, 6],
+    [15, 4, 9, 6],
+    [5, 2, 9, 6],
+    [5, 2, 9, 6],
+    [1, 16, 9, 6],
+    [15, 16, 9, 6],
+    [0, 2, 8, 6],
+    [13, 2, 8, 6],
+    [31, 4, 8, 6],
+    [28, 4, 8, 6],
+    [0, 2, 8, 6],
+    [13, 2, 8, 6],
+    [32, 34, 8, 6],
+    [29, 34, 8, 6],
+    [5, 3, 9, 18],
+    [5, 3, 9, 18],
+    [1, 24, 9, 27],
+    [15, 25, 9, 18],
+    [5, 13, 9, 18],
+    [5, 13, 9, 18],
+    [1, 21, 9, 27],
+    [15, 22, 9, 18],
+    [0, 3, 8, 18],
+    [13, 3, 8, 18],
+    [33, 25, 8, 18],
+    [28, 14, 8, 18],
+    [0, 13, 8, 18],
+    [13, 13, 8, 18],
+    [33, 20, 8, 18],
+    [28, 22, 8, 18],
+    [5, 2, 9, 6],
+    [5, 2, 9, 6],
+    [1, 4, 9, 6],
+    [15, 4, 9, 6],
+    [5, 2, 9, 6],
+    [5, 2, 9, 6],
+    [1, 16, 9, 6],
+    [15, 16, 9, 6],
+    [0, 2, 18, 6],
+    [13, 2, 18, 6],
+    [32, 4, 35, 6],
+    [29, 4, 35, 6],
+    [0, 2, 18, 6],
+    [13, 2, 18, 6],
+    [33, 16, 18, 6],
+    [30, 16, 18, 6],
+    [5, 3, 9, 18],
+    [5, 3, 9, 18],
+    [1, 24, 9, 27],
+    [15, 25, 9, 18],
+    [5, 13, 9, 18],
+    [5, 13, 9, 18],
+    [1, 21, 9, 27],
+    [15, 22, 9, 18],
+    [0, 3, 18, 18],
+    [13, 3, 18, 18],
+    [33, 25, 18, 18],
+    [29, 14, 35, 18],
+    [0, 13, 18, 18],
+    [13, 13, 18, 18],
+    [12, 21, 18, 27],
+    [30, 22, 18, 18],
+    [5, 2, 7, 16],
+    [5, 2, 7, 16],
+    [1, 4, 7, 16],
+    [15, 4, 7, 16],
+    [5, 2, 7, 16],
+    [5, 2, 7, 16],
+    [1, 16, 7, 16],
+    [15, 16, 7, 16],
+    [0, 2, 50, 48],
+    [13, 2, 51, 16],
+    [33, 4, 51, 16],
+    [28, 4, 17, 16],
+    [0, 2, 50, 48],
+    [13, 2, 51, 16],
+    [33, 16, 51, 16],
+    [29, 34, 17, 16],
+    [5, 3, 7, 36],
+    [5, 3, 7, 36],
+    [1, 14, 7, 36],
+    [15, 14, 7, 36],
+    [5, 39, 7, 37],
+    [5, 39, 7, 37],
+    [1, 20, 7, 36],
+    [15, 22, 7, 36],
+    [0, 3, 17, 36],
+    [13, 3, 17, 36],
+    [33, 25, 51, 36],
+    [28, 14, 17, 36],
+    [0, 13, 51, 36],
+    [13, 39, 17, 37],
+    [12, 20, 17, 36],
+    [28, 22, 17, 36],
+    [5, 2, 15, 16],
+    [5, 2, 15, 16],
+    [1, 4, 15, 16],
+    [15, 4, 15, 16],
+    [5, 2, 15, 16],
+    [5, 2, 15, 16],
+    [1, 16, 15, 16],
+    [15, 16, 15, 16],
+    [0, 2, 45, 48],
+    [13, 2, 46, 16],
+    [33, 4, 44, 16],
+    [28, 4, 46, 16],
+    [0, 2, 45, 48],
+    [13, 2, 46, 16],
+    [12, 16, 45, 48],
+    [30, 16, 46, 16],
+    [5, 3, 40, 37],
+    [5, 3, 40, 37],
+    [1, 25, 15, 36],
+    [15, 14, 40, 37],
+    [5, 13, 15, 38],
+    [5, 13, 15, 38],
+    [1, 20, 15, 38],
+    [15, 22, 15, 38],
+    [0, 3, 44, 36],
+    [13, 3, 46, 36],
+    [12, 14, 44, 36],
+    [28, 14, 46, 36],
+    [0, 13, 44, 38],
+    [13, 13, 46, 38],
+    [12, 20, 44, 38],
+    [30, 22, 46, 38],
+];
+
+/// The distinct rules of hq3x, indexed by [`HQ3X`]
+pub(super) const HQ3X_RULES: [Rule; 69] = [
+    Mix(&[(5, 1)]),
+    Mix(&[(5, 2), (2, 1), (6, 1)]),
+    Mix(&[(5, 2), (4, 1), (2, 1)]),
+    Mix(&[(5, 2), (6, 1), (8, 1)]),
+    Mix(&[(5, 2), (8, 1), (4, 1)]),
+    Mix(&[(5, 3), (1, 1)]),
+    Mix(&[(5, 3), (2, 1)]),
+    Mix(&[(5, 3), (3, 1)]),
+    Mix(&[(5, 3), (4, 1)]),
+    Mix(&[(5, 3), (6, 1)]),
+    Mix(&[(5, 3), (7, 1)]),
+    Mix(&[(5, 3), (8, 1)]),
+    Mix(&[(5, 3), (9, 1)]),
+    IfDiffers(2, 6, &[(5, 1)], &[(2, 1), (6, 1)]),
+    IfDiffers(2, 6, &[(5, 1)], &[(2, 3), (5, 1)]),
+    IfDiffers(2, 6, &[(5, 1)], &[(5, 2), (2, 1), (6, 1)]),
+    IfDiffers(2, 6, &[(5, 1)], &[(5, 2), (2, 7), (6, 7)]),
+    IfDiffers(2, 6, &[(5, 1)], &[(5, 3), (2, 1)]),
+    IfDiffers(2, 6, &[(5, 1)], &[(5, 3), (6, 1)]),
+    IfDiffers(2, 6, &[(5, 1)], &[(5, 7), (2, 1)]),
+    IfDiffers(2, 6, &[(5, 1)], &[(5, 7), (6, 1)]),
+    IfDiffers(2, 6, &[(5, 1)], &[(6, 3), (5, 1)]),
+    IfDiffers(2, 6, &[(5, 3), (3, 1)], &[(2, 1), (6, 1)]),
+    IfDiffers(2, 6, &[(5, 3), (3, 1)], &[(5, 2), (2, 1), (6, 1)]),
+    IfDiffers(2, 6, &[(5, 3), (3, 1)], &[(5, 2), (2, 7), (6, 7)]),
+    IfDiffers(2, 6, &[(5, 3), (4, 1)], &[(5, 2), (4, 1), (2, 1)]),
+    IfDiffers(2, 6, &[(5, 3), (8, 1)], &[(5, 2), (6, 1), (8, 1)]),
+    IfDiffers(4, 2, &[(5, 1)], &[(2, 3), (5, 1)]),
+    IfDiffers(4, 2, &[(5, 1)], &[(4, 1), (2, 1)]),
+    IfDiffers(4, 2, &[(5, 1)], &[(4, 3), (5, 1)]),
+    IfDiffers(4, 2, &[(5, 1)], &[(5, 2), (4, 1), (2, 1)]),
+    IfDiffers(4, 2, &[(5, 1)], &[(5, 2), (4, 7), (2, 7)]),
+    IfDiffers(4, 2, &[(5, 1)], &[(5, 3), (2, 1)]),
+    IfDiffers(4, 2, &[(5, 1)], &[(5, 3), (4, 1)]),
+    IfDiffers(4, 2, &[(5, 1)], &[(5, 7), (2, 1)]),
+    IfDiffers(4, 2, &[(5, 1)], &[(5, 7), (4, 1)]),
+    IfDiffers(4, 2, &[(5, 3), (1, 1)], &[(4, 1), (2, 1)]),
+    IfDiffers(4, 2, &[(5, 3), (1, 1)], &[(5, 2), (4, 1), (2, 1)]),
+    IfDiffers(4, 2, &[(5, 3), (1, 1)], &[(5, 2), (4, 7), (2, 7)]),
+    IfDiffers(4, 2, &[(5, 3), (6, 1)], &[(5, 2), (2, 1), (6, 1)]),
+    IfDiffers(4, 2, &[(5, 3), (8, 1)], &[(5, 2), (8, 1), (4, 1)]),
+    IfDiffers(6, 8, &[(5, 1)], &[(5, 2), (6, 1), (8, 1)]),
+    IfDiffers(6, 8, &[(5, 1)], &[(5, 2), (6, 7), (8, 7)]),
+    IfDiffers(6, 8, &[(5, 1)], &[(5, 3), (6, 1)]),
+    IfDiffers(6, 8, &[(5, 1)], &[(5, 3), (8, 1)]),
+    IfDiffers(6, 8, &[(5, 1)], &[(5, 7), (6, 1)]),
+    IfDiffers(6, 8, &[(5, 1)], &[(5, 7), (8, 1)]),
+    IfDiffers(6, 8, &[(5, 1)], &[(6, 1), (8, 1)]),
+    IfDiffers(6, 8, &[(5, 1)], &[(6, 3), (5, 1)]),
+    IfDiffers(6, 8, &[(5, 1)], &[(8, 3), (5, 1)]),
+    IfDiffers(6, 8, &[(5, 3), (2, 1)], &[(5, 2), (2, 1), (6, 1)]),
+    IfDiffers(6, 8, &[(5, 3), (4, 1)], &[(5, 2), (8, 1), (4, 1)]),
+    IfDiffers(6, 8, &[(5, 3), (9, 1)], &[(5, 2), (6, 1), (8, 1)]),
+    IfDiffers(6, 8, &[(5, 3), (9, 1)], &[(5, 2), (6, 7), (8, 7)]),
+    IfDiffers(6, 8, &[(5, 3), (9, 1)], &[(6, 1), (8, 1)]),
+    IfDiffers(8, 4, &[(5, 1)], &[(4, 3), (5, 1)]),
+    IfDiffers(8, 4, &[(5, 1)], &[(5, 2), (8, 1), (4, 1)]),
+    IfDiffers(8, 4, &[(5, 1)], &[(5, 2), (8, 7), (4, 7)]),
+    IfDiffers(8, 4, &[(5, 1)], &[(5, 3), (4, 1)]),
+    IfDiffers(8, 4, &[(5, 1)], &[(5, 3), (8, 1)]),
+    IfDiffers(8, 4, &[(5, 1)], &[(5, 7), (4, 1)]),
+    IfDiffers(8, 4, &[(5, 1)], &[(5, 7), (8, 1)]),
+    IfDiffers(8, 4, &[(5, 1)], &[(8, 1), (4, 1)]),
+    IfDiffers(8, 4, &[(5, 1)], &[(8, 3), (5, 1)]),
+    IfDiffers(8, 4, &[(5, 3), (2, 1)], &[(5, 2), (4, 1), (2, 1)]),
+    IfDiffers(8, 4, &[(5, 3), (6, 1)], &[(5, 2), (6, 1), (8, 1)]),
+    IfDiffers(8, 4, &[(5, 3), (7, 1)], &[(5, 2), (8, 1), (4, 1)]),
+    IfDiffers(8, 4, &[(5, 3), (7, 1)], &[(5, 2), (8, 7), (4, 7)]),
+    IfDiffers(8, 4, &[(5, 3), (7, 1)], &[(8, 1), (4, 1)]),
+];
+
+/// The indices into [`HQ3X_RULES`] of the 9 cells of the block of each pattern
+pub(super) const HQ3X: [[u8; 9]; 256] = [
+    [2, 6, 1, 8, 0, 9, 4, 11, 3],
+    [2, 6, 1, 8, 0, 9, 4, 11, 3],
+    [5, 0, 7, 8, 0, 9, 4, 11, 3],
+    [8, 0, 7, 8, 0, 9, 4, 11, 3],
+    [2, 6, 1, 8, 0, 9, 4, 11, 3],
+    [2, 6, 1, 8, 0, 9, 4, 11, 3],
+    [5, 0, 9, 8, 0, 9, 4, 11, 3],
+    [8, 0, 9, 8, 0, 9, 4, 11, 3],
+    [5, 6, 1, 0, 0, 9, 10, 11, 3],
+    [6, 6, 1, 0, 0, 9, 10, 11, 3],
+    [38, 34, 7, 35, 0, 9, 10, 11, 3],
+    [31, 34, 7, 35, 0, 9, 10, 11, 3],
+    [5, 6, 1, 0, 0, 9, 10, 11, 3],
+    [6, 6, 1, 0, 0, 9, 10, 11, 3],
+    [36, 27, 39, 33, 0, 9, 10, 11, 3],
+    [28, 27, 39, 33, 0, 9, 10, 11, 3],
+    [2, 6, 7, 8, 0, 0, 4, 11, 12],
+    [2, 6, 7, 8, 0, 0, 4, 11, 12],
+    [5, 19, 24, 8, 0, 20, 4, 11, 12],
+    [25, 14, 22, 8, 0, 18, 4, 11, 12],
+    [2, 6, 6, 8, 0, 0, 4, 11, 12],
+    [2, 6, 6, 8, 0, 0, 4, 11, 12],
+    [5, 19, 16, 8, 0, 20, 4, 11, 12],
+    [25, 14, 13, 8, 0, 18, 4, 11, 12],
+    [5, 6, 7, 0, 0, 0, 10, 11, 12],
+    [6, 6, 7, 0, 0, 0, 10, 11, 12],
+    [31, 0, 16, 35, 0, 20, 10, 11, 12],
+    [31, 34, 7, 35, 0, 0, 10, 11, 12],
+    [5, 6, 6, 0, 0, 0, 10, 11, 12],
+    [6, 6, 6, 0, 0, 0, 10, 11, 12],
+    [5, 19, 16, 0, 0, 20, 10, 11, 12],
+    [31, 0, 16, 35, 0, 20, 10, 11, 12],
+    [2, 6, 1, 8, 0, 9, 4, 11, 3],
+    [2, 6, 1, 8, 0, 9, 4, 11, 3],
+    [5, 0, 7, 8, 0, 9, 4, 11, 3],
+    [8, 0, 7, 8, 0, 9, 4, 11, 3],
+    [2, 6, 1, 8, 0, 9, 4, 11, 3],
+    [2, 6, 1, 8, 0, 9, 4, 11, 3],
+    [5, 0, 9, 8, 0, 9, 4, 11, 3],
+    [8, 0, 9, 8, 0, 9, 4, 11, 3],
+    [5, 6, 1, 0, 0, 9, 11, 11, 3],
+    [6, 6, 1, 0, 0, 9, 11, 11, 3],
+    [36, 32, 7, 29, 0, 9, 40, 11, 3],
+    [28, 32, 7, 29, 0, 9, 40, 11, 3],
+    [5, 6, 1, 0, 0, 9, 11, 11, 3],
+    [6, 6, 1, 0, 0, 9, 11, 11, 3],
+    [37, 0, 9, 0, 0, 9, 11, 11, 3],
+    [30, 0, 9, 0, 0, 9, 11, 11, 3],
+    [2, 6, 7, 8, 0, 0, 4, 11, 12],
+    [2, 6, 7, 8, 0, 0, 4, 11, 12],
+    [5, 19, 24, 8, 0, 20, 4, 11, 12],
+    [25, 14, 22, 8, 0, 18, 4, 11, 12],
+    [2, 6, 6, 8, 0, 0, 4, 11, 12],
+    [2, 6, 6, 8, 0, 0, 4, 11, 12],
+    [5, 19, 16, 8, 0, 20, 4, 11, 12],
+    [25, 14, 13, 8, 0, 18, 4, 11, 12],
+    [5, 6, 7, 0, 0, 0, 11, 11, 12],
+    [6, 6, 7, 0, 0, 0, 11, 11, 12],
+    [37, 0, 23, 0, 0, 0, 11, 11, 12],
+    [31, 34, 23, 35, 0, 0, 11, 11, 12],
+    [5, 6, 6, 0, 0, 0, 11, 11, 12],
+    [6, 6, 6, 0, 0, 0, 11, 11, 12],
+    [5, 19, 16, 0, 0, 20, 11, 11, 12],
+    [30, 0, 16, 0, 0, 20, 11, 11, 12],
+    [2, 6, 1, 8, 0, 9, 10, 0, 12],
+    [2, 6, 1, 8, 0, 9, 10, 0, 12],
+    [5, 0, 7, 8, 0, 9, 10, 0, 12],
+    [8, 0, 7, 8, 0, 9, 10, 0, 12],
+    [2, 6, 1, 8, 0, 9, 10, 0, 12],
+    [2, 6, 1, 8, 0, 9, 10, 0, 12],
+    [5, 0, 9, 8, 0, 9, 10, 0, 12],
+    [8, 0, 9, 8, 0, 9, 10, 0, 12],
+    [5, 6, 1, 60, 0, 9, 67, 61, 12],
+    [64, 6, 1, 55, 0, 9, 68, 59, 12],
+    [31, 34, 7, 0, 0, 9, 57, 61, 12],
+    [31, 34, 7, 35, 0, 9, 10, 0, 12],
+    [5, 6, 1, 60, 0, 9, 67, 61, 12],
+    [64, 6, 1, 55, 0, 9, 68, 59, 12],
+    [37, 0, 9, 0, 0, 9, 66, 0, 12],
+    [31, 34, 9, 35, 0, 9, 66, 0, 12],
+    [2, 6, 7, 8, 0, 45, 10, 46, 53],
+    [2, 6, 7, 8, 0, 45, 10, 46, 53],
+    [5, 19, 16, 8, 0, 0, 10, 46, 42],
+    [8, 0, 23, 8, 0, 0, 10, 0, 52],
+    [2, 6, 50, 8, 0, 48, 10, 44, 54],
+    [2, 6, 50, 8, 0, 48, 10, 44, 54],
+    [5, 19, 16, 8, 0, 20, 10, 0, 12],
+    [8, 19, 16, 8, 0, 20, 10, 0, 52],
+    [5, 6, 7, 60, 0, 45, 57, 0, 42],
+    [6, 6, 7, 0, 0, 0, 66, 0, 52],
+    [37, 0, 23, 0, 0, 0, 66, 0, 52],
+    [31, 34, 23, 35, 0, 0, 66, 0, 52],
+    [5, 6, 6, 0, 0, 0, 66, 0, 52],
+    [6, 6, 6, 0, 0, 0, 66, 0, 52],
+    [37, 19, 16, 0, 0, 20, 66, 0, 52],
+    [31, 0, 16, 35, 0, 20, 10, 0, 12],
+    [2, 6, 1, 8, 0, 9, 8, 0, 12],
+    [2, 6, 1, 8, 0, 9, 8, 0, 12],
+    [5, 0, 7, 8, 0, 9, 8, 0, 12],
+    [8, 0, 7, 8, 0, 9, 8, 0, 12],
+    [2, 6, 1, 8, 0, 9, 8, 0, 12],
+    [2, 6, 1, 8, 0, 9, 8, 0, 12],
+    [5, 0, 9, 8, 0, 9, 8, 0, 12],
+    [8, 0, 9, 8, 0, 9, 8, 0, 12],
+    [5, 6, 1, 60, 0, 9, 57, 61, 12],
+    [64, 6, 1, 55, 0, 9, 62, 59, 12],
+    [5, 0, 7, 60, 0, 9, 57, 61, 12],
+    [31, 34, 7, 0, 0, 9, 57, 61, 12],
+    [5, 6, 1, 60, 0, 9, 57, 61, 12],
+    [64, 6, 1, 55, 0, 9, 62, 59, 12],
+    [5, 0, 9, 60, 0, 9, 57, 61, 12],
+    [30, 0, 9, 0, 0, 9, 57, 61, 12],
+    [2, 6, 7, 8, 0, 43, 51, 49, 54],
+    [2, 6, 7, 8, 0, 43, 51, 49, 54],
+    [5, 0, 23, 8, 0, 0, 8, 0, 52],
+    [8, 0, 23, 8, 0, 0, 8, 0, 52],
+    [2, 6, 6, 8, 0, 0, 8, 0, 52],
+    [2, 6, 6, 8, 0, 0, 8, 0, 52],
+    [5, 19, 16, 8, 0, 20, 8, 0, 12],
+    [25, 14, 13, 8, 0, 18, 8, 0, 12],
+    [5, 6, 7, 60, 0, 0, 57, 61, 12],
+    [6, 6, 7, 60, 0, 0, 57, 61, 52],
+    [37, 0, 23, 60, 0, 0, 57, 61, 52],
+    [31, 34, 7, 0, 0, 0, 57, 61, 12],
+    [5, 6, 6, 60, 0, 0, 57, 61, 12],
+    [64, 6, 6, 55, 0, 0, 62, 59, 12],
+    [5, 19, 16, 60, 0, 20, 57, 61, 12],
+    [30, 34, 16, 35, 0, 20, 57, 61, 12],
+    [2, 6, 1, 8, 0, 9, 4, 11, 3],
+    [2, 6, 1, 8, 0, 9, 4, 11, 3],
+    [5, 0, 7, 8, 0, 9, 4, 11, 3],
+    [8, 0, 7, 8, 0, 9, 4, 11, 3],
+    [2, 6, 1, 8, 0, 9, 4, 11, 3],
+    [2, 6, 1, 8, 0, 9, 4, 11, 3],
+    [5, 0, 9, 8, 0, 9, 4, 11, 3],
+    [8, 0, 9, 8, 0, 9, 4, 11, 3],
+    [5, 6, 1, 0, 0, 9, 10, 11, 3],
+    [6, 6, 1, 0, 0, 9, 10, 11, 3],
+    [38, 34, 7, 35, 0, 9, 10, 11, 3],
+    [31, 34, 7, 35, 0, 9, 10, 11, 3],
+    [5, 6, 1, 0, 0, 9, 10, 11, 3],
+    [6, 6, 1, 0, 0, 9, 10, 11, 3],
+    [36, 27, 39, 33, 0, 9, 10, 11, 3],
+    [28, 27, 39, 33, 0, 9, 10, 11, 3],
+    [2, 6, 7, 8, 0, 0, 4, 11, 11],
+    [2, 6, 7, 8, 0, 0, 4, 11, 11],
+    [5, 17, 22, 8, 0, 21, 4, 11, 26],
+    [8, 0, 23, 8, 0, 0, 4, 11, 11],
+    [2, 6, 6, 8, 0, 0, 4, 11, 11],
+    [2, 6, 6, 8, 0, 0, 4, 11, 11],
+    [5, 17, 13, 8, 0, 21, 4, 11, 26],
+    [8, 0, 15, 8, 0, 0, 4, 11, 11],
+    [5, 6, 7, 0, 0, 0, 10, 11, 11],
+    [6, 6, 7, 0, 0, 0, 10, 11, 11],
+    [37, 0, 23, 0, 0, 0, 10, 11, 11],
+    [31, 34, 7, 35, 0, 0, 10, 11, 11],
+    [5, 6, 6, 0, 0, 0, 10, 11, 11],
+    [6, 6, 6, 0, 0, 0, 10, 11, 11],
+    [37, 19, 16, 0, 0, 20, 10, 11, 11],
+    [31, 0, 15, 35, 0, 0, 10, 11, 11],
+    [2, 6, 1, 8, 0, 9, 4, 11, 3],
+    [2, 6, 1, 8, 0, 9, 4, 11, 3],
+    [5, 0, 7, 8, 0, 9, 4, 11, 3],
+    [8, 0, 7, 8, 0, 9, 4, 11, 3],
+    [2, 6, 1, 8, 0, 9, 4, 11, 3],
+    [2, 6, 1, 8, 0, 9, 4, 11, 3],
+    [5, 0, 9, 8, 0, 9, 4, 11, 3],
+    [8, 0, 9, 8, 0, 9, 4, 11, 3],
+    [5, 6, 1, 0, 0, 9, 11, 11, 3],
+    [6, 6, 1, 0, 0, 9, 11, 11, 3],
+    [36, 32, 7, 29, 0, 9, 40, 11, 3],
+    [28, 32, 7, 29, 0, 9, 40, 11, 3],
+    [5, 6, 1, 0, 0, 9, 11, 11, 3],
+    [6, 6, 1, 0, 0, 9, 11, 11, 3],
+    [37, 0, 9, 0, 0, 9, 11, 11, 3],
+    [30, 0, 9, 0, 0, 9, 11, 11, 3],
+    [2, 6, 7, 8, 0, 0, 4, 11, 11],
+    [2, 6, 7, 8, 0, 0, 4, 11, 11],
+    [5, 17, 22, 8, 0, 21, 4, 11, 26],
+    [8, 0, 23, 8, 0, 0, 4, 11, 11],
+    [2, 6, 6, 8, 0, 0, 4, 11, 11],
+    [2, 6, 6, 8, 0, 0, 4, 11, 11],
+    [5, 17, 13, 8, 0, 21, 4, 11, 26],
+    [8, 0, 15, 8, 0, 0, 4, 11, 11],
+    [5, 6, 7, 0, 0, 0, 11, 11, 11],
+    [6, 6, 7, 0, 0, 0, 11, 11, 11],
+    [37, 0, 23, 0, 0, 0, 11, 11, 11],
+    [28, 32, 7, 29, 0, 0, 40, 11, 11],
+    [5, 6, 6, 0, 0, 0, 11, 11, 11],
+    [6, 6, 6, 0, 0, 0, 11, 11, 11],
+    [5, 17, 13, 0, 0, 21, 11, 11, 26],
+    [30, 0, 15, 0, 0, 0, 11, 11, 11],
+    [2, 6, 1, 8, 0, 9, 10, 0, 9],
+    [2, 6, 1, 8, 0, 9, 10, 0, 9],
+    [5, 0, 7, 8, 0, 9, 10, 0, 9],
+    [8, 0, 7, 8, 0, 9, 10, 0, 9],
+    [2, 6, 1, 8, 0, 9, 10, 0, 9],
+    [2, 6, 1, 8, 0, 9, 10, 0, 9],
+    [5, 0, 9, 8, 0, 9, 10, 0, 9],
+    [8, 0, 9, 8, 0, 9, 10, 0, 9],
+    [5, 6, 1, 58, 0, 9, 68, 63, 65],
+    [6, 6, 1, 0, 0, 9, 66, 0, 9],
+    [37, 0, 7, 0, 0, 9, 66, 0, 9],
+    [31, 34, 7, 35, 0, 9, 10, 0, 9],
+    [5, 6, 1, 58, 0, 9, 68, 63, 65],
+    [6, 6, 1, 0, 0, 9, 66, 0, 9],
+    [37, 0, 9, 0, 0, 9, 66, 0, 9],
+    [28, 27, 39, 33, 0, 9, 10, 0, 9],
+    [2, 6, 7, 8, 0, 45, 10, 46, 42],
+    [2, 6, 7, 8, 0, 45, 10, 46, 42],
+    [5, 0, 7, 8, 0, 45, 10, 46, 42],
+    [8, 0, 7, 8, 0, 45, 10, 46, 42],
+    [2, 6, 50, 8, 0, 48, 10, 44, 47],
+    [2, 6, 50, 8, 0, 48, 10, 44, 47],
+    [5, 19, 16, 8, 0, 0, 10, 46, 42],
+    [8, 0, 15, 8, 0, 0, 10, 46, 42],
+    [5, 6, 7, 0, 0, 45, 10, 46, 42],
+    [6, 6, 7, 0, 0, 45, 10, 46, 42],
+    [37, 0, 23, 0, 0, 45, 66, 46, 42],
+    [31, 34, 7, 35, 0, 45, 10, 46, 42],
+    [5, 6, 6, 0, 0, 45, 66, 46, 42],
+    [6, 6, 50, 0, 0, 48, 10, 44, 47],
+    [5, 19, 16, 0, 0, 0, 10, 46, 42],
+    [31, 19, 15, 35, 0, 20, 10, 46, 42],
+    [2, 6, 1, 8, 0, 9, 8, 0, 9],
+    [2, 6, 1, 8, 0, 9, 8, 0, 9],
+    [5, 0, 7, 8, 0, 9, 8, 0, 9],
+    [8, 0, 7, 8, 0, 9, 8, 0, 9],
+    [2, 6, 1, 8, 0, 9, 8, 0, 9],
+    [2, 6, 1, 8, 0, 9, 8, 0, 9],
+    [5, 0, 9, 8, 0, 9, 8, 0, 9],
+    [8, 0, 9, 8, 0, 9, 8, 0, 9],
+    [5, 6, 1, 58, 0, 9, 62, 63, 65],
+    [6, 6, 1, 0, 0, 9, 56, 0, 9],
+    [37, 0, 7, 60, 0, 9, 57, 61, 9],
+    [31, 34, 7, 0, 0, 9, 56, 0, 9],
+    [5, 6, 1, 58, 0, 9, 62, 63, 65],
+    [6, 6, 1, 0, 0, 9, 56, 0, 9],
+    [5, 0, 9, 58, 0, 9, 62, 63, 65],
+    [30, 0, 9, 0, 0, 9, 56, 0, 9],
+    [2, 6, 7, 8, 0, 43, 51, 49, 47],
+    [2, 6, 7, 8, 0, 43, 51, 49, 47],
+    [5, 0, 23, 8, 0, 45, 8, 46, 42],
+    [8, 0, 7, 8, 0, 43, 51, 49, 47],
+    [2, 6, 6, 8, 0, 0, 8, 0, 41],
+    [2, 6, 6, 8, 0, 0, 8, 0, 41],
+    [5, 19, 16, 8, 0, 0, 8, 0, 41],
+    [8, 0, 15, 8, 0, 0, 8, 0, 41],
+    [5, 6, 7, 60, 0, 45, 57, 0, 42],
+    [6, 6, 7, 0, 0, 45, 56, 0, 42],
+    [5, 0, 7, 60, 0, 45, 57, 0, 42],
+    [31, 34, 7, 60, 0, 45, 56, 61, 42],
+    [5, 6, 6, 60, 0, 0, 57, 0, 41],
+    [6, 6, 6, 0, 0, 0, 56, 0, 41],
+    [5, 19, 16, 60, 0, 45, 57, 46, 41],
+    [30, 0, 15, 0, 0, 0, 56, 0, 41],
+];
@@ -0,0 +1,107 @@
+use crate::{
+    file::project_file::ProjectData,
+    project::{self, Palette},
+    types::{BlendMode, Coord, IndexedPixel, PCoord, Pixel, TruePixel, UCoord},
+};
+
+use std::collections::HashMap;
+
+/// The layout of a [`Project`](project::Project) in version 0 Project files, which were plain
+/// savefile dumps of it
+///
+/// These structs are frozen as they were when version 0 files were written, savefile reads the
+/// fields in the order they are declared here & so they must never change.
+#[derive(Savefile)]
+pub(crate) struct Project {
+    canvas: Canvas,
+    out_dim: PCoord,
+    focus: (Coord, u16),
+    out_mul: u8,
+    out_repeat: PCoord,
+    cursors: HashMap<(UCoord, u16), ()>,
+    num_cursors: u64,
+    sel_cursor: Option<(UCoord, u16)>,
+}
+
+#[derive(Savefile)]
+struct Canvas {
+    layers: LayersType,
+    palette: Palette,
+}
+
+#[derive(Savefile)]
+enum LayersType {
+    True(Layers<TruePixel>),
+    Indexed(Layers<IndexedPixel>),
+}
+
+#[derive(Savefile)]
+struct Layers<T: Pixel> {
+    layers: Vec<Layer<T>>,
+    dimensions: PCoord,
+}
+
+#[rustfmt::skip] //Savefile's macro fails on rustfmt appending a comma to the where clause
+#[derive(Savefile)]
+struct Layer<T=TruePixel>
+where T: Pixel
+{
+    scene: Scene<T>,
+    opacity: u8,
+    mute: bool,
+    blend_mode: BlendMode,
+}
+
+/// A Scene as a flattened grid of optional pixels, before Scenes were stored in tiles
+#[rustfmt::skip] //Savefile's macro fails on rustfmt appending a comma to the where clause
+#[derive(Savefile)]
+struct Scene<T=TruePixel>
+where T: Pixel
+{
+    dim: PCoord,
+    grid: Vec<Option<T>>,
+}
+
+impl<T: Pixel> Layers<T> {
+    fn convert(self) -> Result<project::Layers<T>, String> {
+        let mut layers = project::Layers::new(self.dimensions);
+        for layer in self.layers {
+            layers
+                .add_layer(project::Layer {
+                    scene: project::Scene::new(layer.scene.dim, layer.scene.grid)
+                        .map_err(|err| err.to_string())?,
+                    opacity: layer.opacity,
+                    mute: layer.mute,
+                    blend_mode: layer.blend_mode,
+                })
+                .map_err(|err| err.to_string())?;
+        }
+        Ok(layers)
+    }
+}
+
+impl TryFrom<Project> for ProjectData {
+    type Error = String;
+
+    fn try_from(project: Project) -> Result<ProjectData, String> {
+        let mut cursors = project.cursors.into_keys().collect::<Vec<(UCoord, u16)>>();
+        cursors.sort_by_key(|(coord, layer)| (*layer, coord.x, coord.y));
+
+        Ok(ProjectData {
+            canvas: project::Canvas {
+                layers: match project.canvas.layers {
+                    LayersType::True(layers) => project::LayersType::True(layers.convert()?),
+                    LayersType::Indexed(layers) => project::LayersType::Indexed(layers.convert()?),
+                },
+                palette: project.canvas.palette,
+            },
+            out_dim: project.out_dim,
+            focus: project.focus,
+            out_mul: project.out_mul,
+            out_repeat: project.out_repeat,
+            cursors,
+            cursor_registers: HashMap::new(),
+            history: None,
+        })
+    }
+}
@@ -1,8 +1,17 @@
 mod png_file;
 pub use png_file::{PngFile, PngFileError};
 
+mod hqx;
+mod upscale;
+pub use upscale::Upscaler;
+
+mod downsample;
+pub use downsample::Downsampler;
+
+mod legacy;
+
 mod project_file;
 pub use project_file::{ProjectFile, ProjectFileError};
 
 mod canvas_file;
-pub use canvas_file::{CanvasFile, CanvasFileError};
+pub use canvas_file::{CanvasFile, CanvasFileError, SceneEncoding};
@@ -1,3 +1,7 @@
+use super::{
+    downsample::{detect_pixel_size, Downsampler},
+    Upscaler,
+};
 use crate::{
     project::{Canvas, Layer, LayersType, Palette, Scene},
     types::{BlendMode, IndexedPixel, PCoord, Pixel, TruePixel, UCoord},
@@ -13,6 +17,7 @@ pub struct PngFile {
     bit_depth: BitDepth,
     bytes: Vec<u8>,
     palette: Option<Vec<u8>>,
+    trns: Option<Vec<u8>>,
 }
 
 impl PngFile {
@@ -37,6 +42,7 @@ impl PngFile {
             bit_depth: info.bit_depth,
             bytes,
             palette: reader.info().palette.clone().map(|p| Vec::from(p)),
+            trns: reader.info().trns.clone().map(Vec::from),
         })
     }
 
@@ -52,6 +58,9 @@ impl PngFile {
         if let Some(palette) = &self.palette {
             encoder.set_palette(palette);
         }
+        if let Some(trns) = &self.trns {
+            encoder.set_trns(trns);
+        }
 
         let mut writer = encoder
             .write_header()
@@ -90,6 +99,7 @@ impl PngFile {
                     bit_depth: BitDepth::Eight,
                     bytes,
                     palette: None,
+                    trns: None,
                 })
             }
             LayersType::Indexed(_) => {
@@ -114,15 +124,20 @@ impl PngFile {
                 let palette_len = palette_map.iter().map(|(id, _)| *id).max().unwrap_or(0);
 
                 let mut palette = Vec::new();
+                let mut trns = Vec::new();
                 for i in 0..(palette_len + 1) {
-                    if let Some(TruePixel { r, g, b, .. }) = palette_map.get(&i) {
+                    if let Some(TruePixel { r, g, b, a }) = palette_map.get(&i) {
                         palette.push(*r);
                         palette.push(*g);
                         palette.push(*b);
+                        trns.push(*a);
                     } else {
                         palette.extend_from_slice(&[0, 0, 0]);
+                        trns.push(255);
                     }
                 }
+                //the alpha of every palette entry is only written if any of them is translucent
+                let trns = trns.iter().any(|a| *a != 255).then_some(trns);
 
                 Ok(Self {
                     height: dim.x().into(),
@@ -131,6 +146,7 @@ impl PngFile {
                     bit_depth: BitDepth::Eight,
                     bytes,
                     palette: Some(palette),
+                    trns,
                 })
             }
         }
@@ -151,8 +167,7 @@ impl PngFile {
 
         match (self.color_type, self.bit_depth) {
             (Rgb, Eight) => {
-                let mut scene =
-                    Scene::<TruePixel>::new(dim, vec![None; dim.area() as usize]).unwrap(); //wont fail because same dim used in both parameters
+                let mut scene = Scene::<TruePixel>::new_empty(dim);
                 for i in 0..scene.dim().x() as usize {
                     for j in 0..scene.dim().y() as usize {
                         scene
@@ -186,8 +201,7 @@ impl PngFile {
                 })
             }
             (Rgba, Eight) => {
-                let mut scene =
-                    Scene::<TruePixel>::new(dim, vec![None; dim.area() as usize]).unwrap(); //wont fail because same dim used in both parameters
+                let mut scene = Scene::<TruePixel>::new_empty(dim);
                 for i in 0..scene.dim().x() as usize {
                     for j in 0..scene.dim().y() as usize {
                         scene
@@ -221,8 +235,7 @@ impl PngFile {
                 })
             }
             (Indexed, Eight) => {
-                let mut scene =
-                    Scene::<IndexedPixel>::new(dim, vec![None; dim.area() as usize]).unwrap(); //wont fail because same dim used in both parameters
+                let mut scene = Scene::<IndexedPixel>::new_empty(dim);
                 for i in 0..scene.dim().x() as usize {
                     for j in 0..scene.dim().y() as usize {
                         scene
@@ -255,11 +268,12 @@ impl PngFile {
                             .iter()
                             .chunks(3)
                             .into_iter()
-                            .map(|mut p| TruePixel {
+                            .enumerate()
+                            .map(|(i, mut p)| TruePixel {
                                 r: *p.next().unwrap_or(&0),
                                 g: *p.next().unwrap_or(&0),
                                 b: *p.next().unwrap_or(&0),
-                                a: 255,
+                                a: self.alpha_at(i),
                             })
                             .collect::<Vec<TruePixel>>(),
                     ),
@@ -321,6 +335,7 @@ impl PngFile {
             (Rgb, Sixteen) => 3 * 2,
             (Rgba, Eight) => 4 * 1,
             (Rgba, Sixteen) => 4 * 2,
+            (Indexed, Eight) => 1,
             (ct, bd) => {
                 return Err(Unsupported(ct, bd));
             }
@@ -336,8 +351,8 @@ impl PngFile {
 
         match Self::enlarge_matrix(
             &mut folded_bytes,
-            self.width,
             self.height,
+            self.width,
             (new_width / self.width, new_height / self.height),
         ) {
             Err(1) => {
@@ -442,7 +457,7 @@ impl PngFile {
                     .map_err(|err| ResizeError(err))?;
                 _ = replace(
                     &mut self.bytes,
-                    out.iter().map(|p| vec![p.r, p.g, p.b]).flatten().collect(),
+                    out.iter().flat_map(|p| vec![p.r, p.g, p.b]).collect(),
                 );
                 self.width = new_width;
                 self.height = new_height;
@@ -486,10 +501,8 @@ impl PngFile {
                 _ = replace(
                     &mut self.bytes,
                     out.iter()
-                        .map(|p| vec![p.r, p.g, p.b])
-                        .flatten()
-                        .map(|sixteen| vec![(sixteen) as u8, (sixteen >> 8) as u8])
-                        .flatten()
+                        .flat_map(|p| vec![p.r, p.g, p.b])
+                        .flat_map(|sixteen| vec![(sixteen) as u8, (sixteen >> 8) as u8])
                         .collect(),
                 );
                 self.width = new_width;
@@ -527,10 +540,7 @@ impl PngFile {
                     .map_err(|err| ResizeError(err))?;
                 _ = replace(
                     &mut self.bytes,
-                    out.iter()
-                        .map(|p| vec![p.r, p.g, p.b, p.a])
-                        .flatten()
-                        .collect(),
+                    out.iter().flat_map(|p| vec![p.r, p.g, p.b, p.a]).collect(),
                 );
                 self.width = new_width;
                 self.height = new_height;
@@ -575,10 +585,8 @@ impl PngFile {
                 _ = replace(
                     &mut self.bytes,
                     out.iter()
-                        .map(|p| vec![p.r, p.g, p.b, p.a])
-                        .flatten()
-                        .map(|sixteen| vec![(sixteen) as u8, (sixteen >> 8) as u8])
-                        .flatten()
+                        .flat_map(|p| vec![p.r, p.g, p.b, p.a])
+                        .flat_map(|sixteen| vec![(sixteen) as u8, (sixteen >> 8) as u8])
                         .collect(),
                 );
                 self.width = new_width;
@@ -589,6 +597,174 @@ impl PngFile {
         }
     }
 
+    /// Upscales this png by the [`factor`](Upscaler::factor) of the given [`Upscaler`]
+    ///
+    /// Indexed pngs stay indexed if the upscaler [preserves colors](Upscaler::preserves_colors),
+    /// otherwise they are converted to Rgba using their palette & tRNS transparency before
+    /// upscaling.
+    ///
+    /// `Note`: This method may fail with the [`Unsupported`][u] or [`ZeroDimError`][z] error
+    /// variants only.
+    ///
+    /// [u]: PngFileError::Unsupported
+    /// [z]: PngFileError::ZeroDimError
+    pub fn upscale(&mut self, upscaler: Upscaler) -> Result<(), PngFileError> {
+        use itertools::Itertools;
+        use BitDepth::*;
+        use ColorType::*;
+        use PngFileError::Unsupported;
+
+        self.check_dimensions()?;
+        let (height, width) = (self.height as usize, self.width as usize);
+
+        match (self.color_type, self.bit_depth) {
+            (Rgb, Eight) | (Rgba, Eight) => {
+                let channels = if self.color_type == Rgb { 3 } else { 4 };
+                let pixels = self
+                    .bytes
+                    .iter()
+                    .chunks(channels)
+                    .into_iter()
+                    .map(|mut p| {
+                        [
+                            *p.next().unwrap(),
+                            *p.next().unwrap(),
+                            *p.next().unwrap(),
+                            *p.next().unwrap_or(&255),
+                        ]
+                    })
+                    .collect::<Vec<[u8; 4]>>();
+                self.bytes = upscaler
+                    .upscale_rgba(&pixels, height, width)
+                    .into_iter()
+                    .flat_map(|p| p.into_iter().take(channels))
+                    .collect();
+            }
+            (Indexed, Eight) => match upscaler.upscale_exact(&self.bytes, height, width) {
+                Some(bytes) => {
+                    self.bytes = bytes;
+                }
+                None => {
+                    let palette = self.palette.take().unwrap_or_default();
+                    let pixels = self
+                        .bytes
+                        .iter()
+                        .map(|i| {
+                            let a = self.alpha_at(*i as usize);
+                            let i = *i as usize * 3;
+                            match palette.get(i..i + 3) {
+                                Some(rgb) => [rgb[0], rgb[1], rgb[2], a],
+                                None => [0, 0, 0, a],
+                            }
+                        })
+                        .collect::<Vec<[u8; 4]>>();
+                    self.trns = None;
+                    self.bytes = upscaler
+                        .upscale_rgba(&pixels, height, width)
+                        .into_iter()
+                        .flatten()
+                        .collect();
+                    self.color_type = Rgba;
+                }
+            },
+            (ct, bd) => {
+                return Err(Unsupported(ct, bd));
+            }
+        }
+
+        self.height *= upscaler.factor();
+        self.width *= upscaler.factor();
+        Ok(())
+    }
+
+    /// Downsamples this png to `new_dim` with the given [`Downsampler`], only ever using colors
+    /// present in the source
+    ///
+    /// `Note`: This method may fail with the [`Unsupported`][u], [`ZeroDimError`][z] or
+    /// [`TryingToUpscale`][t] error variants only.
+    ///
+    /// [u]: PngFileError::Unsupported
+    /// [z]: PngFileError::ZeroDimError
+    /// [t]: PngFileError::TryingToUpscale
+    pub fn downsample(
+        &mut self,
+        new_dim: PCoord<u32>,
+        downsampler: Downsampler,
+    ) -> Result<(), PngFileError> {
+        use PngFileError::TryingToUpscale;
+
+        self.check_dimensions()?;
+        let new_height = new_dim.x();
+        let new_width = new_dim.y();
+        if new_width > self.width || new_height > self.height {
+            return Err(TryingToUpscale(
+                self.width,
+                self.height,
+                new_width,
+                new_height,
+            ));
+        }
+
+        let bytes = downsampler
+            .downsample(
+                &self.pixels()?,
+                (self.height as usize, self.width as usize),
+                (new_height as usize, new_width as usize),
+            )
+            .concat();
+        self.bytes = bytes;
+        self.width = new_width;
+        self.height = new_height;
+        Ok(())
+    }
+
+    /// Detects the pixel grid of this png, i.e. the dimensions of the pixel-art image that was
+    /// upscaled to produce this png
+    ///
+    /// `Note`: This method may fail with the [`Unsupported`][u] or [`ZeroDimError`][z] error
+    /// variants only.
+    ///
+    /// [u]: PngFileError::Unsupported
+    /// [z]: PngFileError::ZeroDimError
+    pub fn detect_pixel_grid(&self) -> Result<PCoord<u32>, PngFileError> {
+        self.check_dimensions()?;
+        let (rows, cols) =
+            detect_pixel_size(&self.pixels()?, self.height as usize, self.width as usize);
+        //wont fail because runs are never longer than the dimensions
+        Ok(PCoord::new(self.height / rows as u32, self.width / cols as u32).unwrap())
+    }
+
+    /// Splits the bytes into one slice per pixel
+    fn pixels(&self) -> Result<Vec<&[u8]>, PngFileError> {
+        use BitDepth::*;
+        use ColorType::*;
+
+        let samples = match self.color_type {
+            Grayscale | Indexed => 1,
+            GrayscaleAlpha => 2,
+            Rgb => 3,
+            Rgba => 4,
+        };
+        let depth = match self.bit_depth {
+            Eight => 1,
+            Sixteen => 2,
+            bd => {
+                return Err(PngFileError::Unsupported(self.color_type, bd));
+            }
+        };
+        Ok(self.bytes.chunks(samples * depth).collect())
+    }
+
+    /// Returns the alpha of the palette entry at an index of an indexed png, given by its tRNS
+    /// chunk & opaque if it has none for that entry
+    fn alpha_at(&self, index: usize) -> u8 {
+        self.trns
+            .as_ref()
+            .and_then(|trns| trns.get(index))
+            .copied()
+            .unwrap_or(255)
+    }
+
     fn check_dimensions(&self) -> Result<(), PngFileError> {
         if self.width == 0 || self.height == 0 {
             Err(PngFileError::ZeroDimError(self.height, self.width))
@@ -610,6 +786,7 @@ pub enum PngFileError {
     SceneSizeError(u32, u32),
     ZeroDimError(u32, u32),
     TryingToDownscale(u32, u32, u32, u32),
+    TryingToUpscale(u32, u32, u32, u32),
     NonAbsoluteUpscale(u32, u32, u32, u32),
 
     #[cfg(feature = "resize")]
@@ -666,6 +843,11 @@ impl fmt::Display for PngFileError {
                 feature 'resize' to overcome this",
                 ow, oh, nw, nh,
             ),
+            TryingToUpscale(ow, oh, nw, nh) => write!(
+                f,
+                "cannot downsample ({},{}) to ({},{}) as it is an upscaling operation",
+                ow, oh, nw, nh,
+            ),
             NonAbsoluteUpscale(ow, oh, nw, nh) => write!(
                 f,
                 "cannot resize ({},{}) to ({},{}) as it is not an absolute upscaling. enable \
@@ -1,25 +1,305 @@
-use crate::project::Project;
+use crate::{
+    file::{
+        canvas_file::{decode_scenes, encode_canvas},
+        legacy, SceneEncoding,
+    },
+    project::{Canvas, History, Project, ProjectError},
+    types::{Coord, PCoord, UCoord},
+};
 
+use flate2::{read::DeflateDecoder, write::DeflateEncoder, Compression};
 use savefile::prelude::*;
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    fmt, fs,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+};
 
+/// A `.pixylene` Project file
+///
+/// A Project file starts with the [`MAGIC`](ProjectFile::MAGIC) bytes, followed by a header
+/// consisting of the little-endian `u32` version of the file and a byte of flags, followed by the
+/// project data. The project data is the JSON of the Project's fields with the Scenes of its
+/// Canvas in the [`Base64`](SceneEncoding::Base64) encoding, deflate-compressed if the
+/// [`COMPRESSED`](ProjectFile::COMPRESSED) flag is set. The project data may also contain the
+/// undo [`History`] of the Project, see [`write_with_history`](ProjectFile::write_with_history).
+///
+/// Files of older versions are migrated to the current [`VERSION`](ProjectFile::VERSION) when
+/// read. Files without the magic bytes are treated as version 0, which were plain savefile dumps
+/// of the [`Project`] with its Scenes stored as flattened grids of optional pixels.
+///
+/// Projects can also be saved in a human-readable form to `.pixylene.json` files using
+/// [`write_json`](ProjectFile::write_json).
 pub struct ProjectFile {
-    pub version: u32,
+    /// Whether to compress the project data when writing
+    pub compress: bool,
 }
+
 impl ProjectFile {
-    pub fn read(&self, path: &PathBuf) -> Result<Project, ProjectFileError> {
-        use ProjectFileError::LoadingError;
-        match load_file(path.clone(), self.version) {
-            Ok(project) => Ok(project),
-            Err(error) => Err(LoadingError(path.clone(), error)),
+    /// The bytes every Project file (after version 0) begins with
+    pub const MAGIC: &'static [u8; 8] = b"PIXYLENE";
+
+    /// The version of Project files written by this version of the library
+    pub const VERSION: u32 = 2;
+
+    /// The flag set when the project data is deflate-compressed
+    pub const COMPRESSED: u8 = 0b0000_0001;
+
+    /// The extension of human-readable Project files
+    pub const JSON_EXTENSION: &'static str = "pixylene.json";
+
+    /// Migrations of the project data from each version to the next, where `MIGRATIONS[i]`
+    /// migrates version `i + 1` to version `i + 2`
+    const MIGRATIONS: [Migration; Self::VERSION as usize - 1] = [encode_v1_scenes];
+
+    pub fn read(path: &PathBuf) -> Result<Project, ProjectFileError> {
+        Self::read_with_history(path).map(|(project, _)| project)
+    }
+
+    /// Reads a Project file along with the undo [`History`] saved in it, if any
+    pub fn read_with_history(
+        path: &PathBuf,
+    ) -> Result<(Project, Option<History>), ProjectFileError> {
+        use ProjectFileError::{
+            DeserializeError, InvalidLegacyProject, InvalidProject, LoadingError, ReadError,
+            TruncatedHeader,
+        };
+
+        let bytes = fs::read(path).map_err(|err| ReadError(path.clone(), err))?;
+        if !bytes.starts_with(Self::MAGIC) {
+            let project = load_file::<legacy::Project, _>(path.clone(), 0)
+                .map_err(|err| LoadingError(path.clone(), err))?;
+            let project_data = ProjectData::try_from(project)
+                .map_err(|err| InvalidLegacyProject(path.clone(), err))?;
+            return project_data
+                .try_into()
+                .map(|project| (project, None))
+                .map_err(|err| InvalidProject(path.clone(), err));
+        }
+
+        let header_len = Self::MAGIC.len() + 5;
+        let header = bytes
+            .get(Self::MAGIC.len()..header_len)
+            .ok_or(TruncatedHeader(path.clone()))?;
+        let version = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+        let flags = header[4];
+        let mut data = Vec::new();
+        if flags & Self::COMPRESSED != 0 {
+            DeflateDecoder::new(&bytes[header_len..])
+                .read_to_end(&mut data)
+                .map_err(|err| ReadError(path.clone(), err))?;
+        } else {
+            data.extend_from_slice(&bytes[header_len..]);
         }
+
+        let value: Value =
+            serde_json::from_slice(&data).map_err(|err| DeserializeError(path.clone(), err))?;
+        Self::from_value(path, value, version)
     }
+
     pub fn write(&self, path: &PathBuf, project: &Project) -> Result<(), ProjectFileError> {
-        use ProjectFileError::SavingError;
-        match save_file(path.clone(), self.version, project) {
-            Ok(()) => Ok(()),
-            Err(error) => Err(SavingError(path.clone(), error)),
+        self.write_with_history(path, project, None)
+    }
+
+    /// Writes a Project file along with an undo [`History`] whose current state is the Project's
+    /// Canvas
+    pub fn write_with_history(
+        &self,
+        path: &PathBuf,
+        project: &Project,
+        history: Option<&History>,
+    ) -> Result<(), ProjectFileError> {
+        use ProjectFileError::{SerializeError, WriteError};
+
+        let mut project_data = ProjectData::from(project);
+        project_data.history = history.cloned();
+        let data = serde_json::to_value(&project_data)
+            .and_then(|mut value| {
+                value["canvas"] = encode_canvas(&project.canvas, SceneEncoding::Base64)?;
+                serde_json::to_vec(&value)
+            })
+            .map_err(|err| SerializeError(path.clone(), err))?;
+
+        let mut bytes = Vec::from(Self::MAGIC.as_slice());
+        bytes.extend_from_slice(&Self::VERSION.to_le_bytes());
+        if self.compress {
+            bytes.push(Self::COMPRESSED);
+            let mut encoder = DeflateEncoder::new(bytes, Compression::default());
+            encoder
+                .write_all(&data)
+                .map_err(|err| WriteError(path.clone(), err))?;
+            bytes = encoder
+                .finish()
+                .map_err(|err| WriteError(path.clone(), err))?;
+        } else {
+            bytes.push(0);
+            bytes.extend(data);
         }
+
+        fs::write(path, bytes).map_err(|err| WriteError(path.clone(), err))
+    }
+
+    /// Returns whether the path is of a human-readable Project file, i.e. has the extension
+    /// [`JSON_EXTENSION`](ProjectFile::JSON_EXTENSION)
+    pub fn is_json_path(path: &Path) -> bool {
+        path.file_name()
+            .map(|name| {
+                name.to_string_lossy()
+                    .ends_with(&format!(".{}", Self::JSON_EXTENSION))
+            })
+            .unwrap_or(false)
+    }
+
+    /// Reads a human-readable Project file written by [`write_json`](ProjectFile::write_json),
+    /// migrating it if it is of an older version
+    ///
+    /// Human-readable Project files do not carry an undo [`History`].
+    pub fn read_json(path: &PathBuf) -> Result<Project, ProjectFileError> {
+        use ProjectFileError::{DeserializeError, MissingVersion, ReadError};
+
+        let mut value: Value = serde_json::from_str(
+            &fs::read_to_string(path).map_err(|err| ReadError(path.clone(), err))?,
+        )
+        .map_err(|err| DeserializeError(path.clone(), err))?;
+        let version = value
+            .as_object_mut()
+            .and_then(|object| object.remove("version"))
+            .and_then(|version| version.as_u64())
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or(MissingVersion(path.clone()))?;
+        Self::from_value(path, value, version).map(|(project, _)| project)
+    }
+
+    /// Writes the Project to a human-readable, pretty-printed JSON file
+    ///
+    /// The file is an object with the following fields:
+    /// - `version`: the version of the schema, same as the [`VERSION`](ProjectFile::VERSION) of
+    ///   binary Project files
+    /// - `canvas`: the [`Canvas`], in the same form as in a [`CanvasFile`](super::CanvasFile) with
+    ///   its Scenes in the [`Rle`](SceneEncoding::Rle) encoding so that each row is a line
+    /// - `out_dim`, `out_mul`, `out_repeat`: see [`Scene::render`](crate::project::Scene::render)
+    /// - `focus`: the [`focus`](Project::focus) as `[{"x": .., "y": ..}, layer]`
+    /// - `cursors`: the list of cursors as `[{"x": .., "y": ..}, layer]`, sorted by layer & then
+    ///   coordinates so that the file diffs cleanly
+    pub fn write_json(path: &PathBuf, project: &Project) -> Result<(), ProjectFileError> {
+        use ProjectFileError::{SerializeError, WriteError};
+
+        let mut json = serde_json::to_value(ProjectData::from(project))
+            .and_then(|mut data| {
+                data["canvas"] = encode_canvas(&project.canvas, SceneEncoding::Rle)?;
+                serde_json::to_string_pretty(&ProjectJson {
+                    version: Self::VERSION,
+                    data,
+                })
+            })
+            .map_err(|err| SerializeError(path.clone(), err))?;
+        json.push('\n');
+        fs::write(path, json).map_err(|err| WriteError(path.clone(), err))
+    }
+
+    fn from_value(
+        path: &PathBuf,
+        mut value: Value,
+        version: u32,
+    ) -> Result<(Project, Option<History>), ProjectFileError> {
+        use ProjectFileError::{
+            DecodeError, DeserializeError, InvalidProject, MigrationError, UnsupportedVersion,
+        };
+
+        if version == 0 || version > Self::VERSION {
+            return Err(UnsupportedVersion(path.clone(), version));
+        }
+        for (from, migration) in (version..).zip(&Self::MIGRATIONS[(version - 1) as usize..]) {
+            migration(&mut value).map_err(|err| MigrationError(path.clone(), from, err))?;
+        }
+        if let Some(canvas) = value.get_mut("canvas") {
+            decode_scenes(canvas).map_err(|err| DecodeError(path.clone(), err))?;
+        }
+
+        let mut project_data = serde_json::from_value::<ProjectData>(value)
+            .map_err(|err| DeserializeError(path.clone(), err))?;
+        let history = project_data.history.take();
+        let project = project_data
+            .try_into()
+            .map_err(|err| InvalidProject(path.clone(), err))?;
+        Ok((project, history))
+    }
+}
+
+/// Migrates version 1 project data, which had the Scenes of the Canvas as verbose grids of every
+/// pixel, to version 2 by encoding them in the [`Base64`](SceneEncoding::Base64) encoding
+fn encode_v1_scenes(value: &mut Value) -> Result<(), String> {
+    let canvas = value
+        .get_mut("canvas")
+        .ok_or("project data has no 'canvas'")?;
+    let decoded = serde_json::from_value::<Canvas>(canvas.take()).map_err(|err| err.to_string())?;
+    *canvas = encode_canvas(&decoded, SceneEncoding::Base64).map_err(|err| err.to_string())?;
+    Ok(())
+}
+
+type Migration = fn(&mut Value) -> Result<(), String>;
+
+#[derive(Serialize)]
+struct ProjectJson {
+    version: u32,
+    #[serde(flatten)]
+    data: Value,
+}
+
+/// The serializable representation of a [`Project`]
+#[derive(Serialize, Deserialize)]
+pub(crate) struct ProjectData {
+    //written by encode_canvas instead, straight from the scenes
+    #[serde(skip_serializing)]
+    pub canvas: Canvas,
+    pub out_dim: PCoord,
+    pub focus: (Coord, u16),
+    pub out_mul: u8,
+    pub out_repeat: PCoord,
+    pub cursors: Vec<(UCoord, u16)>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub cursor_registers: HashMap<String, Vec<(UCoord, u16)>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history: Option<History>,
+}
+
+impl From<&Project> for ProjectData {
+    fn from(project: &Project) -> ProjectData {
+        let mut cursors = project.cursors().copied().collect::<Vec<(UCoord, u16)>>();
+        cursors.sort_by_key(|(coord, layer)| (*layer, coord.x, coord.y));
+
+        ProjectData {
+            canvas: project.canvas.clone(),
+            out_dim: project.out_dim,
+            focus: project.focus,
+            out_mul: project.get_out_mul(),
+            out_repeat: project.out_repeat,
+            cursors,
+            cursor_registers: project.cursor_registers.clone(),
+            history: None,
+        }
+    }
+}
+
+impl TryFrom<ProjectData> for Project {
+    type Error = ProjectError;
+
+    fn try_from(data: ProjectData) -> Result<Project, ProjectError> {
+        let mut project = Project::new(data.canvas);
+        project.out_dim = data.out_dim;
+        project.focus = data.focus;
+        project.out_repeat = data.out_repeat;
+        project.set_out_mul(data.out_mul)?;
+        for cursor in &data.cursors {
+            if !project.is_cursor_at(cursor)? {
+                project.toggle_cursor_at(cursor)?;
+            }
+        }
+        project.cursor_registers = data.cursor_registers;
+        Ok(project)
     }
 }
 
@@ -28,10 +308,20 @@ impl ProjectFile {
 #[derive(Debug)]
 pub enum ProjectFileError {
     LoadingError(PathBuf, SavefileError),
-    SavingError(PathBuf, SavefileError),
+    ReadError(PathBuf, io::Error),
+    WriteError(PathBuf, io::Error),
+    TruncatedHeader(PathBuf),
+    MissingVersion(PathBuf),
+    UnsupportedVersion(PathBuf, u32),
+    DeserializeError(PathBuf, serde_json::Error),
+    SerializeError(PathBuf, serde_json::Error),
+    InvalidProject(PathBuf, ProjectError),
+    InvalidLegacyProject(PathBuf, String),
+    MigrationError(PathBuf, u32, String),
+    DecodeError(PathBuf, String),
 }
-impl std::fmt::Display for ProjectFileError {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl fmt::Display for ProjectFileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         use ProjectFileError::*;
         match self {
             LoadingError(path, savefile_error) => write!(
@@ -40,11 +330,73 @@ impl std::fmt::Display for ProjectFileError {
                 path.display(),
                 savefile_error,
             ),
-            SavingError(path, savefile_error) => write!(
+            ReadError(path, io_error) => write!(
                 f,
-                "failed to save project file '{}': {}",
+                "failed to read project file '{}': {}",
                 path.display(),
-                savefile_error,
+                io_error,
+            ),
+            WriteError(path, io_error) => write!(
+                f,
+                "failed to write project file '{}': {}",
+                path.display(),
+                io_error,
+            ),
+            TruncatedHeader(path) => write!(
+                f,
+                "project file '{}' ends before its header is complete",
+                path.display(),
+            ),
+            MissingVersion(path) => write!(
+                f,
+                "project file '{}' is missing the 'version' field",
+                path.display(),
+            ),
+            UnsupportedVersion(path, version) => write!(
+                f,
+                "project file '{}' has version {} which is not supported by this version of \
+                pixylene (latest supported version is {})",
+                path.display(),
+                version,
+                ProjectFile::VERSION,
+            ),
+            DeserializeError(path, err) => write!(
+                f,
+                "failed to deserialize project file '{}': {}",
+                path.display(),
+                err,
+            ),
+            SerializeError(path, err) => write!(
+                f,
+                "failed to serialize project file '{}': {}",
+                path.display(),
+                err,
+            ),
+            InvalidProject(path, err) => write!(
+                f,
+                "project file '{}' contains an invalid project: {}",
+                path.display(),
+                err,
+            ),
+            InvalidLegacyProject(path, err) => write!(
+                f,
+                "version 0 project file '{}' contains an invalid project: {}",
+                path.display(),
+                err,
+            ),
+            MigrationError(path, version, err) => write!(
+                f,
+                "failed to migrate project file '{}' from version {} to {}: {}",
+                path.display(),
+                version,
+                version + 1,
+                err,
+            ),
+            DecodeError(path, err) => write!(
+                f,
+                "failed to decode the scenes of project file '{}': {}",
+                path.display(),
+                err,
             ),
         }
     }
@@ -0,0 +1,391 @@
+use super::hqx::{Rule, HQ2X, HQ2X_RULES, HQ3X, HQ3X_RULES};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// Pixel-art aware upscaling algorithms that can be applied to a [`PngFile`](super::PngFile)
+/// before it is written
+///
+/// Unlike nearest-neighbour or filtered resizing, these algorithms detect edges between
+/// same-colored regions and smoothen them while keeping flat areas untouched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Upscaler {
+    /// Scale2x, also known as EPX or AdvMAME2x
+    #[serde(alias = "scale2x", alias = "epx")]
+    Scale2x,
+
+    /// Scale3x, also known as AdvMAME3x
+    #[serde(alias = "scale3x")]
+    Scale3x,
+
+    /// Scale4x, Scale2x applied twice
+    #[serde(alias = "scale4x")]
+    Scale4x,
+
+    /// Eagle, doubles the size
+    #[serde(alias = "eagle")]
+    Eagle,
+
+    /// hq2x, doubles the size and blends edges based on which of the 8 neighbours of each
+    /// pixel differ from it
+    #[serde(alias = "hq2x")]
+    Hq2x,
+
+    /// hq3x, triples the size like [`Hq2x`](Upscaler::Hq2x)
+    #[serde(alias = "hq3x")]
+    Hq3x,
+
+    /// xBR (level 1), doubles the size and blends edges along their detected direction
+    #[serde(alias = "xbr")]
+    Xbr,
+}
+
+impl Upscaler {
+    pub const ALL: [Upscaler; 7] = [
+        Upscaler::Scale2x,
+        Upscaler::Scale3x,
+        Upscaler::Scale4x,
+        Upscaler::Eagle,
+        Upscaler::Hq2x,
+        Upscaler::Hq3x,
+        Upscaler::Xbr,
+    ];
+
+    /// Returns the factor by which this upscaler multiplies both dimensions
+    pub fn factor(&self) -> u32 {
+        use Upscaler::*;
+        match self {
+            Scale2x | Eagle | Hq2x | Xbr => 2,
+            Scale3x | Hq3x => 3,
+            Scale4x => 4,
+        }
+    }
+
+    /// Returns whether this upscaler only ever copies existing colors into the output, which
+    /// makes it usable on indexed images without leaving the palette
+    pub fn preserves_colors(&self) -> bool {
+        use Upscaler::*;
+        match self {
+            Scale2x | Scale3x | Scale4x | Eagle => true,
+            Hq2x | Hq3x | Xbr => false,
+        }
+    }
+
+    /// Upscales a row-major `height`x`width` grid of pixels that can only be compared for
+    /// equality, returning `None` if this upscaler needs to blend colors
+    pub(crate) fn upscale_exact<T: Copy + PartialEq>(
+        &self,
+        pixels: &[T],
+        height: usize,
+        width: usize,
+    ) -> Option<Vec<T>> {
+        use Upscaler::*;
+        let grid = Grid {
+            pixels,
+            height,
+            width,
+        };
+        match self {
+            Scale2x => Some(scale2x(&grid)),
+            Scale3x => Some(scale3x(&grid)),
+            Scale4x => Some(scale2x(&Grid {
+                pixels: &scale2x(&grid),
+                height: height * 2,
+                width: width * 2,
+            })),
+            Eagle => Some(eagle(&grid)),
+            Hq2x | Hq3x | Xbr => None,
+        }
+    }
+
+    /// Upscales a row-major `height`x`width` grid of RGBA pixels
+    pub(crate) fn upscale_rgba(
+        &self,
+        pixels: &[[u8; 4]],
+        height: usize,
+        width: usize,
+    ) -> Vec<[u8; 4]> {
+        use Upscaler::*;
+        let grid = Grid {
+            pixels,
+            height,
+            width,
+        };
+        match self {
+            Hq2x => hqx(&grid, 2, &HQ2X_RULES, &HQ2X),
+            Hq3x => hqx(&grid, 3, &HQ3X_RULES, &HQ3X),
+            Xbr => xbr(&grid),
+            //wont fail because these upscalers dont blend
+            Scale2x | Scale3x | Scale4x | Eagle => {
+                self.upscale_exact(pixels, height, width).unwrap()
+            }
+        }
+    }
+}
+
+impl fmt::Display for Upscaler {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use Upscaler::*;
+        write!(
+            f,
+            "{}",
+            match self {
+                Scale2x => "scale2x",
+                Scale3x => "scale3x",
+                Scale4x => "scale4x",
+                Eagle => "eagle",
+                Hq2x => "hq2x",
+                Hq3x => "hq3x",
+                Xbr => "xbr",
+            }
+        )
+    }
+}
+
+impl FromStr for Upscaler {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use Upscaler::*;
+        match s.to_lowercase().as_str() {
+            "scale2x" | "epx" | "advmame2x" => Ok(Scale2x),
+            "scale3x" | "advmame3x" => Ok(Scale3x),
+            "scale4x" | "advmame4x" => Ok(Scale4x),
+            "eagle" => Ok(Eagle),
+            "hq2x" => Ok(Hq2x),
+            "hq3x" => Ok(Hq3x),
+            "xbr" => Ok(Xbr),
+            _ => Err(format!(
+                "unknown upscaler '{}', expected one of: {}",
+                s,
+                Upscaler::ALL
+                    .iter()
+                    .map(|u| u.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", "),
+            )),
+        }
+    }
+}
+
+struct Grid<'a, T> {
+    pixels: &'a [T],
+    height: usize,
+    width: usize,
+}
+
+impl<'a, T: Copy> Grid<'a, T> {
+    /// Gets the pixel at the offset (dx, dy) from (x, y), clamping to the edges of the grid
+    fn at(&self, x: usize, y: usize, dx: isize, dy: isize) -> T {
+        let x = (x as isize + dx).clamp(0, self.height as isize - 1) as usize;
+        let y = (y as isize + dy).clamp(0, self.width as isize - 1) as usize;
+        self.pixels[x * self.width + y]
+    }
+
+    /// Upscales by `factor` by computing each output block of `factor`x`factor` pixels with `f`
+    fn map_blocks<F>(&self, factor: usize, mut f: F) -> Vec<T>
+    where
+        F: FnMut(usize, usize, &mut [T]),
+    {
+        let out_width = self.width * factor;
+        let mut out = vec![self.pixels[0]; self.pixels.len() * factor * factor];
+        let mut block = vec![self.pixels[0]; factor * factor];
+        for x in 0..self.height {
+            for y in 0..self.width {
+                f(x, y, &mut block);
+                for i in 0..factor {
+                    for j in 0..factor {
+                        out[(x * factor + i) * out_width + (y * factor + j)] =
+                            block[i * factor + j];
+                    }
+                }
+            }
+        }
+        out
+    }
+}
+
+/// The four corners of a block, as the (row, column) direction they face
+const CORNERS: [(isize, isize); 4] = [(-1, -1), (-1, 1), (1, -1), (1, 1)];
+
+fn scale2x<T: Copy + PartialEq>(grid: &Grid<T>) -> Vec<T> {
+    grid.map_blocks(2, |x, y, block| {
+        let e = grid.at(x, y, 0, 0);
+        for (i, (dx, dy)) in CORNERS.iter().enumerate() {
+            let vertical = grid.at(x, y, *dx, 0);
+            let horizontal = grid.at(x, y, 0, *dy);
+            let opposite_vertical = grid.at(x, y, -*dx, 0);
+            let opposite_horizontal = grid.at(x, y, 0, -*dy);
+            block[i] = if vertical == horizontal
+                && vertical != opposite_horizontal
+                && horizontal != opposite_vertical
+            {
+                vertical
+            } else {
+                e
+            };
+        }
+    })
+}
+
+fn scale3x<T: Copy + PartialEq>(grid: &Grid<T>) -> Vec<T> {
+    grid.map_blocks(3, |x, y, block| {
+        let p = |dx, dy| grid.at(x, y, dx, dy);
+        let (a, b, c) = (p(-1, -1), p(-1, 0), p(-1, 1));
+        let (d, e, f) = (p(0, -1), p(0, 0), p(0, 1));
+        let (g, h, i) = (p(1, -1), p(1, 0), p(1, 1));
+
+        block.fill(e);
+        if b != h && d != f {
+            block[0] = if d == b { d } else { e };
+            block[1] = if (d == b && e != c) || (b == f && e != a) {
+                b
+            } else {
+                e
+            };
+            block[2] = if b == f { f } else { e };
+            block[3] = if (d == b && e != g) || (d == h && e != a) {
+                d
+            } else {
+                e
+            };
+            block[5] = if (b == f && e != i) || (h == f && e != c) {
+                f
+            } else {
+                e
+            };
+            block[6] = if d == h { d } else { e };
+            block[7] = if (d == h && e != i) || (h == f && e != g) {
+                h
+            } else {
+                e
+            };
+            block[8] = if h == f { f } else { e };
+        }
+    })
+}
+
+fn eagle<T: Copy + PartialEq>(grid: &Grid<T>) -> Vec<T> {
+    grid.map_blocks(2, |x, y, block| {
+        for (i, (dx, dy)) in CORNERS.iter().enumerate() {
+            let vertical = grid.at(x, y, *dx, 0);
+            let horizontal = grid.at(x, y, 0, *dy);
+            let diagonal = grid.at(x, y, *dx, *dy);
+            block[i] = if vertical == horizontal && horizontal == diagonal {
+                vertical
+            } else {
+                grid.at(x, y, 0, 0)
+            };
+        }
+    })
+}
+
+/// Converts a color to its (Y, U, V) components
+fn yuv([r, g, b, _]: [u8; 4]) -> (f32, f32, f32) {
+    let (r, g, b) = (f32::from(r), f32::from(g), f32::from(b));
+    (
+        0.299 * r + 0.587 * g + 0.114 * b,
+        -0.169 * r - 0.331 * g + 0.5 * b + 128.0,
+        0.5 * r - 0.419 * g - 0.081 * b + 128.0,
+    )
+}
+
+/// The thresholds used by hqx to decide whether two colors are different
+fn yuv_differs(p: [u8; 4], q: [u8; 4]) -> bool {
+    if p[3] == 0 && q[3] == 0 {
+        return false;
+    }
+    if p[3] != q[3] {
+        return true;
+    }
+    let (py, pu, pv) = yuv(p);
+    let (qy, qu, qv) = yuv(q);
+    (py - qy).abs() > 48.0 || (pu - qu).abs() > 7.0 || (pv - qv).abs() > 6.0
+}
+
+/// The weighted color distance used by xBR
+fn xbr_distance(p: [u8; 4], q: [u8; 4]) -> f32 {
+    let (py, pu, pv) = yuv(p);
+    let (qy, qu, qv) = yuv(q);
+    48.0 * (py - qy).abs()
+        + 7.0 * (pu - qu).abs()
+        + 6.0 * (pv - qv).abs()
+        + 48.0 * (f32::from(p[3]) - f32::from(q[3])).abs()
+}
+
+/// Mixes colors in proportion to their weights
+fn interpolate(colors: &[([u8; 4], u32)]) -> [u8; 4] {
+    let total: u32 = colors.iter().map(|(_, weight)| weight).sum();
+    let mut out = [0; 4];
+    for (c, channel) in out.iter_mut().enumerate() {
+        let sum: u32 = colors
+            .iter()
+            .map(|(color, weight)| u32::from(color[c]) * weight)
+            .sum();
+        *channel = ((sum + total / 2) / total) as u8;
+    }
+    out
+}
+
+/// hq2x & hq3x, filling the `factor`x`factor` block of each pixel with the rules its pattern maps
+/// to in `table`
+fn hqx<const N: usize>(
+    grid: &Grid<[u8; 4]>,
+    factor: usize,
+    rules: &[Rule],
+    table: &[[u8; N]; 256],
+) -> Vec<[u8; 4]> {
+    grid.map_blocks(factor, |x, y, block| {
+        //w[1] to w[9] as numbered in the tables, w[0] being unused
+        let mut w = [[0; 4]; 10];
+        for (k, pixel) in w.iter_mut().enumerate().skip(1) {
+            *pixel = grid.at(x, y, (k as isize - 1) / 3 - 1, (k as isize - 1) % 3 - 1);
+        }
+        let pattern = [1, 2, 3, 4, 6, 7, 8, 9]
+            .iter()
+            .enumerate()
+            .filter(|(_, k)| yuv_differs(w[5], w[**k]))
+            .fold(0, |pattern, (bit, _)| pattern | 1 << bit);
+        let mix = |weights: &[(usize, u32)]| {
+            interpolate(
+                &weights
+                    .iter()
+                    .map(|(k, weight)| (w[*k], *weight))
+                    .collect::<Vec<([u8; 4], u32)>>(),
+            )
+        };
+
+        for (cell, rule) in block.iter_mut().zip(table[pattern]) {
+            *cell = match rules[usize::from(rule)] {
+                Rule::Mix(weights) => mix(weights),
+                Rule::IfDiffers(a, b, then, otherwise) => mix(if yuv_differs(w[a], w[b]) {
+                    then
+                } else {
+                    otherwise
+                }),
+            };
+        }
+    })
+}
+
+fn xbr(grid: &Grid<[u8; 4]>) -> Vec<[u8; 4]> {
+    grid.map_blocks(2, |x, y, block| {
+        for (i, (sx, sy)) in CORNERS.iter().enumerate() {
+            //neighbourhood mirrored so that the corner being computed always faces bottom-right
+            let p = |dx: isize, dy: isize| grid.at(x, y, dx * sx, dy * sy);
+            let (b, d, e, f, h) = (p(-1, 0), p(0, -1), p(0, 0), p(0, 1), p(1, 0));
+            let (c, g, i_) = (p(-1, 1), p(1, -1), p(1, 1));
+            let (f4, i4, h5, i5) = (p(0, 2), p(1, 2), p(2, 0), p(2, 1));
+            let dist = xbr_distance;
+
+            let across = dist(e, c) + dist(e, g) + dist(i_, f4) + dist(i_, h5) + 4.0 * dist(h, f);
+            let along = dist(h, d) + dist(h, i5) + dist(f, i4) + dist(f, b) + 4.0 * dist(e, i_);
+
+            block[i] = if e != f && e != h && across < along {
+                let new = if dist(e, f) <= dist(e, h) { f } else { h };
+                interpolate(&[(e, 1), (new, 1)])
+            } else {
+                e
+            };
+        }
+    })
+}
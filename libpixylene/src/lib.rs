@@ -145,4 +145,895 @@ mod tests {
         ))
         .unwrap();
     }
+
+    #[test]
+    fn export_upscaled_canvas() {
+        for upscaler in file::Upscaler::ALL {
+            let mut png = file::PngFile::read(&std::path::PathBuf::from(
+                "../assets/images/rgb_8bit_16x16.png",
+            ))
+            .unwrap();
+            png.upscale(upscaler).unwrap();
+            let factor = upscaler.factor() as u16;
+            assert_eq!(
+                png.to_canvas().unwrap().layers.dim(),
+                types::PCoord::new(16 * factor, 16 * factor).unwrap()
+            );
+            png.write(&std::path::PathBuf::from(format!(
+                "/tmp/rgb_8bit_16x16_{}.png",
+                upscaler
+            )))
+            .unwrap();
+
+            let mut png = file::PngFile::read(&std::path::PathBuf::from(
+                "../assets/images/indexed_8bit_33x33.png",
+            ))
+            .unwrap();
+            png.upscale(upscaler).unwrap();
+            let canvas = png.to_canvas().unwrap();
+            assert_eq!(
+                canvas.layers.dim(),
+                types::PCoord::new(33 * factor, 33 * factor).unwrap()
+            );
+            assert_eq!(
+                matches!(canvas.layers, project::LayersType::Indexed(_)),
+                upscaler.preserves_colors()
+            );
+        }
+    }
+
+    #[test]
+    fn upscalers_pixel_exact() {
+        use file::Upscaler::*;
+
+        let grid = |rows: &[&str]| {
+            rows.iter()
+                .flat_map(|row| row.bytes().map(|b| b - b'0'))
+                .collect::<Vec<u8>>()
+        };
+        let input = grid(&["1000", "1100", "0110", "0010"]);
+        let scale2x = grid(&[
+            "11000000", "11100000", "11100000", "11111000", "01111000", "00011100", "00011100",
+            "00001100",
+        ]);
+        let scale3x = grid(&[
+            "111000000000",
+            "111100000000",
+            "111100000000",
+            "111110000000",
+            "111111000000",
+            "111111100000",
+            "011111110000",
+            "000111110000",
+            "000011111000",
+            "000001111000",
+            "000001111000",
+            "000000111000",
+        ]);
+        let scale4x = grid(&[
+            "1111000000000000",
+            "1111100000000000",
+            "1111100000000000",
+            "1111110000000000",
+            "1111110000000000",
+            "1111111000000000",
+            "1111111110000000",
+            "1111111111000000",
+            "0111111111000000",
+            "0001111111100000",
+            "0000011111100000",
+            "0000001111110000",
+            "0000001111110000",
+            "0000000111110000",
+            "0000000111110000",
+            "0000000011110000",
+        ]);
+        for (upscaler, expected) in [
+            (Scale2x, &scale2x),
+            (Scale3x, &scale3x),
+            (Scale4x, &scale4x),
+            (Eagle, &scale2x),
+        ] {
+            assert_eq!(
+                &upscaler.upscale_exact(&input, 4, 4).unwrap(),
+                expected,
+                "{}",
+                upscaler
+            );
+        }
+        assert_eq!(Hq2x.upscale_exact(&input, 4, 4), None);
+
+        let (w, k) = ([255, 255, 255, 255], [0, 0, 0, 255]);
+        let gray = |v: u8| [v, v, v, 255];
+        assert_eq!(
+            Scale2x.upscale_rgba(&[w, k], 1, 2),
+            vec![w, w, k, k, w, w, k, k]
+        );
+        //hqx keeps straight edges sharp & only blends across diagonal ones
+        assert_eq!(
+            Hq2x.upscale_rgba(&[w, k], 1, 2),
+            Scale2x.upscale_rgba(&[w, k], 1, 2)
+        );
+        assert_eq!(
+            Hq2x.upscale_rgba(&[w, k, k, k], 2, 2),
+            vec![w, w, k, k, w, gray(223), k, k, k, k, k, k, k, k, k, k]
+        );
+        let hq3x = Hq3x.upscale_rgba(&[w, k, k, k], 2, 2);
+        assert_eq!(hq3x.len(), 36);
+        for (i, pixel) in hq3x.iter().enumerate() {
+            let (x, y) = (i / 6, i % 6);
+            let expected = match (x, y) {
+                (2, 2) => gray(128),
+                (0..=2, 0..=2) => w,
+                _ => k,
+            };
+            assert_eq!(*pixel, expected, "({}, {})", x, y);
+        }
+        assert_eq!(
+            Xbr.upscale_rgba(&[w, k, k, k], 2, 2),
+            vec![w, w, k, k, w, gray(128), k, k, k, k, k, k, k, k, k, k]
+        );
+    }
+
+    #[test]
+    fn indexed_png_transparency() {
+        use types::UCoord;
+
+        let path = std::path::PathBuf::from("/tmp/indexed_trns_2x1.png");
+        {
+            let file = std::fs::File::create(&path).unwrap();
+            let mut encoder = png::Encoder::new(std::io::BufWriter::new(file), 2, 1);
+            encoder.set_color(png::ColorType::Indexed);
+            encoder.set_depth(png::BitDepth::Eight);
+            encoder.set_palette(vec![255, 0, 0, 0, 0, 255]);
+            //second entry is missing from tRNS so it stays opaque
+            encoder.set_trns(vec![0]);
+            let mut writer = encoder.write_header().unwrap();
+            writer.write_image_data(&[0, 1]).unwrap();
+        }
+
+        let png = file::PngFile::read(&path).unwrap();
+        let canvas = png.to_canvas().unwrap();
+        assert_eq!(canvas.palette.get_color(0).unwrap().a, 0);
+        assert_eq!(canvas.palette.get_color(1).unwrap().a, 255);
+
+        //exported palette alphas survive a round trip
+        let exported = std::path::PathBuf::from("/tmp/indexed_trns_2x1_exported.png");
+        file::PngFile::from_canvas(&canvas)
+            .unwrap()
+            .write(&exported)
+            .unwrap();
+        let canvas = file::PngFile::read(&exported).unwrap().to_canvas().unwrap();
+        assert_eq!(canvas.palette.get_color(0).unwrap().a, 0);
+        assert_eq!(canvas.palette.get_color(1).unwrap().a, 255);
+
+        //converting to rgba for a blending upscaler keeps the transparency
+        let mut png = file::PngFile::read(&path).unwrap();
+        png.upscale(file::Upscaler::Hq2x).unwrap();
+        let canvas = png.to_canvas().unwrap();
+        let scene = &canvas.layers.to_true().unwrap().get_layer(0).unwrap().scene;
+        let alpha_at = |x, y| scene.get_pixel(UCoord { x, y }).unwrap().unwrap().a;
+        assert_eq!(alpha_at(0, 0), 0);
+        assert_eq!(alpha_at(1, 3), 255);
+    }
+
+    #[test]
+    fn import_downsampled_canvas() {
+        for path in [
+            "../assets/images/rgb_8bit_16x16.png",
+            "../assets/images/indexed_8bit_33x33.png",
+        ] {
+            let original = file::PngFile::read(&std::path::PathBuf::from(path)).unwrap();
+            let original_dim = original.to_canvas().unwrap().layers.dim();
+
+            for downsampler in [file::Downsampler::Mode, file::Downsampler::Center] {
+                let mut png = file::PngFile::read(&std::path::PathBuf::from(path)).unwrap();
+                let scale = types::PCoord::new(3, 3).unwrap();
+                png.resize(
+                    types::PCoordContainer::<u32>::from(original_dim)
+                        .0
+                        .mul(scale)
+                        .unwrap(),
+                )
+                .unwrap();
+                let grid = png.detect_pixel_grid().unwrap();
+                assert_eq!(grid, types::PCoordContainer::<u32>::from(original_dim).0);
+
+                png.downsample(grid, downsampler).unwrap();
+                assert_eq!(
+                    png.to_canvas().unwrap().layers,
+                    original.to_canvas().unwrap().layers
+                );
+            }
+        }
+    }
+
+    fn fixture_project() -> project::Project {
+        let mut pixylene = Pixylene::new(
+            &PixyleneDefaults {
+                dim: types::PCoord::new(4, 6).unwrap(),
+                palette: project::Palette::gruvbox(),
+                repeat: types::PCoord::new(1, 2).unwrap(),
+            },
+            false,
+        );
+        let project = &mut pixylene.project;
+        let layers = project.canvas.layers.to_true_mut().unwrap();
+        layers.new_layer(None).unwrap();
+        layers.new_layer(Some(types::TruePixel::BLACK)).unwrap();
+        layers[0]
+            .scene
+            .set_pixel(
+                types::UCoord { x: 1, y: 2 },
+                Some(types::TruePixel::FAVOURITE),
+            )
+            .unwrap();
+        layers[1].opacity = 128;
+        project.focus = (types::Coord { x: 1, y: 2 }, 1);
+        project.set_out_mul(2).unwrap();
+        project
+            .toggle_cursor_at(&(types::UCoord { x: 0, y: 0 }, 0))
+            .unwrap();
+        project
+            .toggle_cursor_at(&(types::UCoord { x: 3, y: 5 }, 1))
+            .unwrap();
+        pixylene.project
+    }
+
+    fn assert_projects_eq(a: &project::Project, b: &project::Project) {
+        let cursors = |p: &project::Project| {
+            let mut cursors = p.cursors().copied().collect::<Vec<_>>();
+            cursors.sort_by_key(|(coord, layer)| (*layer, coord.x, coord.y));
+            cursors
+        };
+        assert_eq!(a.canvas, b.canvas);
+        assert_eq!(a.out_dim, b.out_dim);
+        assert_eq!(a.focus, b.focus);
+        assert_eq!(a.get_out_mul(), b.get_out_mul());
+        assert_eq!(a.out_repeat, b.out_repeat);
+        assert_eq!(cursors(a), cursors(b));
+        assert_eq!(a.num_cursors(), b.num_cursors());
+    }
+
+    /// Writes the fixtures of the current Project file version, run when bumping the version
+    #[test]
+    #[ignore]
+    fn generate_project_fixtures() {
+        let version = file::ProjectFile::VERSION;
+        for compress in [false, true] {
+            file::ProjectFile { compress }
+                .write(
+                    &std::path::PathBuf::from(format!(
+                        "../assets/projects/v{}{}.pixylene",
+                        version,
+                        if compress { "_compressed" } else { "" },
+                    )),
+                    &fixture_project(),
+                )
+                .unwrap();
+        }
+        file::ProjectFile::write_json(
+            &std::path::PathBuf::from(format!("../assets/projects/v{}.pixylene.json", version)),
+            &fixture_project(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn open_project_fixtures() {
+        for path in [
+            "../assets/projects/v0.pixylene",
+            "../assets/projects/v1.pixylene",
+            "../assets/projects/v1_compressed.pixylene",
+            "../assets/projects/v1.pixylene.json",
+            "../assets/projects/v2.pixylene",
+            "../assets/projects/v2_compressed.pixylene",
+            "../assets/projects/v2.pixylene.json",
+        ] {
+            let pixylene = Pixylene::open_project(&std::path::PathBuf::from(path)).unwrap();
+            assert_projects_eq(&pixylene.project, &fixture_project());
+        }
+    }
+
+    #[test]
+    fn migrate_invalid_project() {
+        //a pixel that isn't a color fails the migration of version 1 scenes instead of being lost
+        let json = std::fs::read_to_string("../assets/projects/v1.pixylene.json")
+            .unwrap()
+            .replacen("\"r\": 245", "\"r\": \"pink\"", 1);
+        let path = std::path::PathBuf::from("/tmp/invalid_v1.pixylene.json");
+        std::fs::write(&path, json).unwrap();
+        assert!(matches!(
+            file::ProjectFile::read_json(&path),
+            Err(file::ProjectFileError::MigrationError(_, 1, _)),
+        ));
+    }
+
+    #[test]
+    fn save_project() {
+        for compress in [false, true] {
+            let path = std::path::PathBuf::from(format!("/tmp/project_{}.pixylene", compress));
+            file::ProjectFile { compress }
+                .write(&path, &fixture_project())
+                .unwrap();
+            assert_projects_eq(&file::ProjectFile::read(&path).unwrap(), &fixture_project());
+        }
+    }
+
+    #[test]
+    fn save_project_history() {
+        let old = fixture_project();
+        let mut new = fixture_project();
+        let layers = new.canvas.layers.to_true_mut().unwrap();
+        layers[0]
+            .scene
+            .set_pixel(types::UCoord { x: 3, y: 5 }, Some(types::TruePixel::BLACK))
+            .unwrap();
+        layers.move_layer(0, 1).unwrap();
+        new.canvas.palette.set_color(7, "#123456").unwrap();
+
+        let delta = project::CanvasDelta::between(&old.canvas, &new.canvas).unwrap();
+        assert!(delta.can_apply(&old.canvas) && !delta.can_apply(&new.canvas));
+        assert!(delta.can_revert(&new.canvas) && !delta.can_revert(&old.canvas));
+        let history = project::History {
+            current: 1,
+            last_seq: 1,
+            states: vec![
+                project::HistoryState {
+                    seq: 0,
+                    parent: None,
+                    name: String::new(),
+                    time: std::time::SystemTime::UNIX_EPOCH,
+                    delta: None,
+                },
+                project::HistoryState {
+                    seq: 1,
+                    parent: Some(0),
+                    name: String::from("pencil"),
+                    time: std::time::SystemTime::UNIX_EPOCH,
+                    delta: Some(delta),
+                },
+            ],
+        };
+
+        for compress in [false, true] {
+            let path = std::path::PathBuf::from(format!("/tmp/history_{}.pixylene", compress));
+            file::ProjectFile { compress }
+                .write_with_history(&path, &new, Some(&history))
+                .unwrap();
+            let (project, read_history) = file::ProjectFile::read_with_history(&path).unwrap();
+            assert_projects_eq(&project, &new);
+            assert_eq!(read_history.as_ref(), Some(&history));
+
+            let mut canvas = project.canvas.clone();
+            read_history.unwrap().states[1]
+                .delta
+                .as_ref()
+                .unwrap()
+                .revert(&mut canvas);
+            assert_eq!(canvas, old.canvas);
+        }
+
+        let path = std::path::PathBuf::from("/tmp/history.pixylene");
+        file::ProjectFile { compress: false }
+            .write(&path, &new)
+            .unwrap();
+        assert_eq!(file::ProjectFile::read_with_history(&path).unwrap().1, None);
+    }
+
+    #[test]
+    fn canvas_delta_round_trip() {
+        use types::{PCoord, TruePixel, UCoord};
+
+        let edits: [(&str, fn(&mut project::Canvas)); 7] = [
+            ("pixel edits", |canvas| {
+                let layers = canvas.layers.to_true_mut().unwrap();
+                layers[0]
+                    .scene
+                    .set_pixel(UCoord { x: 3, y: 5 }, Some(TruePixel::BLACK))
+                    .unwrap();
+                layers[0]
+                    .scene
+                    .set_pixel(UCoord { x: 1, y: 2 }, None)
+                    .unwrap();
+                layers[1].opacity = 12;
+            }),
+            ("layer add", |canvas| {
+                let layers = canvas.layers.to_true_mut().unwrap();
+                layers.new_layer(Some(TruePixel::FAVOURITE)).unwrap();
+                layers.move_layer(2, 0).unwrap();
+            }),
+            ("layer delete", |canvas| {
+                canvas.layers.to_true_mut().unwrap().del_layer(0).unwrap();
+            }),
+            ("layer move", |canvas| {
+                canvas
+                    .layers
+                    .to_true_mut()
+                    .unwrap()
+                    .move_layer(0, 1)
+                    .unwrap();
+            }),
+            ("palette change", |canvas| {
+                canvas.palette.set_color(7, "#123456").unwrap();
+            }),
+            ("resize", |canvas| {
+                let mut layers = project::Layers::new(PCoord::new(8, 3).unwrap());
+                layers.new_layer(Some(TruePixel::BLACK)).unwrap();
+                canvas.layers = project::LayersType::True(layers);
+            }),
+            ("everything", |canvas| {
+                let layers = canvas.layers.to_true_mut().unwrap();
+                layers[1]
+                    .scene
+                    .set_pixel(UCoord { x: 0, y: 0 }, None)
+                    .unwrap();
+                layers.move_layer(0, 1).unwrap();
+                layers.duplicate_layer(1).unwrap();
+                layers.del_layer(0).unwrap();
+                canvas.palette.set_color(3, "#abcdef").unwrap();
+            }),
+        ];
+
+        let old = fixture_project().canvas;
+        for (name, edit) in edits {
+            let mut new = old.clone();
+            edit(&mut new);
+            let delta = project::CanvasDelta::between(&old, &new).unwrap();
+            assert!(delta.can_apply(&old) && delta.can_revert(&new), "{}", name);
+            //a move only stores the new order of the layers, which fits both states
+            if name != "layer move" {
+                assert!(
+                    !delta.can_apply(&new) && !delta.can_revert(&old),
+                    "{}",
+                    name
+                );
+            }
+
+            let mut canvas = old.clone();
+            delta.apply(&mut canvas);
+            assert_eq!(canvas, new, "{}", name);
+            delta.revert(&mut canvas);
+            assert_eq!(canvas, old, "{}", name);
+        }
+        assert_eq!(project::CanvasDelta::between(&old, &old), None);
+    }
+
+    #[test]
+    fn save_project_json() {
+        let path = std::path::PathBuf::from("/tmp/project.pixylene.json");
+        assert!(file::ProjectFile::is_json_path(&path));
+        let pixylene = Pixylene {
+            project: fixture_project(),
+        };
+        pixylene.save_project(&path, true).unwrap();
+        assert_projects_eq(
+            &file::ProjectFile::read_json(&path).unwrap(),
+            &fixture_project(),
+        );
+    }
+
+    #[test]
+    fn save_canvas_encodings() {
+        let mut indexed = Pixylene::new(
+            &PixyleneDefaults {
+                dim: types::PCoord::new(3, 5).unwrap(),
+                palette: project::Palette::gruvbox(),
+                repeat: types::PCoord::new(1, 2).unwrap(),
+            },
+            true,
+        );
+        let layers = indexed.project.canvas.layers.to_indexed_mut().unwrap();
+        layers.new_layer(Some(types::IndexedPixel(2))).unwrap();
+        layers[0]
+            .scene
+            .set_pixel(types::UCoord { x: 2, y: 4 }, Some(types::IndexedPixel(7)))
+            .unwrap();
+
+        for pixylene in [
+            Pixylene {
+                project: fixture_project(),
+            },
+            indexed,
+        ] {
+            for encoding in [
+                file::SceneEncoding::Verbose,
+                file::SceneEncoding::Rle,
+                file::SceneEncoding::Base64,
+            ] {
+                let path = std::path::PathBuf::from(format!("/tmp/canvas_{}.json", encoding));
+                pixylene.save_canvas(&path, encoding).unwrap();
+                let json = std::fs::read_to_string(&path).unwrap();
+                assert_eq!(
+                    json.contains(&format!("\"encoding\":\"{}\"", encoding)),
+                    encoding != file::SceneEncoding::Verbose,
+                );
+                assert_eq!(
+                    file::CanvasFile::read(&path).unwrap(),
+                    pixylene.project.canvas
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn corrupt_rle_canvas() {
+        let path = std::path::PathBuf::from("/tmp/canvas_corrupt_rle.json");
+        let pixylene = Pixylene {
+            project: fixture_project(),
+        };
+        pixylene
+            .save_canvas(&path, file::SceneEncoding::Rle)
+            .unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+
+        //runs past the area of the scene fail before being expanded, however long they are
+        for (row, valid) in [
+            ("18446744073709551615*#000000ff", false),
+            ("4*_ 18446744073709551615*#000000ff", false),
+            ("25*_", false),
+            ("24*_", true),
+        ] {
+            let mut json = json.clone();
+            let scene = &mut json["layers"]["True"]["layers"][0]["scene"];
+            let dim = (scene["dim"]["x"].as_u64(), scene["dim"]["y"].as_u64());
+            assert_eq!(dim, (Some(4), Some(6)));
+            scene["rows"] = serde_json::json!([row]);
+            std::fs::write(&path, json.to_string()).unwrap();
+            let read = file::CanvasFile::read(&path);
+            if valid {
+                assert!(read.unwrap().layers.to_true().unwrap()[0]
+                    .scene
+                    .grid()
+                    .all(|pixel| pixel.is_none()));
+            } else {
+                assert!(matches!(read, Err(file::CanvasFileError::DecodeError(..))));
+            }
+        }
+    }
+
+    #[test]
+    fn sparse_scene_storage() {
+        let dim = types::PCoord::new(70, 45).unwrap();
+        let mut scene = project::Scene::<types::TruePixel>::new_empty(dim);
+        let empty_size = scene.heap_size();
+        let mut expected = vec![None; dim.area() as usize];
+        for (x, y) in [(0, 0), (31, 31), (32, 32), (69, 44), (40, 3)] {
+            let pixel = types::TruePixel {
+                r: x as u8,
+                g: y as u8,
+                b: 7,
+                a: 255,
+            };
+            scene
+                .set_pixel(types::UCoord { x, y }, Some(pixel))
+                .unwrap();
+            expected[usize::from(x) * 45 + usize::from(y)] = Some(pixel);
+        }
+        assert_eq!(scene.grid().collect::<Vec<_>>(), expected);
+        assert_eq!(scene, project::Scene::new(dim, expected.clone()).unwrap());
+        assert!(scene.heap_size() > empty_size);
+        let diff = project::Scene::new_empty(dim).diff(&scene).unwrap();
+        assert_eq!(diff.len(), 5);
+        assert!(diff
+            .iter()
+            .all(|(coord, old, new)| old.is_none() && *new == scene.get_pixel(*coord).unwrap()));
+
+        //tiles empty in both scenes are filled with a single call each
+        let mut calls = 0;
+        let zipped = scene
+            .zip_with(&project::Scene::new_empty(dim), |_, a, b| {
+                calls += 1;
+                Ok::<_, ()>(a.or(b).or(Some(types::TruePixel::BLACK)))
+            })
+            .unwrap()
+            .unwrap();
+        assert_eq!(calls, 32 * 32 * 2 + 32 * 13 + 6 * 13 + 2);
+        let filled = expected
+            .iter()
+            .map(|pixel| pixel.or(Some(types::TruePixel::BLACK)))
+            .collect::<Vec<_>>();
+        assert_eq!(zipped.grid().collect::<Vec<_>>(), filled);
+        assert_eq!(zipped, project::Scene::new(dim, filled).unwrap());
+        assert!(scene
+            .zip_with(
+                &project::Scene::new_empty(types::PCoord::new(1, 1).unwrap()),
+                |_, a, _| { Ok::<_, ()>(a) }
+            )
+            .is_none());
+
+        for (x, y) in [(0, 0), (31, 31), (32, 32), (69, 44), (40, 3)] {
+            scene.set_pixel(types::UCoord { x, y }, None).unwrap();
+        }
+        assert_eq!(scene, project::Scene::new_empty(dim));
+        assert_eq!(scene.heap_size(), empty_size);
+    }
+
+    #[test]
+    fn cursor_set_operations() {
+        let mut project = fixture_project();
+        _ = project.clear_cursors();
+        assert_eq!(
+            project
+                .add_cursors_at_color(Some(types::TruePixel::FAVOURITE), 0)
+                .unwrap(),
+            1
+        );
+        assert!(project
+            .is_cursor_at(&(types::UCoord { x: 1, y: 2 }, 0))
+            .unwrap());
+
+        project.grow_cursors();
+        assert_eq!(project.num_cursors(), 5);
+        project.shrink_cursors();
+        assert_eq!(project.num_cursors(), 1);
+        project.grow_cursors();
+        let at = |x, y| types::UCoord { x, y };
+        assert_eq!(
+            project.cursor_bounds(),
+            std::collections::HashMap::from([(0, (at(0, 1), at(2, 3)))])
+        );
+        project.bound_cursors();
+        assert_eq!(project.num_cursors(), 9);
+        project.invert_cursors(0).unwrap();
+        assert_eq!(project.num_cursors(), 24 - 9);
+
+        project.save_cursors("a");
+        _ = project.clear_cursors();
+        assert!(project.restore_cursors("b").is_err());
+        project.restore_cursors("a").unwrap();
+        assert_eq!(project.num_cursors(), 24 - 9);
+
+        assert!(project.add_cursors_at_index(1, 0).is_err());
+        assert_eq!(project.add_cursors_at_content(1).unwrap(), 24);
+        assert_eq!(project.add_cursors_at_all_content(), 1);
+
+        let path = std::path::PathBuf::from("/tmp/project_cursor_registers.pixylene");
+        file::ProjectFile { compress: false }
+            .write(&path, &project)
+            .unwrap();
+        let mut read = file::ProjectFile::read(&path).unwrap();
+        assert_projects_eq(&read, &project);
+        _ = read.clear_cursors();
+        read.restore_cursors("a").unwrap();
+        assert_eq!(read.num_cursors(), 24 - 9);
+    }
+
+    #[test]
+    fn palette_secondary_and_recent() {
+        let mut palette =
+            project::Palette::from(&[(1, "#000000"), (2, "#ffffff"), (3, "#ff0000")]).unwrap();
+        assert_eq!(palette.equipped(), 1);
+        assert_eq!(palette.secondary().unwrap(), 2);
+
+        palette.swap().unwrap();
+        assert_eq!(palette.equipped(), 2);
+        assert_eq!(palette.secondary().unwrap(), 1);
+        assert_eq!(*palette.get_secondary().unwrap(), types::TruePixel::BLACK);
+
+        //colors set later don't become the secondary color by themselves
+        let mut single = project::Palette::from(&[(4, "#000000")]).unwrap();
+        assert!(matches!(
+            single.secondary(),
+            Err(project::PaletteError::NoSecondary)
+        ));
+        single.set_color(5, "#ffffff").unwrap();
+        assert!(single.get_secondary().is_err());
+        assert!(matches!(
+            single.swap(),
+            Err(project::PaletteError::NoSecondary)
+        ));
+        single.equip_secondary(5).unwrap();
+        single.swap().unwrap();
+        assert_eq!(single.equipped(), 5);
+
+        palette.equip(3).unwrap();
+        palette.equip(2).unwrap();
+        assert_eq!(
+            palette
+                .recent()
+                .map(|(index, _)| *index)
+                .collect::<Vec<u8>>(),
+            vec![2, 3]
+        );
+        for _ in 0..project::Palette::RECENT_LEN {
+            palette.equip(3).unwrap();
+            palette.equip(1).unwrap();
+        }
+        assert_eq!(
+            palette
+                .recent()
+                .map(|(index, _)| *index)
+                .collect::<Vec<u8>>(),
+            vec![1, 3, 2]
+        );
+
+        palette.unset_color(1);
+        assert_eq!(palette.get_secondary_index(), None);
+        assert!(palette.swap().is_err());
+        assert_eq!(
+            palette
+                .recent()
+                .map(|(index, _)| *index)
+                .collect::<Vec<u8>>(),
+            vec![3, 2]
+        );
+
+        let json = serde_json::to_string(&palette).unwrap();
+        assert_eq!(
+            serde_json::from_str::<project::Palette>(&json).unwrap(),
+            palette
+        );
+    }
+
+    #[test]
+    fn blend_mode_from_str() {
+        use types::BlendMode;
+
+        for blend_mode in [
+            BlendMode::Normal,
+            BlendMode::Overwrite,
+            BlendMode::Composite(64, 191),
+        ] {
+            assert_eq!(blend_mode.to_string().parse::<BlendMode>(), Ok(blend_mode));
+        }
+        assert_eq!("Overwrite".parse::<BlendMode>(), Ok(BlendMode::Overwrite));
+        assert!("composite:256".parse::<BlendMode>().is_err());
+        assert!("multiply".parse::<BlendMode>().is_err());
+    }
+
+    #[test]
+    fn dither_patterns() {
+        use types::{IndexedPixel, PCoord, UCoord};
+
+        let at = |x, y| UCoord { x, y };
+        assert!(project::Pattern::ordered(17).is_err());
+        for level in 0..=project::Pattern::LEVELS {
+            let pattern = project::Pattern::ordered(level).unwrap();
+            let on = (0..4)
+                .flat_map(|x| (0..4).map(move |y| at(x, y)))
+                .filter(|coord| pattern.is_on(*coord))
+                .count();
+            assert_eq!(on, level as usize);
+        }
+
+        //half is a checkerboard that tiles over the canvas
+        let checker = project::Pattern::ordered(8).unwrap();
+        for x in 0..9 {
+            for y in 0..9 {
+                assert_eq!(checker.is_on(at(x, y)), (x + y) % 2 == 0);
+            }
+        }
+
+        let mut scene = project::Scene::<IndexedPixel>::new_empty(PCoord::new(6, 6).unwrap());
+        scene.set_pixel(at(2, 3), Some(IndexedPixel(1))).unwrap();
+        scene.set_pixel(at(3, 4), Some(IndexedPixel(1))).unwrap();
+        let custom =
+            project::Pattern::from_region(&scene, at(2, 3), PCoord::new(2, 2).unwrap()).unwrap();
+        assert_eq!(custom.level(), None);
+        assert!(custom.is_on(at(0, 0)) && custom.is_on(at(5, 7)));
+        assert!(!custom.is_on(at(0, 1)) && !custom.is_on(at(4, 3)));
+        assert!(
+            project::Pattern::from_region(&scene, at(5, 5), PCoord::new(2, 2).unwrap()).is_err()
+        );
+        assert!(project::Pattern::from_scene(&scene).is_on(at(8, 9)));
+    }
+
+    #[test]
+    fn palette_ramps_and_shading() {
+        let mut palette = project::Palette::from(&[
+            (1, "#000000"),
+            (2, "#555555"),
+            (3, "#aaaaaa"),
+            (4, "#ffffff"),
+            (5, "#ff0000"),
+        ])
+        .unwrap();
+        assert!(palette.add_ramp(vec![1, 2, 9]).is_err());
+        palette.add_ramp(vec![1, 2, 3, 4]).unwrap();
+        assert_eq!(palette.shade(2, true), Some(3));
+        assert_eq!(palette.shade(2, false), Some(1));
+        assert_eq!(palette.shade(4, true), Some(4));
+        assert_eq!(palette.shade(1, false), Some(1));
+        assert_eq!(palette.shade(5, true), None);
+
+        palette.unset_color(3);
+        assert_eq!(palette.shade(2, true), Some(4));
+        let json = serde_json::to_string(&palette).unwrap();
+        assert_eq!(
+            serde_json::from_str::<project::Palette>(&json).unwrap(),
+            palette
+        );
+        palette.clear_ramps();
+        assert_eq!(palette.ramps().count(), 0);
+
+        let gray = types::TruePixel::from_hex("#777777").unwrap();
+        let (lighter, darker) = (gray.shade(0.1), gray.shade(-0.1));
+        assert!(lighter.r > gray.r && lighter.r == lighter.g && lighter.g == lighter.b);
+        assert!(darker.r < gray.r && darker.a == 255);
+        assert_eq!(gray.shade(0.0), gray);
+        assert_eq!(
+            gray.shade(1.0),
+            types::TruePixel::from_hex("#ffffff").unwrap()
+        );
+        assert_eq!(gray.shade(-1.0), types::TruePixel::BLACK);
+    }
+    #[test]
+    fn replace_colors_and_indexes() {
+        use project::{Canvas, Layers, LayersType, ReplaceScope};
+        use types::{IndexedPixel, PCoord, TruePixel, UCoord};
+
+        let palette =
+            project::Palette::from(&[(1, "#000000"), (2, "#ff0000"), (3, "#0000ff")]).unwrap();
+        let dim = PCoord::new(2, 2).unwrap();
+        let red = TruePixel::from_hex("#ff0000").unwrap();
+        let reddish = TruePixel::from_hex("#f80404").unwrap();
+        let blue = TruePixel::from_hex("#0000ff").unwrap();
+
+        let mut layers = Layers::<TruePixel>::new(dim);
+        layers.new_layer(Some(red)).unwrap();
+        layers.new_layer(Some(reddish)).unwrap();
+        let mut canvas = Canvas {
+            layers: LayersType::True(layers),
+            palette: palette.clone(),
+        };
+        assert!(canvas.remap_index(1, 2, &ReplaceScope::All).is_err());
+        assert!(canvas
+            .replace_color(red, blue, 0, &ReplaceScope::Layer(2))
+            .is_err());
+        assert_eq!(
+            canvas
+                .replace_color(red, blue, 0, &ReplaceScope::All)
+                .unwrap(),
+            4
+        );
+        assert_eq!(
+            canvas
+                .replace_color(red, blue, 8, &ReplaceScope::Layer(1))
+                .unwrap(),
+            4
+        );
+        assert_eq!(
+            canvas
+                .merged_true_scene(None)
+                .get_pixel(UCoord { x: 1, y: 1 })
+                .unwrap(),
+            Some(blue)
+        );
+
+        let mut layers = Layers::<IndexedPixel>::new(dim);
+        layers.new_layer(Some(IndexedPixel(2))).unwrap();
+        layers.new_layer(None).unwrap();
+        let mut canvas = Canvas {
+            layers: LayersType::Indexed(layers),
+            palette,
+        };
+        let at = |canvas: &Canvas, x: u16, y: u16, layer: u16| {
+            canvas.layers.to_indexed().unwrap()[layer]
+                .scene
+                .get_pixel(UCoord { x, y })
+                .unwrap()
+        };
+        assert!(canvas.remap_index(2, 9, &ReplaceScope::All).is_err());
+        assert!(canvas
+            .replace_color(red, blue, 0, &ReplaceScope::All)
+            .is_err());
+        let selection = ReplaceScope::Pixels(vec![
+            (UCoord { x: 0, y: 0 }, 0),
+            (UCoord { x: 0, y: 0 }, 0),
+            (UCoord { x: 0, y: 1 }, 1),
+        ]);
+        assert_eq!(canvas.swap_indexes(2, 3, &selection).unwrap(), 1);
+        assert_eq!(at(&canvas, 0, 0, 0), Some(IndexedPixel(3)));
+        assert_eq!(at(&canvas, 0, 1, 1), None);
+        assert_eq!(canvas.swap_indexes(3, 2, &ReplaceScope::All).unwrap(), 4);
+        assert_eq!(at(&canvas, 0, 0, 0), Some(IndexedPixel(2)));
+        assert_eq!(at(&canvas, 1, 1, 0), Some(IndexedPixel(3)));
+        assert_eq!(
+            canvas.remap_index(3, 1, &ReplaceScope::Layer(0)).unwrap(),
+            3
+        );
+        assert_eq!(at(&canvas, 1, 0, 0), Some(IndexedPixel(1)));
+    }
 }
@@ -1,6 +1,9 @@
 use crate::{
-    file::{CanvasFile, CanvasFileError, PngFile, PngFileError, ProjectFile, ProjectFileError},
-    project::{Canvas, Layers, LayersType, Palette, Project, SceneError},
+    file::{
+        CanvasFile, CanvasFileError, Downsampler, PngFile, PngFileError, ProjectFile,
+        ProjectFileError, SceneEncoding, Upscaler,
+    },
+    project::{Canvas, History, Layers, LayersType, Palette, Project, SceneError},
     types::{IndexedPixel, PCoord, TruePixel},
 };
 use std::path::PathBuf;
@@ -42,33 +45,77 @@ impl Pixylene {
             })
             .map_err(|error| PixyleneError::CanvasFileError(error))
     }
-    pub fn save_canvas(&self, path: &PathBuf) -> Result<(), PixyleneError> {
-        CanvasFile::write(path, &self.project.canvas)
+    /// Saves to a Canvas file, encoding its Scenes with `encoding`
+    pub fn save_canvas(
+        &self,
+        path: &PathBuf,
+        encoding: SceneEncoding,
+    ) -> Result<(), PixyleneError> {
+        CanvasFile::write(path, &self.project.canvas, encoding)
             .map_err(|err| PixyleneError::CanvasFileError(err))
     }
 
     //To/Fro Project File
+    /// Opens a Project file, reading it as human-readable if the path has the extension
+    /// [`JSON_EXTENSION`](ProjectFile::JSON_EXTENSION)
     pub fn open_project(path: &PathBuf) -> Result<Self, PixyleneError> {
-        match (ProjectFile { version: 0 }).read(path) {
-            Ok(project) => Ok(Pixylene { project }),
+        Self::open_project_with_history(path).map(|(pixylene, _)| pixylene)
+    }
+    /// Opens a Project file like [`open_project`](Pixylene::open_project), along with the undo
+    /// [`History`] saved in it, if any
+    pub fn open_project_with_history(
+        path: &PathBuf,
+    ) -> Result<(Self, Option<History>), PixyleneError> {
+        let project = if ProjectFile::is_json_path(path) {
+            ProjectFile::read_json(path).map(|project| (project, None))
+        } else {
+            ProjectFile::read_with_history(path)
+        };
+        match project {
+            Ok((project, history)) => Ok((Pixylene { project }, history)),
             Err(error) => Err(PixyleneError::ProjectFileError(error)),
         }
     }
-    pub fn save_project(&self, path: &PathBuf) -> Result<(), PixyleneError> {
-        (ProjectFile { version: 0 })
-            .write(path, &self.project)
-            .map_err(|err| PixyleneError::ProjectFileError(err))
+    /// Saves to a Project file, writing it as human-readable if the path has the extension
+    /// [`JSON_EXTENSION`](ProjectFile::JSON_EXTENSION), in which case `compress` is ignored
+    pub fn save_project(&self, path: &PathBuf, compress: bool) -> Result<(), PixyleneError> {
+        self.save_project_with_history(path, compress, None)
+    }
+    /// Saves to a Project file like [`save_project`](Pixylene::save_project), along with an undo
+    /// [`History`] whose current state is the Project's Canvas
+    ///
+    /// The history is not saved to human-readable Project files.
+    pub fn save_project_with_history(
+        &self,
+        path: &PathBuf,
+        compress: bool,
+        history: Option<&History>,
+    ) -> Result<(), PixyleneError> {
+        if ProjectFile::is_json_path(path) {
+            ProjectFile::write_json(path, &self.project)
+        } else {
+            (ProjectFile { compress }).write_with_history(path, &self.project, history)
+        }
+        .map_err(|err| PixyleneError::ProjectFileError(err))
     }
 
     //To/Fro PNG File
+    /// Imports a png as a new project, resizing it to `resize` if provided
+    ///
+    /// If a `downsampler` is provided, the png is instead downsampled with it to `resize`, or to
+    /// its [detected pixel grid](PngFile::detect_pixel_grid) if `resize` is not provided.
     pub fn import(
         path: &PathBuf,
         resize: Option<PCoord<u32>>,
+        downsampler: Option<Downsampler>,
         defaults: &PixyleneDefaults,
     ) -> Result<Pixylene, PixyleneError> {
         let mut png = PngFile::read(path)?;
-        if let Some(resize) = resize {
-            png.resize(resize)?;
+        match (resize, downsampler) {
+            (Some(resize), Some(downsampler)) => png.downsample(resize, downsampler)?,
+            (None, Some(downsampler)) => png.downsample(png.detect_pixel_grid()?, downsampler)?,
+            (Some(resize), None) => png.resize(resize)?,
+            (None, None) => (),
         }
         let mut project = Project::new(png.to_canvas()?);
         if matches!(project.canvas.layers, LayersType::True(_)) {
@@ -79,8 +126,18 @@ impl Pixylene {
         Ok(Pixylene { project })
     }
 
-    pub fn export(&self, resize: Option<PCoord<u32>>, path: &PathBuf) -> Result<(), PixyleneError> {
+    /// Exports the canvas to a png, first upscaling it with `upscaler` and then resizing the
+    /// upscaled png to `resize`
+    pub fn export(
+        &self,
+        upscaler: Option<Upscaler>,
+        resize: Option<PCoord<u32>>,
+        path: &PathBuf,
+    ) -> Result<(), PixyleneError> {
         let mut png = PngFile::from_canvas(&self.project.canvas)?;
+        if let Some(upscaler) = upscaler {
+            png.upscale(upscaler)?;
+        }
         if let Some(resize) = resize {
             png.resize(resize)?;
         }
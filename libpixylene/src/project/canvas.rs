@@ -1,8 +1,9 @@
-use super::{Layer, Layers, Palette, Scene};
-use crate::types::{BlendMode, IndexedPixel, PCoord, TruePixel};
+use super::{Layer, Layers, LayersError, Palette, PaletteError, Scene, SceneError};
+use crate::types::{BlendMode, IndexedPixel, PCoord, Pixel, TruePixel, UCoord};
 use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, fmt};
 
-#[derive(Clone, Debug, PartialEq, Savefile, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum LayersType {
     True(Layers<TruePixel>),
     Indexed(Layers<IndexedPixel>),
@@ -54,7 +55,20 @@ impl LayersType {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Savefile, Serialize, Deserialize)]
+/// The pixels of a [`Canvas`] that a replacement of colors or indices applies to
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReplaceScope {
+    /// Every pixel of every layer
+    All,
+
+    /// Every pixel of the layer at the given index
+    Layer(u16),
+
+    /// The given pixels, each a coordinate on the layer at a layer index
+    Pixels(Vec<(UCoord, u16)>),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Canvas {
     pub layers: LayersType,
     pub palette: Palette,
@@ -113,7 +127,7 @@ impl Canvas {
                         .enumerate()
                         .map(|(i, p)| {
                             if let Some(p) = p {
-                                new_buf[i] = Some(*p);
+                                new_buf[i] = Some(p);
                             }
                         })
                         .collect::<()>();
@@ -123,4 +137,186 @@ impl Canvas {
             LayersType::True(_) => Err(()),
         }
     }
+
+    /// Replaces every pixel of a true-color Canvas within the scope that is of the given color,
+    /// or differs from it by at most the tolerance in every channel, with another color,
+    /// returning the number of pixels replaced
+    ///
+    /// `Note`: This method may fail with the [`ExpectingTrueCanvas`][etc], [`LayersError`][le] or
+    /// [`SceneError`][se] error variants only.
+    ///
+    /// [etc]: CanvasError::ExpectingTrueCanvas
+    /// [le]: CanvasError::LayersError
+    /// [se]: CanvasError::SceneError
+    pub fn replace_color(
+        &mut self,
+        from: TruePixel,
+        to: TruePixel,
+        tolerance: u8,
+        scope: &ReplaceScope,
+    ) -> Result<u64, CanvasError> {
+        let layers = self
+            .layers
+            .to_true_mut()
+            .map_err(|_| CanvasError::ExpectingTrueCanvas)?;
+        let matches = |pixel: TruePixel| {
+            [
+                (pixel.r, from.r),
+                (pixel.g, from.g),
+                (pixel.b, from.b),
+                (pixel.a, from.a),
+            ]
+            .iter()
+            .all(|(a, b)| a.abs_diff(*b) <= tolerance)
+        };
+        map_pixels(layers, scope, |pixel| matches(pixel).then_some(to))
+    }
+
+    /// Remaps every pixel of an indexed Canvas within the scope that is of one palette index to
+    /// another, returning the number of pixels remapped
+    ///
+    /// `Note`: This method may fail with the [`ExpectingIndexedCanvas`][eic],
+    /// [`PaletteError`][pe], [`LayersError`][le] or [`SceneError`][se] error variants only.
+    ///
+    /// [eic]: CanvasError::ExpectingIndexedCanvas
+    /// [pe]: CanvasError::PaletteError
+    /// [le]: CanvasError::LayersError
+    /// [se]: CanvasError::SceneError
+    pub fn remap_index(
+        &mut self,
+        from: u8,
+        to: u8,
+        scope: &ReplaceScope,
+    ) -> Result<u64, CanvasError> {
+        self.palette.get_color(to)?;
+        let layers = self
+            .layers
+            .to_indexed_mut()
+            .map_err(|_| CanvasError::ExpectingIndexedCanvas)?;
+        map_pixels(layers, scope, |pixel| {
+            (pixel.0 == from).then_some(IndexedPixel(to))
+        })
+    }
+
+    /// Swaps the pixels of two palette indices with each other everywhere within the scope of an
+    /// indexed Canvas, returning the number of pixels swapped
+    ///
+    /// `Note`: This method may fail with the [`ExpectingIndexedCanvas`][eic],
+    /// [`PaletteError`][pe], [`LayersError`][le] or [`SceneError`][se] error variants only.
+    ///
+    /// [eic]: CanvasError::ExpectingIndexedCanvas
+    /// [pe]: CanvasError::PaletteError
+    /// [le]: CanvasError::LayersError
+    /// [se]: CanvasError::SceneError
+    pub fn swap_indexes(&mut self, a: u8, b: u8, scope: &ReplaceScope) -> Result<u64, CanvasError> {
+        self.palette.get_color(a)?;
+        self.palette.get_color(b)?;
+        let layers = self
+            .layers
+            .to_indexed_mut()
+            .map_err(|_| CanvasError::ExpectingIndexedCanvas)?;
+        map_pixels(layers, scope, |pixel| match pixel.0 {
+            index if index == a => Some(IndexedPixel(b)),
+            index if index == b => Some(IndexedPixel(a)),
+            _ => None,
+        })
+    }
+}
+
+//replaces every present pixel within the scope that the mapping returns a new pixel for, counting
+//the pixels matched & visiting each pixel only once so that swaps are not undone
+fn map_pixels<T: Pixel + PartialEq>(
+    layers: &mut Layers<T>,
+    scope: &ReplaceScope,
+    map: impl Fn(T) -> Option<T>,
+) -> Result<u64, CanvasError> {
+    let dim = layers.dim();
+    let whole = |layer: u16| {
+        (0..dim.x()).flat_map(move |x| (0..dim.y()).map(move |y| (UCoord { x, y }, layer)))
+    };
+    let pixels: Vec<(UCoord, u16)> = match scope {
+        ReplaceScope::All => (0..layers.len()).flat_map(whole).collect(),
+        ReplaceScope::Layer(layer) => {
+            layers.get_layer(*layer)?;
+            whole(*layer).collect()
+        }
+        ReplaceScope::Pixels(pixels) => {
+            let mut seen = HashSet::new();
+            pixels
+                .iter()
+                .filter(|pixel| seen.insert(**pixel))
+                .copied()
+                .collect()
+        }
+    };
+
+    let mut replaced = 0;
+    for (coord, layer) in pixels {
+        let scene = &mut layers.get_layer_mut(layer)?.scene;
+        if let Some(pixel) = scene.get_pixel(coord)? {
+            if let Some(new_pixel) = map(pixel) {
+                if new_pixel != pixel {
+                    scene.set_pixel(coord, Some(new_pixel))?;
+                }
+                replaced += 1;
+            }
+        }
+    }
+    Ok(replaced)
+}
+
+// Error Types
+
+/// Error enum to describe various errors returned by Canvas methods
+#[derive(Debug)]
+pub enum CanvasError {
+    /// Error that occurs when trying to replace true colors on a Canvas that is indexed
+    ExpectingTrueCanvas,
+
+    /// Error that occurs when trying to remap or swap palette indices on a Canvas that is not
+    /// indexed
+    ExpectingIndexedCanvas,
+
+    /// Error that is propagated when trying to use a palette index that has no color
+    PaletteError(PaletteError),
+
+    /// Error that is propagated when trying to access a Layer that is out of bounds for the Canvas
+    LayersError(LayersError),
+
+    /// Error that is propagated when trying to access a pixel that is out of bounds for the Canvas
+    SceneError(SceneError),
+}
+
+impl fmt::Display for CanvasError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use CanvasError::*;
+        match self {
+            ExpectingTrueCanvas => write!(f, "true colors can only be replaced on a true canvas"),
+            ExpectingIndexedCanvas => write!(
+                f,
+                "palette indices can only be remapped or swapped on an indexed canvas",
+            ),
+            PaletteError(error) => write!(f, "{}", error),
+            LayersError(error) => write!(f, "{}", error),
+            SceneError(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl From<PaletteError> for CanvasError {
+    fn from(item: PaletteError) -> CanvasError {
+        CanvasError::PaletteError(item)
+    }
+}
+
+impl From<LayersError> for CanvasError {
+    fn from(item: LayersError) -> CanvasError {
+        CanvasError::LayersError(item)
+    }
+}
+
+impl From<SceneError> for CanvasError {
+    fn from(item: SceneError) -> CanvasError {
+        CanvasError::SceneError(item)
+    }
 }
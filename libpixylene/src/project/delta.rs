@@ -0,0 +1,442 @@
+use super::{Canvas, Layer, Layers, LayersType, Palette};
+use crate::types::{BlendMode, IndexedPixel, PCoord, Pixel, TruePixel, UCoord};
+
+use serde::{Deserialize, Serialize};
+use std::mem::size_of;
+
+/// The difference between two states of a [`Canvas`], storing only what changed between them
+///
+/// A delta can be [applied](CanvasDelta::apply) to a Canvas in its old state to change it into the
+/// new state, and [reverted](CanvasDelta::revert) from a Canvas in its new state to change it back.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CanvasDelta {
+    layers: LayersDelta,
+    /// The old & new palettes, if the palette was edited
+    palette: Option<Box<(Palette, Palette)>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum LayersDelta {
+    Unchanged,
+    True(LayerChanges<TruePixel>),
+    Indexed(LayerChanges<IndexedPixel>),
+    /// The old & new layers, stored whole because the Canvas changed its color type or dimensions
+    Replaced(Box<(LayersType, LayersType)>),
+}
+
+/// The opacity, mute & blend-mode of a layer
+type Properties = (u8, bool, BlendMode);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct LayerChanges<T: Pixel> {
+    /// How layers were added, removed or moved, if they were
+    structure: Option<Structure<T>>,
+    /// The edits to layers present in both states
+    edits: Vec<LayerEdit<T>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Structure<T: Pixel> {
+    /// For every layer of the new state, the index of the same layer in the old state, or `None`
+    /// if it was added
+    sources: Vec<Option<u16>>,
+    /// The layers added, in the order of their indexes in the new state
+    added: Vec<Layer<T>>,
+    /// The layers removed along with their indexes in the old state
+    removed: Vec<(u16, Layer<T>)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct LayerEdit<T> {
+    /// The index of the layer in the new state
+    index: u16,
+    /// The old & new properties of the layer, if they changed
+    properties: Option<(Properties, Properties)>,
+    /// The coordinates, old pixels & new pixels of every pixel that changed
+    pixels: Vec<(UCoord, Option<T>, Option<T>)>,
+}
+
+impl CanvasDelta {
+    /// Returns the delta that changes `old` into `new`, or `None` if they are the same
+    pub fn between(old: &Canvas, new: &Canvas) -> Option<CanvasDelta> {
+        let layers = match (&old.layers, &new.layers) {
+            (LayersType::True(a), LayersType::True(b)) if a.dim() == b.dim() => {
+                LayerChanges::between(a, b).map_or(LayersDelta::Unchanged, LayersDelta::True)
+            }
+            (LayersType::Indexed(a), LayersType::Indexed(b)) if a.dim() == b.dim() => {
+                LayerChanges::between(a, b).map_or(LayersDelta::Unchanged, LayersDelta::Indexed)
+            }
+            (a, b) => LayersDelta::Replaced(Box::new((a.clone(), b.clone()))),
+        };
+        let palette = (old.palette != new.palette)
+            .then(|| Box::new((old.palette.clone(), new.palette.clone())));
+
+        if palette.is_none() && matches!(layers, LayersDelta::Unchanged) {
+            None
+        } else {
+            Some(CanvasDelta { layers, palette })
+        }
+    }
+
+    /// Changes the old state of the Canvas into the new one
+    pub fn apply(&self, canvas: &mut Canvas) {
+        self.step(canvas, true);
+    }
+
+    /// Changes the new state of the Canvas back into the old one
+    pub fn revert(&self, canvas: &mut Canvas) {
+        self.step(canvas, false);
+    }
+
+    /// Returns whether the Canvas is in the old state as far as this delta is concerned, i.e.,
+    /// whether this delta can be [applied](CanvasDelta::apply) to it
+    pub fn can_apply(&self, canvas: &Canvas) -> bool {
+        self.matches(canvas, true)
+    }
+
+    /// Returns whether the Canvas is in the new state as far as this delta is concerned, i.e.,
+    /// whether this delta can be [reverted](CanvasDelta::revert) from it
+    pub fn can_revert(&self, canvas: &Canvas) -> bool {
+        self.matches(canvas, false)
+    }
+
+    /// Returns the approximate number of bytes taken by this delta
+    pub fn size(&self) -> usize {
+        let layers_size = |layers: &LayersType| match layers {
+            LayersType::True(layers) => layers.layers().map(layer_size).sum::<usize>(),
+            LayersType::Indexed(layers) => layers.layers().map(layer_size).sum::<usize>(),
+        };
+        let palette_size = |palette: &Palette| {
+            size_of::<Palette>() + palette.colors().count() * size_of::<(u8, TruePixel)>()
+        };
+
+        size_of::<Self>()
+            + match &self.layers {
+                LayersDelta::Unchanged => 0,
+                LayersDelta::True(changes) => changes.size(),
+                LayersDelta::Indexed(changes) => changes.size(),
+                LayersDelta::Replaced(layers) => layers_size(&layers.0) + layers_size(&layers.1),
+            }
+            + self
+                .palette
+                .as_ref()
+                .map(|palette| palette_size(&palette.0) + palette_size(&palette.1))
+                .unwrap_or(0)
+    }
+
+    fn matches(&self, canvas: &Canvas, forward: bool) -> bool {
+        let palette_matches = self
+            .palette
+            .as_ref()
+            .is_none_or(|palette| &canvas.palette == if forward { &palette.0 } else { &palette.1 });
+        palette_matches
+            && match (&self.layers, &canvas.layers) {
+                (LayersDelta::Unchanged, _) => true,
+                (LayersDelta::Replaced(replaced), layers) => {
+                    layers == if forward { &replaced.0 } else { &replaced.1 }
+                }
+                (LayersDelta::True(changes), LayersType::True(layers)) => {
+                    changes.matches(layers, forward)
+                }
+                (LayersDelta::Indexed(changes), LayersType::Indexed(layers)) => {
+                    changes.matches(layers, forward)
+                }
+                _ => false,
+            }
+    }
+
+    fn step(&self, canvas: &mut Canvas, forward: bool) {
+        if let LayersDelta::Replaced(layers) = &self.layers {
+            canvas.layers = if forward { &layers.1 } else { &layers.0 }.clone();
+        } else {
+            match (&self.layers, &mut canvas.layers) {
+                (LayersDelta::True(changes), LayersType::True(layers)) => {
+                    changes.step(layers, forward)
+                }
+                (LayersDelta::Indexed(changes), LayersType::Indexed(layers)) => {
+                    changes.step(layers, forward)
+                }
+                _ => (),
+            }
+        }
+        if let Some(palette) = &self.palette {
+            canvas.palette = if forward { &palette.1 } else { &palette.0 }.clone();
+        }
+    }
+}
+
+impl<T: Pixel + PartialEq> LayerChanges<T> {
+    fn between(old: &Layers<T>, new: &Layers<T>) -> Option<LayerChanges<T>> {
+        let old = old.layers().collect::<Vec<&Layer<T>>>();
+        let new = new.layers().collect::<Vec<&Layer<T>>>();
+
+        let identity = || (0..new.len()).map(|j| Some(j as u16)).collect::<Vec<_>>();
+        let sources = if old.len() == new.len() {
+            //a reordering of the same layers is a move, otherwise layers are edited in place
+            moved(&old, &new).unwrap_or_else(identity)
+        } else {
+            aligned(&old, &new)
+        };
+
+        let structure = (sources != identity()).then(|| Structure {
+            added: sources
+                .iter()
+                .zip(new.iter())
+                .filter(|(source, _)| source.is_none())
+                .map(|(_, layer)| (*layer).clone())
+                .collect(),
+            removed: (0..old.len())
+                .filter(|i| !sources.contains(&Some(*i as u16)))
+                .map(|i| (i as u16, old[i].clone()))
+                .collect(),
+            sources: sources.clone(),
+        });
+
+        let mut edits = Vec::new();
+        for (j, source) in sources.iter().enumerate() {
+            if let Some(i) = source {
+                let (a, b) = (old[usize::from(*i)], new[j]);
+                let properties = ((a.opacity, a.mute, a.blend_mode)
+                    != (b.opacity, b.mute, b.blend_mode))
+                    .then_some((
+                        (a.opacity, a.mute, a.blend_mode),
+                        (b.opacity, b.mute, b.blend_mode),
+                    ));
+                let pixels = a.scene.diff(&b.scene).unwrap(); //wont fail because layers of a
+                                                              //canvas have the same dimensions
+                if properties.is_some() || !pixels.is_empty() {
+                    edits.push(LayerEdit {
+                        index: j as u16,
+                        properties,
+                        pixels,
+                    });
+                }
+            }
+        }
+
+        if structure.is_none() && edits.is_empty() {
+            None
+        } else {
+            Some(LayerChanges { structure, edits })
+        }
+    }
+
+    fn matches(&self, layers: &Layers<T>, forward: bool) -> bool {
+        let len = usize::from(layers.len());
+        let expected_len = match &self.structure {
+            Some(structure) if !structure.is_valid(layers.dim()) => return false,
+            Some(structure) if forward => structure.old_len(),
+            Some(structure) => structure.sources.len(),
+            None => len,
+        };
+        if len != expected_len {
+            return false;
+        }
+
+        let removed_match = match &self.structure {
+            Some(structure) if forward => structure
+                .removed
+                .iter()
+                .all(|(i, layer)| layers.get_layer(*i).ok() == Some(layer)),
+            _ => true,
+        };
+        removed_match
+            && self.edits.iter().all(|edit| {
+                //edits are indexed by the new state, so their layers are found in the old state
+                //through the sources
+                let index = match &self.structure {
+                    Some(structure) if forward => structure
+                        .sources
+                        .get(usize::from(edit.index))
+                        .copied()
+                        .flatten(),
+                    _ => Some(edit.index),
+                };
+                let Some(layer) = index.and_then(|index| layers.get_layer(index).ok()) else {
+                    return false;
+                };
+                edit.properties.is_none_or(|(old, new)| {
+                    (layer.opacity, layer.mute, layer.blend_mode) == if forward { old } else { new }
+                }) && edit.pixels.iter().all(|(coord, old, new)| {
+                    layer.scene.get_pixel(*coord).ok() == Some(if forward { *old } else { *new })
+                })
+            })
+    }
+}
+
+impl<T: Pixel> LayerChanges<T> {
+    fn step(&self, layers: &mut Layers<T>, forward: bool) {
+        //edits are indexed by the new state, so they are undone before restructuring
+        if forward {
+            if let Some(structure) = &self.structure {
+                structure.apply(layers);
+            }
+        }
+        for edit in &self.edits {
+            let layer = layers.get_layer_mut(edit.index).unwrap(); //wont fail because edits are
+                                                                   //indexed by the new state
+            if let Some((old, new)) = edit.properties {
+                (layer.opacity, layer.mute, layer.blend_mode) = if forward { new } else { old };
+            }
+            for (coord, old, new) in &edit.pixels {
+                layer
+                    .scene
+                    .set_pixel(*coord, if forward { *new } else { *old })
+                    .unwrap(); //wont fail because coordinates were taken from a scene of the
+                               //same dimensions
+            }
+        }
+        if !forward {
+            if let Some(structure) = &self.structure {
+                structure.revert(layers);
+            }
+        }
+    }
+
+    fn size(&self) -> usize {
+        self.structure
+            .as_ref()
+            .map(|structure| {
+                structure.sources.len() * size_of::<Option<u16>>()
+                    + structure.added.iter().map(layer_size).sum::<usize>()
+                    + structure
+                        .removed
+                        .iter()
+                        .map(|(_, layer)| size_of::<u16>() + layer_size(layer))
+                        .sum::<usize>()
+            })
+            .unwrap_or(0)
+            + self
+                .edits
+                .iter()
+                .map(|edit| {
+                    size_of::<LayerEdit<T>>()
+                        + edit.pixels.len() * size_of::<(UCoord, Option<T>, Option<T>)>()
+                })
+                .sum::<usize>()
+    }
+}
+
+impl<T: Pixel> Structure<T> {
+    /// Returns the number of layers in the old state
+    fn old_len(&self) -> usize {
+        self.sources.iter().flatten().count() + self.removed.len()
+    }
+
+    /// Returns whether every index of the old state is either a source or removed exactly once,
+    /// there is an added layer for every `None` source and all the stored layers have the given
+    /// dimensions
+    fn is_valid(&self, dim: PCoord) -> bool {
+        let mut seen = vec![false; self.old_len()];
+        let indexes = self
+            .sources
+            .iter()
+            .flatten()
+            .chain(self.removed.iter().map(|(i, _)| i));
+        for i in indexes {
+            match seen.get_mut(usize::from(*i)) {
+                Some(seen) if !*seen => *seen = true,
+                _ => return false,
+            }
+        }
+        self.added.len()
+            == self
+                .sources
+                .iter()
+                .filter(|source| source.is_none())
+                .count()
+            && self
+                .added
+                .iter()
+                .chain(self.removed.iter().map(|(_, layer)| layer))
+                .all(|layer| layer.scene.dim() == dim)
+    }
+
+    fn apply(&self, layers: &mut Layers<T>) {
+        let mut old = take_layers(layers)
+            .into_iter()
+            .map(Some)
+            .collect::<Vec<Option<Layer<T>>>>();
+        let mut added = self.added.iter();
+        for source in &self.sources {
+            layers
+                .add_layer(match source {
+                    Some(i) => old[usize::from(*i)].take().unwrap(), //wont fail because sources
+                    //are unique
+                    None => added.next().unwrap().clone(), //wont fail because there is one
+                                                           //added layer for every `None` source
+                })
+                .unwrap(); //wont fail because the layers came from the same canvas
+        }
+    }
+
+    fn revert(&self, layers: &mut Layers<T>) {
+        let mut new = take_layers(layers)
+            .into_iter()
+            .map(Some)
+            .collect::<Vec<Option<Layer<T>>>>();
+        let mut old = (0..self.sources.iter().flatten().count() + self.removed.len())
+            .map(|_| None)
+            .collect::<Vec<Option<Layer<T>>>>();
+        for (j, source) in self.sources.iter().enumerate() {
+            if let Some(i) = source {
+                old[usize::from(*i)] = new[j].take();
+            }
+        }
+        for (i, layer) in &self.removed {
+            old[usize::from(*i)] = Some(layer.clone());
+        }
+        for layer in old {
+            layers.add_layer(layer.unwrap()).unwrap(); //wont fail because every old index is
+                                                       //either a source or removed
+        }
+    }
+}
+
+/// Returns the sources of the new layers if they are a reordering of the old layers, preferring
+/// to keep layers at the same index
+fn moved<T: Pixel + PartialEq>(old: &[&Layer<T>], new: &[&Layer<T>]) -> Option<Vec<Option<u16>>> {
+    let mut used = old
+        .iter()
+        .zip(new.iter())
+        .map(|(a, b)| a == b)
+        .collect::<Vec<bool>>();
+    let mut sources = Vec::with_capacity(new.len());
+    for (j, layer) in new.iter().enumerate() {
+        if old[j] == *layer {
+            sources.push(Some(j as u16));
+            continue;
+        }
+        let i = (0..old.len()).find(|i| !used[*i] && old[*i] == *layer)?;
+        used[i] = true;
+        sources.push(Some(i as u16));
+    }
+    Some(sources)
+}
+
+/// Returns the sources of the new layers by matching them in order to equal old layers, the
+/// unmatched ones being added
+fn aligned<T: Pixel + PartialEq>(old: &[&Layer<T>], new: &[&Layer<T>]) -> Vec<Option<u16>> {
+    let mut next = 0;
+    new.iter()
+        .map(|layer| {
+            let i = (next..old.len()).find(|i| old[*i] == *layer)?;
+            next = i + 1;
+            Some(i as u16)
+        })
+        .collect()
+}
+
+/// Removes all the layers, returning them in order
+fn take_layers<T: Pixel>(layers: &mut Layers<T>) -> Vec<Layer<T>> {
+    let mut taken = (0..layers.len())
+        .rev()
+        .map(|i| layers.del_layer(i).unwrap()) //wont fail because i < len
+        .collect::<Vec<Layer<T>>>();
+    taken.reverse();
+    taken
+}
+
+fn layer_size<T: Pixel>(layer: &Layer<T>) -> usize {
+    size_of::<Layer<T>>() + layer.scene.heap_size()
+}
@@ -0,0 +1,41 @@
+use super::CanvasDelta;
+
+use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
+
+/// An undo history that can be saved alongside a [`Project`](super::Project) in a
+/// [`ProjectFile`](crate::file::ProjectFile)
+///
+/// The history is a tree of states of the Canvas, every state but the oldest one kept being
+/// reached from its parent by a named action. The [`current`](History::current) state is the
+/// Canvas of the Project the history is saved with.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct History {
+    /// The sequence number of the state the Project's Canvas is at
+    pub current: usize,
+
+    /// The sequence number of the latest state committed, which may no longer be kept
+    pub last_seq: usize,
+
+    /// The states of the tree in the order they were committed
+    pub states: Vec<HistoryState>,
+}
+
+/// A state of the Canvas in a [`History`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryState {
+    /// The sequence number of the commit that led to this state
+    pub seq: usize,
+
+    /// The sequence number of the previous state, `None` for the oldest state kept
+    pub parent: Option<usize>,
+
+    /// The name of the action that led to this state
+    pub name: String,
+
+    /// When this state was committed
+    pub time: SystemTime,
+
+    /// The delta from the previous state, `None` for the oldest state kept
+    pub delta: Option<CanvasDelta>,
+}
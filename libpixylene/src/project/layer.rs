@@ -8,10 +8,10 @@ use serde::{Deserialize, Serialize};
 
 /// A [`Scene`](Scene) with additional information including an opacity, mute switch and a
 /// [`BlendMode`](BlendMode).
-#[rustfmt::skip] //Savefile's macro fails on rustfmt appending a comma to line 14
-#[derive(Debug, Serialize, Deserialize, PartialEq, Savefile, Clone)]
-pub struct Layer<T=TruePixel>
-where T: Pixel
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct Layer<T = TruePixel>
+where
+    T: Pixel,
 {
     pub scene: Scene<T>,
     pub opacity: u8,
@@ -23,11 +23,14 @@ impl<T: Pixel> Layer<T> {
     /// Create a new layer with the given dimensions and single color
     pub fn new_with_solid_color(dimensions: PCoord, color: Option<T>) -> Layer<T> {
         Layer::<T> {
-            scene: Scene::<T>::new(
-                dimensions,
-                vec![color; usize::try_from(dimensions.area()).expect(U32TOUSIZE)],
-            )
-            .unwrap(),
+            scene: match color {
+                Some(_) => Scene::<T>::new(
+                    dimensions,
+                    vec![color; usize::try_from(dimensions.area()).expect(U32TOUSIZE)],
+                )
+                .unwrap(),
+                None => Scene::<T>::new_empty(dimensions),
+            },
             opacity: 255,
             mute: false,
             blend_mode: BlendMode::Normal,
@@ -56,6 +59,28 @@ impl Layer<TruePixel> {
     ) -> Result<Scene<TruePixel>, LayerError> {
         use LayerError::{BlendError, MergeError};
         let mut merged_scene_grid: Vec<Option<TruePixel>> = Vec::new();
+
+        //fast path merging both scenes tile by tile when neither can be out of bounds
+        if top.scene.dim() == dimensions && bottom.scene.dim() == dimensions {
+            let dissolved = |layer: &Layer<TruePixel>, pixel: Option<TruePixel>| {
+                if layer.mute {
+                    TruePixel::empty()
+                } else {
+                    pixel.unwrap_or(TruePixel::empty()).dissolve(layer.opacity)
+                }
+            };
+            //wont fail because both scenes have the given dimensions
+            return top
+                .scene
+                .zip_with(&bottom.scene, |coord, top_p, bottom_p| {
+                    blend_mode
+                        .blend(dissolved(top, top_p), dissolved(bottom, bottom_p))
+                        .map(Some)
+                        .map_err(|err| BlendError(coord, err))
+                })
+                .unwrap();
+        }
+
         for i in 0..dimensions.x() {
             for j in 0..dimensions.y() {
                 let coord = UCoord { x: i, y: j };
@@ -11,7 +11,7 @@ use std::{
 /// The maximum number of Layers that a Canvas is allowed to have
 pub const MAX_LAYERS: u16 = u16::MAX;
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Savefile)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Layers<T: Pixel> {
     layers: Vec<Layer<T>>,
     dimensions: PCoord,
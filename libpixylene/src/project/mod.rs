@@ -4,6 +4,9 @@ pub use palette::{Palette, PaletteError};
 mod scene;
 pub use scene::{OPixel, Scene, SceneError};
 
+mod pattern;
+pub use pattern::{Pattern, PatternError};
+
 mod layer;
 pub use layer::{Layer, LayerError};
 
@@ -11,7 +14,13 @@ mod layers;
 pub use layers::{Layers, LayersError};
 
 mod canvas;
-pub use canvas::{Canvas, LayersType};
+pub use canvas::{Canvas, CanvasError, LayersType, ReplaceScope};
+
+mod delta;
+pub use delta::CanvasDelta;
+
+mod history;
+pub use history::{History, HistoryState};
 
 mod project;
 pub use project::{Project, ProjectError};
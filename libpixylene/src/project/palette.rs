@@ -3,17 +3,42 @@ use crate::{
     utils::messages::{EQUIPPEDISINPALETTE, PALETTELEN},
 };
 
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap, VecDeque};
 
 /// A `Palette` containing a set of [`true-color pixels`](TruePixel) (maximum 256)
 ///
 /// The palette works by using a hashmap of u8 indexes to pixel definitions, and the most
-/// significant color at any time can be chosen by its index and picked.
+/// significant color at any time can be chosen by its index and picked. A secondary color can be
+/// chosen the same way and swapped with the equipped one, and the indexes that were last equipped
+/// are remembered, most recent first.
+///
+/// Ramps of indexes, ordered from dark to light, can be defined to shade colors along.
 #[derive(Debug, Serialize, Deserialize, PartialEq, Savefile, Clone)]
 pub struct Palette {
+    #[serde(serialize_with = "serialize_colors")]
     colors: HashMap<u8, TruePixel>,
     equipped: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[savefile_ignore]
+    secondary: Option<u8>,
+    #[serde(default, skip_serializing_if = "VecDeque::is_empty")]
+    #[savefile_ignore]
+    recent: VecDeque<u8>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[savefile_ignore]
+    ramps: Vec<Vec<u8>>,
+}
+
+/// Serializes the colors ordered by their indexes so that serialized palettes are deterministic
+fn serialize_colors<S: Serializer>(
+    colors: &HashMap<u8, TruePixel>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    colors
+        .iter()
+        .collect::<BTreeMap<_, _>>()
+        .serialize(serializer)
 }
 
 impl Palette {
@@ -92,10 +117,16 @@ impl Palette {
         ),
     ];
 
+    /// The number of recently equipped indexes that are remembered
+    pub const RECENT_LEN: usize = 8;
+
     pub fn gruvbox() -> Self {
         Self {
             colors: HashMap::from(Self::GRUVBOX_COLORS),
             equipped: Some(1),
+            secondary: None,
+            recent: VecDeque::new(),
+            ramps: Vec::new(),
         }
     }
 
@@ -104,18 +135,20 @@ impl Palette {
         Palette {
             colors: HashMap::new(),
             equipped: None,
+            secondary: None,
+            recent: VecDeque::new(),
+            ramps: Vec::new(),
         }
     }
 
     /// Returns a Palette initialized with a collection of (index, color hex-string) pairs, failing
     /// if any of the colors fail to get parsed
     ///
+    /// The first color is equipped & the second one, if any, is chosen as the secondary color.
+    ///
     /// This method may fail with the [`TruePixelError`](PaletteError::TruePixelError) error variant only.
     pub fn from(colors: &[(u8, &str)]) -> Result<Palette, PaletteError> {
-        let mut palette = Palette {
-            colors: HashMap::new(),
-            equipped: None,
-        };
+        let mut palette = Palette::new();
 
         for (index, color_hex) in colors {
             palette.set_color(*index, color_hex)?;
@@ -123,6 +156,12 @@ impl Palette {
                 palette.equip(*index).expect(EQUIPPEDISINPALETTE);
             }
         }
+        if let Some((index, _)) = colors
+            .iter()
+            .find(|(index, _)| palette.equipped != Some(*index))
+        {
+            palette.equip_secondary(*index).expect(EQUIPPEDISINPALETTE);
+        }
 
         Ok(palette)
     }
@@ -177,12 +216,89 @@ impl Palette {
 
         if let Some(_) = self.colors.get(&index) {
             self.equipped = Some(index);
+            self.remember(index);
             Ok(())
         } else {
             Err(InvalidIndex(index))
         }
     }
 
+    /// Gets the secondary pixel, fails if no secondary index has been chosen yet
+    ///
+    /// This method may fail with the [`NoSecondary`](PaletteError::NoSecondary) error variant only.
+    pub fn get_secondary(&self) -> Result<&TruePixel, PaletteError> {
+        Ok(self
+            .colors
+            .get(&self.secondary()?)
+            .expect(EQUIPPEDISINPALETTE))
+    }
+
+    /// Returns the secondary index, fails if no secondary index has been chosen yet
+    ///
+    /// This method may fail with the [`NoSecondary`](PaletteError::NoSecondary) error variant only.
+    pub fn secondary(&self) -> Result<u8, PaletteError> {
+        self.secondary.ok_or(PaletteError::NoSecondary)
+    }
+
+    /// Returns the secondary index if one has been chosen, None otherwise
+    pub fn get_secondary_index(&self) -> Option<u8> {
+        self.secondary
+    }
+
+    /// Chooses a particular index as the secondary color, fails if no pixels correspond to
+    /// specified index
+    ///
+    /// This method may fail with the [`InvalidIndex`](PaletteError::InvalidIndex) error variant
+    /// only.
+    pub fn equip_secondary(&mut self, index: u8) -> Result<(), PaletteError> {
+        use PaletteError::InvalidIndex;
+
+        if let Some(_) = self.colors.get(&index) {
+            self.secondary = Some(index);
+            self.remember(index);
+            Ok(())
+        } else {
+            Err(InvalidIndex(index))
+        }
+    }
+
+    /// Swaps the equipped & the secondary indexes, fails if either hasn't been chosen yet
+    ///
+    /// This method may fail with the [`NothingEquipped`][ne] or [`NoSecondary`][ns] error variants
+    /// only.
+    ///
+    /// [ne]: PaletteError::NothingEquipped
+    /// [ns]: PaletteError::NoSecondary
+    pub fn swap(&mut self) -> Result<(), PaletteError> {
+        use PaletteError::{NoSecondary, NothingEquipped};
+
+        match (self.equipped, self.secondary) {
+            (Some(equipped), Some(secondary)) => {
+                self.equipped = Some(secondary);
+                self.secondary = Some(equipped);
+                self.remember(secondary);
+                Ok(())
+            }
+            (None, _) => Err(NothingEquipped),
+            (_, None) => Err(NoSecondary),
+        }
+    }
+
+    /// Returns an iterator to the indexes that were last equipped along with their colors, most
+    /// recent first
+    pub fn recent(&self) -> impl Iterator<Item = (&u8, &TruePixel)> {
+        self.recent
+            .iter()
+            .filter_map(|index| self.colors.get(index).map(|color| (index, color)))
+    }
+
+    //moves an index to the front of the recently equipped ones
+    fn remember(&mut self, index: u8) {
+        self.recent.retain(|recent| *recent != index);
+        self.recent.push_front(index);
+        self.recent.truncate(Self::RECENT_LEN);
+    }
+
     /// Sets a color corresponding to a particular index, overwrites if already present, failing if
     /// the color string failed to be parsed into a [`TruePixel`]
     ///
@@ -217,7 +333,55 @@ impl Palette {
                 }
                 None => (),
             }
+            if self.secondary == Some(index) {
+                self.secondary = None;
+            }
+            self.recent.retain(|recent| *recent != index);
+            for ramp in &mut self.ramps {
+                ramp.retain(|ramp_index| *ramp_index != index);
+            }
+            self.ramps.retain(|ramp| !ramp.is_empty());
+        }
+    }
+
+    /// Returns an iterator to the ramps of the palette, each ordered from dark to light
+    pub fn ramps(&self) -> impl Iterator<Item = &[u8]> {
+        self.ramps.iter().map(|ramp| ramp.as_slice())
+    }
+
+    /// Adds a ramp of indexes ordered from dark to light, fails if any of them has no color
+    ///
+    /// This method may fail with the [`InvalidIndex`](PaletteError::InvalidIndex) error variant
+    /// only.
+    pub fn add_ramp(&mut self, ramp: Vec<u8>) -> Result<(), PaletteError> {
+        use PaletteError::InvalidIndex;
+
+        if let Some(index) = ramp.iter().find(|index| !self.colors.contains_key(index)) {
+            return Err(InvalidIndex(*index));
         }
+        if !ramp.is_empty() {
+            self.ramps.push(ramp);
+        }
+        Ok(())
+    }
+
+    /// Removes all the ramps of the palette
+    pub fn clear_ramps(&mut self) {
+        self.ramps.clear();
+    }
+
+    /// Returns the index one step lighter or darker than an index along the first ramp it is in,
+    /// staying at the ends of the ramp, or None if it isn't in any ramp
+    pub fn shade(&self, index: u8, lighter: bool) -> Option<u8> {
+        self.ramps.iter().find_map(|ramp| {
+            let position = ramp.iter().position(|ramp_index| *ramp_index == index)?;
+            let shaded = if lighter {
+                (position + 1).min(ramp.len() - 1)
+            } else {
+                position.saturating_sub(1)
+            };
+            Some(ramp[shaded])
+        })
     }
 
     /// Returns the equipped index if an index has been equipped, None otherwise
@@ -250,7 +414,10 @@ impl From<&Vec<TruePixel>> for Palette {
 
         Palette {
             equipped: if item.len() > 0 { Some(0) } else { None },
+            secondary: if item.len() > 1 { Some(1) } else { None },
             colors,
+            recent: VecDeque::new(),
+            ramps: Vec::new(),
         }
     }
 }
@@ -266,6 +433,9 @@ pub enum PaletteError {
     /// Error that occurs when equipped index is accessed somehow but nothing has been equipped
     NothingEquipped,
 
+    /// Error that occurs when the secondary index is accessed but none has been chosen
+    NoSecondary,
+
     /// Error that is propagated when trying to parse color hex-strings into
     /// [`TruePixel`](TruePixel)
     TruePixelError(TruePixelError),
@@ -281,6 +451,10 @@ impl std::fmt::Display for PaletteError {
                 index,
             ),
             NothingEquipped => write!(f, "cannot get equipped color as nothing has been equipped",),
+            NoSecondary => write!(
+                f,
+                "cannot get secondary color as no secondary color has been chosen",
+            ),
             TruePixelError(true_pixel_error) => write!(f, "{}", true_pixel_error),
         }
     }
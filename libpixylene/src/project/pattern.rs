@@ -0,0 +1,142 @@
+use super::{Scene, SceneError};
+use crate::types::{PCoord, Pixel, UCoord};
+
+use std::fmt;
+
+/// A grid of pixels that are either on or off, tiled over a canvas from its top-left so that
+/// anything drawn with it lines up no matter where it is drawn
+///
+/// Patterns are either one of the [`LEVELS`](Pattern::LEVELS) ordered-dither levels, or custom
+/// patterns taken from the present pixels of a [`Scene`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pattern {
+    dim: PCoord,
+    grid: Vec<bool>,
+    level: Option<u8>,
+}
+
+impl Pattern {
+    /// The number of ordered-dither levels above 0, i.e. the steps from none of the pixels being
+    /// on to all of them
+    pub const LEVELS: u8 = 16;
+
+    /// Tries to create the ordered-dither pattern that has `level` sixteenths of its pixels on, 8
+    /// being a checkerboard
+    ///
+    /// `Note`: This method may fail with the [`InvalidLevel`](PatternError::InvalidLevel) error
+    /// variant only.
+    pub fn ordered(level: u8) -> Result<Pattern, PatternError> {
+        if level > Self::LEVELS {
+            return Err(PatternError::InvalidLevel(level));
+        }
+        Ok(Pattern {
+            //wont fail because 4 is positive
+            dim: PCoord::new(4, 4).unwrap(),
+            grid: (0..4)
+                .flat_map(|x| (0..4).map(move |y| Self::bayer(4, x, y) < u16::from(level)))
+                .collect(),
+            level: Some(level),
+        })
+    }
+
+    /// Returns the ordered-dither (Bayer) threshold at a position inside a Bayer matrix of the given
+    /// size, a power of 2, the thresholds going from 0 to one less than the number of its pixels
+    pub fn bayer(size: u16, x: u16, y: u16) -> u16 {
+        if size <= 1 {
+            return 0;
+        }
+        let half = size / 2;
+        let quadrant = [[0, 2], [3, 1]][(x / half) as usize][(y / half) as usize];
+        4 * Self::bayer(half, x % half, y % half) + quadrant
+    }
+
+    /// Creates a custom pattern from a scene, its present pixels being on
+    pub fn from_scene<T: Pixel>(scene: &Scene<T>) -> Pattern {
+        Pattern {
+            dim: scene.dim(),
+            grid: scene.grid().map(|pixel| pixel.is_some()).collect(),
+            level: None,
+        }
+    }
+
+    /// Tries to create a custom pattern from a region of a scene starting at a coordinate, its
+    /// present pixels being on
+    ///
+    /// `Note`: This method may fail with the [`OutOfBoundCoordinates`][oobc] error variant only.
+    ///
+    /// [oobc]: SceneError::OutOfBoundCoordinates
+    pub fn from_region<T: Pixel>(
+        scene: &Scene<T>,
+        start: UCoord,
+        dim: PCoord,
+    ) -> Result<Pattern, SceneError> {
+        let mut grid = Vec::new();
+        for x in 0..dim.x() {
+            for y in 0..dim.y() {
+                grid.push(
+                    scene
+                        .get_pixel(UCoord {
+                            x: start.x.saturating_add(x),
+                            y: start.y.saturating_add(y),
+                        })?
+                        .is_some(),
+                );
+            }
+        }
+        Ok(Pattern {
+            dim,
+            grid,
+            level: None,
+        })
+    }
+
+    /// Returns the dimensions of a single tile of the pattern
+    pub fn dim(&self) -> PCoord {
+        self.dim
+    }
+
+    /// Returns the ordered-dither level of the pattern, or `None` if it is a custom pattern
+    pub fn level(&self) -> Option<u8> {
+        self.level
+    }
+
+    /// Returns whether the pattern is on at a coordinate of the canvas it is tiled over
+    pub fn is_on(&self, coord: UCoord) -> bool {
+        let (x, y) = (coord.x % self.dim.x(), coord.y % self.dim.y());
+        self.grid[usize::from(x) * usize::from(self.dim.y()) + usize::from(y)]
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.level {
+            Some(level) => write!(f, "{}/{}", level, Self::LEVELS),
+            None => write!(f, "custom {}", self.dim),
+        }
+    }
+}
+
+// Error Types
+
+/// Error enum to describe various errors returns by Pattern methods
+#[derive(Debug)]
+pub enum PatternError {
+    /// Error that occurs when trying to create an ordered-dither pattern with a level greater
+    /// than [`LEVELS`](Pattern::LEVELS)
+    InvalidLevel(u8),
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use PatternError::*;
+        match self {
+            InvalidLevel(level) => write!(
+                f,
+                "Cannot create an ordered-dither pattern at level {}, levels lie between 0 and {} \
+                (inclusive)",
+                level,
+                Pattern::LEVELS,
+            ),
+        }
+    }
+}
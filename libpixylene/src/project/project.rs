@@ -12,7 +12,6 @@ use std::iter::Iterator;
 /// The `Project` as opposed to the `Canvas` contains data that does not directly influence how
 /// a Pixel Art project looks, including `Cursors` and the data responsible for the rendering
 /// of the `Canvas` on a real screen where the Pixel Art project is being visualized.
-#[derive(Savefile)]
 pub struct Project {
     /// The [`Canvas`] composed into the Project.
     pub canvas: Canvas,
@@ -38,7 +37,8 @@ pub struct Project {
 
     cursors: HashMap<(UCoord, u16), ()>,
     num_cursors: u64,
-    sel_cursor: Option<(UCoord, u16)>,
+
+    pub(crate) cursor_registers: HashMap<String, Vec<(UCoord, u16)>>,
 }
 
 impl Project {
@@ -52,7 +52,7 @@ impl Project {
             out_repeat: PCoord::new(1, 1).unwrap(), //shouldn't fail
             cursors: HashMap::new(),
             num_cursors: 0,
-            sel_cursor: None,
+            cursor_registers: HashMap::new(),
         }
     }
 
@@ -243,6 +243,246 @@ impl Project {
         self.cursors.drain().into_iter().map(|(cursor, _)| cursor)
     }
 
+    /// Adds cursors at every pixel of the [`Layer`] at the given index that has the given color,
+    /// comparing pixels of an indexed [`Canvas`] by their colors in its
+    /// [`Palette`](crate::project::Palette), returning the number of cursors added
+    ///
+    /// `Note`: This method may fail with the [`LayersError`][le] error variant only.
+    ///
+    /// [le]: ProjectError::LayersError
+    pub fn add_cursors_at_color(
+        &mut self,
+        color: Option<TruePixel>,
+        layer: u16,
+    ) -> Result<u64, ProjectError> {
+        let coords = self
+            .layer_pixels(layer)?
+            .into_iter()
+            .filter(|(_, pixel, _)| *pixel == color)
+            .map(|(coord, ..)| coord)
+            .collect::<Vec<UCoord>>();
+        Ok(self.add_cursors(coords.into_iter().map(|coord| (coord, layer))))
+    }
+
+    /// Adds cursors at every pixel of the [`Layer`] at the given index that is of the given
+    /// [`Palette`](crate::project::Palette) index, returning the number of cursors added
+    ///
+    /// `Note`: This method may fail with the [`LayersError`][le] & [`ExpectingIndexedCanvas`][eic]
+    /// error variants only.
+    ///
+    /// [le]: ProjectError::LayersError
+    /// [eic]: ProjectError::ExpectingIndexedCanvas
+    pub fn add_cursors_at_index(&mut self, index: u8, layer: u16) -> Result<u64, ProjectError> {
+        if let LayersType::True(_) = self.canvas.layers {
+            return Err(ProjectError::ExpectingIndexedCanvas);
+        }
+        let coords = self
+            .layer_pixels(layer)?
+            .into_iter()
+            .filter(|(.., pixel_index)| *pixel_index == Some(index))
+            .map(|(coord, ..)| coord)
+            .collect::<Vec<UCoord>>();
+        Ok(self.add_cursors(coords.into_iter().map(|coord| (coord, layer))))
+    }
+
+    /// Adds cursors at every non-empty pixel of the [`Layer`] at the given index, returning the
+    /// number of cursors added
+    ///
+    /// `Note`: This method may fail with the [`LayersError`][le] error variant only.
+    ///
+    /// [le]: ProjectError::LayersError
+    pub fn add_cursors_at_content(&mut self, layer: u16) -> Result<u64, ProjectError> {
+        let coords = self
+            .layer_pixels(layer)?
+            .into_iter()
+            .filter(|(_, pixel, index)| pixel.is_some() || index.is_some())
+            .map(|(coord, ..)| coord)
+            .collect::<Vec<UCoord>>();
+        Ok(self.add_cursors(coords.into_iter().map(|coord| (coord, layer))))
+    }
+
+    /// Adds cursors at every non-empty pixel of every [`Layer`] in the [`Canvas`], returning the
+    /// number of cursors added
+    pub fn add_cursors_at_all_content(&mut self) -> u64 {
+        (0..self.canvas.layers.len())
+            .map(|layer| self.add_cursors_at_content(layer).unwrap()) //cant fail because
+            //layer is in bounds
+            .sum()
+    }
+
+    /// Inverts the cursors on the [`Layer`] at the given index, pointing a cursor at every
+    /// coordinate that had none & unsetting the ones that were there
+    ///
+    /// `Note`: This method may fail with the [`CursorLayerOutOfBounds`][cloob] error variant only.
+    ///
+    /// [cloob]: ProjectError::CursorLayerOutOfBounds
+    pub fn invert_cursors(&mut self, layer: u16) -> Result<(), ProjectError> {
+        let dim = self.canvas.layers.dim();
+        for x in 0..dim.x() {
+            for y in 0..dim.y() {
+                self.toggle_cursor_at(&(UCoord { x, y }, layer))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Grows the cursors on every [`Layer`] by one pixel, pointing a cursor at every coordinate
+    /// adjacent to one, without going out of the bounds of the [`Canvas`]
+    pub fn grow_cursors(&mut self) {
+        let grown = self
+            .cursors()
+            .flat_map(|cursor| self.neighbours(cursor))
+            .collect::<Vec<(UCoord, u16)>>();
+        self.add_cursors(grown.into_iter());
+    }
+
+    /// Shrinks the cursors on every [`Layer`] by one pixel, unsetting the cursors that are
+    /// adjacent to a coordinate without one, with the bounds of the [`Canvas`] not counting as
+    /// such coordinates
+    pub fn shrink_cursors(&mut self) {
+        let shrunk = self
+            .cursors()
+            .filter(|cursor| {
+                self.neighbours(cursor)
+                    .iter()
+                    .any(|neighbour| !self.cursors.contains_key(neighbour))
+            })
+            .copied()
+            .collect::<Vec<(UCoord, u16)>>();
+        for cursor in shrunk {
+            self.cursors.remove(&cursor);
+            self.num_cursors -= 1;
+        }
+    }
+
+    /// Returns the bounding box of the cursors on each [`Layer`] that has any, as its top-left &
+    /// bottom-right corners
+    pub fn cursor_bounds(&self) -> HashMap<u16, (UCoord, UCoord)> {
+        let mut boxes: HashMap<u16, (UCoord, UCoord)> = HashMap::new();
+        for (coord, layer) in self.cursors() {
+            let (start, end) = boxes.entry(*layer).or_insert((*coord, *coord));
+            *start = UCoord {
+                x: start.x.min(coord.x),
+                y: start.y.min(coord.y),
+            };
+            *end = UCoord {
+                x: end.x.max(coord.x),
+                y: end.y.max(coord.y),
+            };
+        }
+        boxes
+    }
+
+    /// Points a cursor at every coordinate inside the bounding box of the cursors on each
+    /// [`Layer`]
+    pub fn bound_cursors(&mut self) {
+        for (layer, (start, end)) in self.cursor_bounds() {
+            self.add_cursors(
+                (start.x..=end.x)
+                    .flat_map(|x| (start.y..=end.y).map(move |y| (UCoord { x, y }, layer))),
+            );
+        }
+    }
+
+    /// Saves the current cursors into the register of the given name, replacing the cursors it
+    /// held
+    pub fn save_cursors(&mut self, register: &str) {
+        let mut cursors = self.cursors().copied().collect::<Vec<(UCoord, u16)>>();
+        cursors.sort_by_key(|(coord, layer)| (*layer, coord.x, coord.y));
+        self.cursor_registers.insert(register.to_owned(), cursors);
+    }
+
+    /// Replaces the current cursors with the ones saved into the register of the given name,
+    /// leaving out the ones that have since gone out of bounds for the [`Canvas`]
+    ///
+    /// `Note`: This method may fail with the [`EmptyCursorRegister`][ecr] error variant only.
+    ///
+    /// [ecr]: ProjectError::EmptyCursorRegister
+    pub fn restore_cursors(&mut self, register: &str) -> Result<(), ProjectError> {
+        let cursors = self
+            .cursor_registers
+            .get(register)
+            .ok_or(ProjectError::EmptyCursorRegister(register.to_owned()))?
+            .clone();
+        _ = self.clear_cursors();
+        for cursor in cursors {
+            if let Ok(false) = self.is_cursor_at(&cursor) {
+                self.toggle_cursor_at(&cursor).unwrap(); //cant fail because checked just now
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns an iterator over the names of the registers that cursors have been saved into
+    pub fn cursor_registers(&self) -> impl Iterator<Item = &String> {
+        self.cursor_registers.keys()
+    }
+
+    //points cursors at the ones that had none, returning how many
+    fn add_cursors(&mut self, cursors: impl Iterator<Item = (UCoord, u16)>) -> u64 {
+        let mut added = 0;
+        for cursor in cursors {
+            if self.cursors.insert(cursor, ()).is_none() {
+                added += 1;
+            }
+        }
+        self.num_cursors += added;
+        added
+    }
+
+    //cursors adjacent to the given one that are in the bounds of the canvas
+    fn neighbours(&self, cursor: &(UCoord, u16)) -> Vec<(UCoord, u16)> {
+        let dim = self.canvas.layers.dim();
+        let (coord, layer) = *cursor;
+        [
+            (coord.x.checked_sub(1), Some(coord.y)),
+            (coord.x.checked_add(1), Some(coord.y)),
+            (Some(coord.x), coord.y.checked_sub(1)),
+            (Some(coord.x), coord.y.checked_add(1)),
+        ]
+        .into_iter()
+        .filter_map(|(x, y)| match (x, y) {
+            (Some(x), Some(y)) if x < dim.x() && y < dim.y() => Some((UCoord { x, y }, layer)),
+            _ => None,
+        })
+        .collect()
+    }
+
+    //every coordinate of a layer with its color & its palette index if the canvas is indexed
+    fn layer_pixels(
+        &self,
+        layer: u16,
+    ) -> Result<Vec<(UCoord, Option<TruePixel>, Option<u8>)>, ProjectError> {
+        let dim = self.canvas.layers.dim();
+        let coord = |i: usize| UCoord {
+            x: (i / usize::from(dim.y())) as u16,
+            y: (i % usize::from(dim.y())) as u16,
+        };
+        Ok(match &self.canvas.layers {
+            LayersType::True(layers) => layers
+                .get_layer(layer)?
+                .scene
+                .grid()
+                .enumerate()
+                .map(|(i, pixel)| (coord(i), pixel, None))
+                .collect(),
+            LayersType::Indexed(layers) => layers
+                .get_layer(layer)?
+                .scene
+                .grid()
+                .enumerate()
+                .map(|(i, pixel)| {
+                    (
+                        coord(i),
+                        pixel
+                            .and_then(|pixel| self.canvas.palette.get_color(pixel.0).ok().copied()),
+                        pixel.map(|pixel| pixel.0),
+                    )
+                })
+                .collect(),
+        })
+    }
+
     pub fn resize(&mut self) {
         todo!()
     }
@@ -295,6 +535,12 @@ pub enum ProjectError {
 
     /// Error that occurs when trying to set the output multipler out_mul to 0
     ZeroMultiplier,
+
+    /// Error that occurs when trying to use palette indices on a Canvas that is not indexed
+    ExpectingIndexedCanvas,
+
+    /// Error that occurs when trying to restore cursors from a register that none were saved into
+    EmptyCursorRegister(String),
 }
 
 impl std::fmt::Display for ProjectError {
@@ -317,6 +563,12 @@ impl std::fmt::Display for ProjectError {
             ),
             LayersError(error) => write!(f, "{}", error),
             ZeroMultiplier => write!(f, "cannot set output multiplier to 0",),
+            ExpectingIndexedCanvas => {
+                write!(f, "palette indices can only be used on an indexed canvas",)
+            }
+            EmptyCursorRegister(register) => {
+                write!(f, "no cursors have been saved into register '{}'", register,)
+            }
         }
     }
 }
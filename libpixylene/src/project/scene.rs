@@ -3,8 +3,13 @@ use crate::{
     utils::messages::U32TOUSIZE,
 };
 
-use serde::{Deserialize, Serialize};
-use std::{fmt, slice::Iter};
+use serde::{
+    de::Error as _, ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{fmt, marker::PhantomData, ops::Range};
+
+/// The length of the sides of the square tiles a Scene's pixels are stored in
+const TILE: u16 = 32;
 
 /// A two-dimensional grid of pixels that are of a generic type T.
 ///
@@ -12,13 +17,85 @@ use std::{fmt, slice::Iter};
 /// Canvas uses a Scene of 8-bit unsigned integers to denote palette indexes.
 ///
 /// Each item of the grid is either empty or has a value.
-#[rustfmt::skip] //Savefile's macro fails on rustfmt appending a comma to line 14
-#[derive(Serialize, Deserialize, PartialEq, Debug, Savefile, Clone)]
-pub struct Scene<T=TruePixel>
-where T: Pixel
+///
+/// The grid is stored in tiles of 32x32 pixels, each consisting of a bitmap of which of its pixels
+/// are present and the [packed](Pixel::pack) bytes of its pixels. Tiles with no pixels present
+/// are not allocated, so mostly-empty Scenes take little memory regardless of their dimensions.
+#[derive(Clone)]
+pub struct Scene<T = TruePixel>
+where
+    T: Pixel,
 {
     dim: PCoord,
-    grid: Vec<Option<T>>,
+    tiles: Vec<Option<Tile>>,
+    pixel: PhantomData<T>,
+}
+
+/// A tile of a [`Scene`], allocated only while any of its pixels are present
+#[derive(Clone, PartialEq)]
+struct Tile {
+    /// Row-major bitmap of which pixels of the tile are present
+    present: Vec<u64>,
+    /// Packed bytes of the pixels of the tile, zeroed where not present
+    bytes: Vec<u8>,
+    /// Number of pixels present
+    count: u32,
+}
+
+impl Tile {
+    fn new(area: usize, len: usize) -> Tile {
+        Tile {
+            present: vec![0; area.div_ceil(64)],
+            bytes: vec![0; area * len],
+            count: 0,
+        }
+    }
+
+    #[inline] //called for every pixel when iterating or rendering scenes from other crates
+    fn get(&self, index: usize, len: usize) -> Option<&[u8]> {
+        if self.is_present(index) {
+            Some(&self.bytes[index * len..(index + 1) * len])
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn is_present(&self, index: usize) -> bool {
+        self.present[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    fn insert(&mut self, index: usize, len: usize) -> &mut [u8] {
+        if self.present[index / 64] & (1 << (index % 64)) == 0 {
+            self.present[index / 64] |= 1 << (index % 64);
+            self.count += 1;
+        }
+        &mut self.bytes[index * len..(index + 1) * len]
+    }
+
+    /// Sets the first `width` pixels of each of the first `height` rows of the tile to `pixel`
+    fn fill<T: Pixel>(&mut self, height: usize, width: usize, len: usize, pixel: T) {
+        let stride = usize::from(TILE);
+        pixel.pack(&mut self.bytes[..len]);
+        for row in 0..height {
+            for index in row * stride..row * stride + width {
+                self.present[index / 64] |= 1 << (index % 64);
+                if index > 0 {
+                    self.bytes.copy_within(..len, index * len);
+                }
+            }
+        }
+        //cast wont fail because tiles are at most TILE x TILE
+        self.count = (height * width) as u32;
+    }
+
+    fn remove(&mut self, index: usize, len: usize) {
+        if self.present[index / 64] & (1 << (index % 64)) != 0 {
+            self.present[index / 64] &= !(1 << (index % 64));
+            self.count -= 1;
+            self.bytes[index * len..(index + 1) * len].fill(0);
+        }
+    }
 }
 
 impl<T: Pixel> Scene<T> {
@@ -33,16 +110,82 @@ impl<T: Pixel> Scene<T> {
         if buffer.len() != usize::try_from(dimensions.area()).expect(U32TOUSIZE) {
             Err(DimensionMismatch(buffer.len(), dimensions))
         } else {
-            Ok(Self {
-                dim: dimensions,
-                grid: buffer,
-            })
+            let mut scene = Self::new_empty(dimensions);
+            let mut pixels = buffer.into_iter();
+            for x in 0..dimensions.x() {
+                for tile_y in 0..dimensions.y().div_ceil(TILE) {
+                    let (tile, start, area) = scene.locate(x, tile_y * TILE);
+                    for index in start..start + scene.tile_width(tile_y) {
+                        //wont fail because buffer has the same length as the area
+                        if let Some(pixel) = pixels.next().unwrap() {
+                            pixel.pack(
+                                scene.tiles[tile]
+                                    .get_or_insert_with(|| Tile::new(area, T::PACKED_LEN))
+                                    .insert(index, T::PACKED_LEN),
+                            );
+                        }
+                    }
+                }
+            }
+            Ok(scene)
+        }
+    }
+
+    /// Creates a new scene with given dimensions and no pixels, without allocating any tiles
+    pub fn new_empty(dimensions: PCoord) -> Self {
+        let tiles =
+            usize::from(dimensions.x().div_ceil(TILE)) * usize::from(dimensions.y().div_ceil(TILE));
+        Self {
+            dim: dimensions,
+            tiles: vec![None; tiles],
+            pixel: PhantomData,
         }
     }
 
-    /// Returns an immutable iterator over the buffer of the pixels in the scene
-    pub fn grid(&self) -> Iter<Option<T>> {
-        self.grid.iter()
+    /// Returns an iterator over the pixels in the scene, row by row
+    pub fn grid(&self) -> impl Iterator<Item = Option<T>> + '_ {
+        (0..self.dim.x()).flat_map(move |x| self.row(x, 0..self.dim.y()))
+    }
+
+    /// Returns an iterator over the pixels of the in-bound row `x` within the in-bound columns
+    /// `ys`, looking up each tile of the row only once
+    fn row(&self, x: u16, ys: Range<u16>) -> impl Iterator<Item = Option<T>> + '_ {
+        (ys.start / TILE..ys.end.div_ceil(TILE)).flat_map(move |tile_y| {
+            let (from, to) = (
+                ys.start.max(tile_y * TILE),
+                ys.end.min(tile_y * TILE + TILE),
+            );
+            let (tile, start, _) = self.locate(x, from);
+            let tile = self.tiles[tile].as_ref();
+            (start..start + usize::from(to - from)).map(move |index| {
+                tile.and_then(|tile| tile.get(index, T::PACKED_LEN))
+                    .map(T::unpack)
+            })
+        })
+    }
+
+    /// Pushes the pixels of the in-bound rows `xs` within the in-bound columns `ys` to `pixels`,
+    /// row by row
+    fn window(&self, xs: Range<u16>, ys: Range<u16>, pixels: &mut Vec<Option<T>>) {
+        for x in xs {
+            for tile_y in ys.start / TILE..ys.end.div_ceil(TILE) {
+                let (from, to) = (
+                    ys.start.max(tile_y * TILE),
+                    ys.end.min(tile_y * TILE + TILE),
+                );
+                let (tile, start, _) = self.locate(x, from);
+                let end = start + usize::from(to - from);
+                match &self.tiles[tile] {
+                    Some(tile) => pixels.extend(
+                        tile.bytes[start * T::PACKED_LEN..end * T::PACKED_LEN]
+                            .chunks_exact(T::PACKED_LEN)
+                            .zip(start..end)
+                            .map(|(bytes, index)| tile.is_present(index).then(|| T::unpack(bytes))),
+                    ),
+                    None => pixels.resize(pixels.len() + end - start, None),
+                }
+            }
+        }
     }
 
     /// Tries to get the item at the given coordinatel & fails with context if coordinate is out of
@@ -57,8 +200,7 @@ impl<T: Pixel> Scene<T> {
         if coord.x >= self.dim.x() || coord.y >= self.dim.y() {
             Err(OutOfBoundCoordinates(coord, self.dim))
         } else {
-            let index = usize::from(coord.x) * usize::from(self.dim.y()) + usize::from(coord.y);
-            Ok(self.grid[index])
+            Ok(self.get(coord.x, coord.y))
         }
     }
 
@@ -74,9 +216,8 @@ impl<T: Pixel> Scene<T> {
         } else if coord.x >= i32::from(self.dim.x()) || coord.y >= i32::from(self.dim.y()) {
             None
         } else {
-            let index = usize::try_from(coord.x).unwrap() * usize::from(self.dim.y())
-                + usize::try_from(coord.y).unwrap();
-            Some(self.grid[index])
+            //casts wont fail because coord is within the dimensions
+            Some(self.get(coord.x as u16, coord.y as u16))
         }
     }
 
@@ -92,8 +233,7 @@ impl<T: Pixel> Scene<T> {
         if coord.x >= self.dim.x() || coord.y >= self.dim.y() {
             Err(OutOfBoundCoordinates(coord, self.dim))
         } else {
-            let index = usize::from(coord.x) * usize::from(self.dim.y()) + usize::from(coord.y);
-            self.grid[index] = new_pixel;
+            self.set(coord.x, coord.y, new_pixel);
             Ok(())
         }
     }
@@ -102,6 +242,201 @@ impl<T: Pixel> Scene<T> {
     pub fn dim(&self) -> PCoord {
         self.dim
     }
+
+    /// Returns the number of bytes allocated on the heap to store the pixels of this scene
+    pub fn heap_size(&self) -> usize {
+        self.tiles.capacity() * std::mem::size_of::<Option<Tile>>()
+            + self
+                .tiles
+                .iter()
+                .flatten()
+                .map(|tile| tile.present.capacity() * 8 + tile.bytes.capacity())
+                .sum::<usize>()
+    }
+
+    /// Returns the coordinates of the pixels that differ between this scene & `other`, along with
+    /// their values in this scene & in `other` respectively, or `None` if the two scenes have
+    /// different dimensions
+    ///
+    /// Only the tiles that differ are compared pixel by pixel.
+    pub fn diff(&self, other: &Scene<T>) -> Option<Vec<(UCoord, Option<T>, Option<T>)>>
+    where
+        T: PartialEq,
+    {
+        if self.dim != other.dim {
+            return None;
+        }
+        let tiles_y = usize::from(self.dim.y().div_ceil(TILE));
+        let mut changes = Vec::new();
+        for (index, (a, b)) in self.tiles.iter().zip(other.tiles.iter()).enumerate() {
+            if a == b {
+                continue;
+            }
+            //casts wont fail because the tile indexes are within the dimensions
+            let (tile_x, tile_y) = ((index / tiles_y) as u16, (index % tiles_y) as u16);
+            for x in tile_x * TILE..(tile_x * TILE + TILE).min(self.dim.x()) {
+                for y in tile_y * TILE..(tile_y * TILE + TILE).min(self.dim.y()) {
+                    let (old, new) = (self.get(x, y), other.get(x, y));
+                    if old != new {
+                        changes.push((UCoord { x, y }, old, new));
+                    }
+                }
+            }
+        }
+        Some(changes)
+    }
+
+    /// Returns a scene of the same dimensions whose pixels are given by `f` from the pixels of this
+    /// scene & `other` at each coordinate, or `None` if the two scenes have different dimensions
+    ///
+    /// The scene is built tile by tile, and tiles that are empty in both scenes are filled by
+    /// calling `f` only once, on their first coordinate. `f` must hence give the same pixel for
+    /// the same pair of pixels regardless of the coordinate, which it may only use for errors.
+    pub fn zip_with<E>(
+        &self,
+        other: &Scene<T>,
+        f: impl FnMut(UCoord, Option<T>, Option<T>) -> Result<Option<T>, E>,
+    ) -> Option<Result<Scene<T>, E>> {
+        if self.dim != other.dim {
+            None
+        } else {
+            Some(self.zip_tiles(other, f))
+        }
+    }
+
+    fn zip_tiles<E>(
+        &self,
+        other: &Scene<T>,
+        mut f: impl FnMut(UCoord, Option<T>, Option<T>) -> Result<Option<T>, E>,
+    ) -> Result<Scene<T>, E> {
+        let len = T::PACKED_LEN;
+        let tiles_y = usize::from(self.dim.y().div_ceil(TILE));
+        let mut scene = Self::new_empty(self.dim);
+        for (index, (a, b)) in self.tiles.iter().zip(other.tiles.iter()).enumerate() {
+            //casts wont fail because the tile indexes are within the dimensions
+            let (tile_x, tile_y) = ((index / tiles_y) as u16, (index % tiles_y) as u16);
+            let (_, _, area) = self.locate(tile_x * TILE, tile_y * TILE);
+            let (height, width) = (area / usize::from(TILE), self.tile_width(tile_y));
+
+            let mut tile = Tile::new(area, len);
+            if a.is_none() && b.is_none() {
+                let coord = UCoord {
+                    x: tile_x * TILE,
+                    y: tile_y * TILE,
+                };
+                if let Some(pixel) = f(coord, None, None)? {
+                    tile.fill(height, width, len, pixel);
+                }
+            } else {
+                for row in 0..height {
+                    for column in 0..width {
+                        let i = row * usize::from(TILE) + column;
+                        let pixel_a = a.as_ref().and_then(|a| a.get(i, len)).map(T::unpack);
+                        let pixel_b = b.as_ref().and_then(|b| b.get(i, len)).map(T::unpack);
+                        //casts wont fail because the tiles are at most TILE x TILE
+                        let coord = UCoord {
+                            x: tile_x * TILE + row as u16,
+                            y: tile_y * TILE + column as u16,
+                        };
+                        if let Some(pixel) = f(coord, pixel_a, pixel_b)? {
+                            pixel.pack(tile.insert(i, len));
+                        }
+                    }
+                }
+            }
+            if tile.count > 0 {
+                scene.tiles[index] = Some(tile);
+            }
+        }
+        Ok(scene)
+    }
+
+    /// Returns the width of the tiles in the given column of tiles
+    fn tile_width(&self, tile_y: u16) -> usize {
+        usize::from(TILE.min(self.dim.y() - tile_y * TILE))
+    }
+
+    /// Returns the index of the tile containing the in-bound coordinate, the index of the
+    /// coordinate within the tile & the area of the tile
+    ///
+    /// Rows of tiles are always [`TILE`] pixels apart, so that a pixel is found without knowing
+    /// the width of its tile, & the tiles on the right edge are padded with absent pixels.
+    fn locate(&self, x: u16, y: u16) -> (usize, usize, usize) {
+        let (tile_x, tile_y) = (x / TILE, y / TILE);
+        let height = usize::from(TILE.min(self.dim.x() - tile_x * TILE));
+        (
+            usize::from(tile_x) * usize::from(self.dim.y().div_ceil(TILE)) + usize::from(tile_y),
+            usize::from(x % TILE) * usize::from(TILE) + usize::from(y % TILE),
+            height * usize::from(TILE),
+        )
+    }
+
+    fn get(&self, x: u16, y: u16) -> Option<T> {
+        let (tile, index, _) = self.locate(x, y);
+        self.tiles[tile]
+            .as_ref()
+            .and_then(|tile| tile.get(index, T::PACKED_LEN))
+            .map(T::unpack)
+    }
+
+    fn set(&mut self, x: u16, y: u16, new_pixel: Option<T>) {
+        let (tile, index, area) = self.locate(x, y);
+        match new_pixel {
+            Some(pixel) => pixel.pack(
+                self.tiles[tile]
+                    .get_or_insert_with(|| Tile::new(area, T::PACKED_LEN))
+                    .insert(index, T::PACKED_LEN),
+            ),
+            None => {
+                if let Some(existing) = &mut self.tiles[tile] {
+                    existing.remove(index, T::PACKED_LEN);
+                    if existing.count == 0 {
+                        self.tiles[tile] = None;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Scenes are compared tile by tile, which is equivalent to comparing them pixel by pixel since
+/// cleared pixels are zeroed & tiles are freed as soon as they become empty
+impl<T: Pixel + PartialEq> PartialEq for Scene<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.dim == other.dim && self.tiles == other.tiles
+    }
+}
+
+impl<T: Pixel + fmt::Debug> fmt::Debug for Scene<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Scene")
+            .field("dim", &self.dim)
+            .field("grid", &self.grid().collect::<Vec<Option<T>>>())
+            .finish()
+    }
+}
+
+/// Scenes are (de)serialized as their dimensions along with the flattened grid of optional pixels
+impl<T: Pixel + Serialize> Serialize for Scene<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Scene", 2)?;
+        state.serialize_field("dim", &self.dim)?;
+        state.serialize_field("grid", &self.grid().collect::<Vec<Option<T>>>())?;
+        state.end()
+    }
+}
+
+impl<'de, T: Pixel + Deserialize<'de>> Deserialize<'de> for Scene<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct SceneData<T> {
+            dim: PCoord,
+            grid: Vec<Option<T>>,
+        }
+
+        let data = SceneData::<T>::deserialize(deserializer)?;
+        Scene::new(data.dim, data.grid).map_err(D::Error::custom)
+    }
 }
 
 impl Scene<TruePixel> {
@@ -127,52 +462,6 @@ impl Scene<TruePixel> {
     /// [oos]: OPixel::OutOfScene
     pub fn render(&self, dim: PCoord, mul: u8, repeat: PCoord, focus: Coord) -> Vec<OPixel> {
         use OPixel::*;
-        let mut grid: Vec<OPixel> =
-            vec![OutOfScene; usize::try_from(dim.area()).expect(U32TOUSIZE)];
-
-        let mut render_pixel = |i: i64, j: i64, x: i32, y: i32, is_focus: bool| {
-            for mi in 0..i64::from(u16::from(mul) * u16::from(repeat.x())) {
-                for mj in 0..i64::from(u16::from(mul) * u16::from(repeat.y())) {
-                    if (i + mi) < 0
-                        || (i + mi) >= i64::from(dim.x())
-                        || (j + mj) < 0
-                        || (j + mj) >= i64::from(dim.y())
-                    {
-                        continue;
-                    }
-                    if let Some(pixel_maybe) = self.get_pixel_raw(Coord { x, y }) {
-                        if let Some(pixel) = pixel_maybe {
-                            let index = usize::try_from(i + mi).unwrap() * usize::from(dim.y())
-                                + usize::try_from(j + mj).unwrap();
-                            grid[index] = Filled {
-                                scene_coord: UCoord {
-                                    x: u16::try_from(x).unwrap(),
-                                    y: u16::try_from(y).unwrap(),
-                                },
-                                color: pixel,
-                                is_focus: is_focus,
-                                has_cursor: false,
-                            };
-                        } else {
-                            let index = usize::try_from(i + mi).unwrap() * usize::from(dim.y())
-                                + usize::try_from(j + mj).unwrap();
-                            grid[index] = Empty {
-                                scene_coord: UCoord {
-                                    x: u16::try_from(x).unwrap(),
-                                    y: u16::try_from(y).unwrap(),
-                                },
-                                has_cursor: false,
-                            };
-                        }
-                    } else {
-                        let index = usize::try_from(i + mi).unwrap() * usize::from(dim.y())
-                            + usize::try_from(j + mj).unwrap();
-                        grid[index] = OutOfScene;
-                    }
-                }
-            }
-        };
-
         let focus_x = i32::from(focus.x);
         let focus_y = i32::from(focus.y);
         let mul_x = i64::from(u16::from(mul) * u16::from(repeat.x()));
@@ -190,51 +479,67 @@ impl Scene<TruePixel> {
                 .unwrap(),
         );
 
-        let mut i = mid_x;
-        let mut x = focus_x;
-        while i > -1 * mul_x {
-            let mut j = mid_y;
-            let mut y = focus_y;
-            while j > -1 * mul_y {
-                render_pixel(i, j, x, y, i == mid_x && j == mid_y);
-                j -= mul_y;
-                y -= 1;
-            }
-            j = mid_y + mul_y;
-            y = focus_y + 1;
-            while j < dim.y().into() {
-                render_pixel(i, j, x, y, i == mid_x && j == mid_y);
-                j += mul_y;
-                y += 1;
-            }
-            i -= mul_x;
-            x -= 1;
-        }
-        i = mid_x + mul_x;
-        x = focus_x + 1;
-        while i < dim.x().into() {
-            let mut j = mid_y;
-            let mut y = focus_y;
-            while j > -1 * mul_y {
-                render_pixel(i, j, x, y, false);
-                j -= mul_y;
-                y -= 1;
-            }
-            j = mid_y + mul_y;
-            y = focus_y + 1;
-            while j < dim.y().into() {
-                render_pixel(i, j, x, y, false);
-                j += mul_y;
-                y += 1;
+        //the scene coordinate of each output row & column, the focus being mapped to the block of
+        //mul x repeat output pixels starting at (mid_x, mid_y)
+        let rows = (0..i64::from(dim.x()))
+            .map(|i| focus_x + (i - mid_x).div_euclid(mul_x) as i32)
+            .collect::<Vec<i32>>();
+        let columns = (0..i64::from(dim.y()))
+            .map(|j| focus_y + (j - mid_y).div_euclid(mul_y) as i32)
+            .collect::<Vec<i32>>();
+
+        //the scene pixels that are rendered are read row by row up front, which is much faster
+        //than looking up the tile of each of them
+        let xs = within(rows[0], rows[rows.len() - 1], self.dim.x());
+        let ys = within(columns[0], columns[columns.len() - 1], self.dim.y());
+        let mut window = Vec::with_capacity(xs.len() * ys.len());
+        self.window(xs.clone(), ys.clone(), &mut window);
+
+        let mut grid: Vec<OPixel> =
+            Vec::with_capacity(usize::try_from(dim.area()).expect(U32TOUSIZE));
+        for &x in &rows {
+            for &y in &columns {
+                let in_scene = x >= i32::from(xs.start)
+                    && x < i32::from(xs.end)
+                    && y >= i32::from(ys.start)
+                    && y < i32::from(ys.end);
+                if !in_scene {
+                    grid.push(OutOfScene);
+                    continue;
+                }
+                //casts wont fail because the coordinate is within the scene
+                let scene_coord = UCoord {
+                    x: x as u16,
+                    y: y as u16,
+                };
+                let index = usize::from(scene_coord.x - xs.start) * ys.len()
+                    + usize::from(scene_coord.y - ys.start);
+                grid.push(match window[index] {
+                    Some(color) => Filled {
+                        scene_coord,
+                        color,
+                        is_focus: x == focus_x && y == focus_y,
+                        has_cursor: false,
+                    },
+                    None => Empty {
+                        scene_coord,
+                        has_cursor: false,
+                    },
+                });
             }
-            i += mul_x;
-            x += 1;
         }
 
         return grid;
     }
 }
 
+/// Returns the range of coordinates from `first` to `last` (inclusive) along one axis that lie
+/// within a Scene of length `len` along it
+fn within(first: i32, last: i32, len: u16) -> Range<u16> {
+    //casts wont fail because both are clamped within the length
+    (first.clamp(0, len.into()) as u16)..((last + 1).clamp(0, len.into()) as u16)
+}
+
 impl fmt::Display for Scene<TruePixel> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut iter = self.grid();
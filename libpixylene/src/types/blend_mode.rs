@@ -1,7 +1,7 @@
 use super::TruePixel;
 
 use serde::{Deserialize, Serialize};
-use std::fmt;
+use std::{fmt, str::FromStr};
 
 /// Enum of the different types of [blend-modes][b]
 ///
@@ -80,6 +80,37 @@ impl BlendMode {
     }
 }
 
+impl fmt::Display for BlendMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Composite(frac_a, _) => write!(f, "composite:{}", frac_a),
+            Self::Normal => write!(f, "normal"),
+            Self::Overwrite => write!(f, "overwrite"),
+        }
+    }
+}
+
+/// Parses a blend-mode by its name, a [`Composite`](BlendMode::Composite) blend-mode being written
+/// with the fraction of the top pixel, e.g. `composite:64`
+impl FromStr for BlendMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().split_once(':') {
+            None if s.eq_ignore_ascii_case("normal") => Ok(Self::Normal),
+            None if s.eq_ignore_ascii_case("overwrite") => Ok(Self::Overwrite),
+            Some(("composite", frac_a)) => match frac_a.parse::<u8>() {
+                Ok(frac_a) => Ok(Self::Composite(frac_a, 255 - frac_a)),
+                Err(err) => Err(format!("invalid composite fraction '{}': {}", frac_a, err)),
+            },
+            _ => Err(format!(
+                "unknown blend-mode '{}', expected one of: normal, overwrite, composite:<0-255>",
+                s
+            )),
+        }
+    }
+}
+
 // Error Types
 
 /// Error enum to describe various errors returns by BlendMode methods
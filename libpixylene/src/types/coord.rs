@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 use super::{PCoord, UCoord};
@@ -5,7 +6,7 @@ use super::{PCoord, UCoord};
 /// An integer coordinate type composed of two 32-bit integers.
 ///
 /// `This type can be constructed directly`.
-#[derive(Copy, Clone, PartialEq, Default, Debug, Savefile)]
+#[derive(Copy, Clone, PartialEq, Default, Debug, Savefile, Serialize, Deserialize)]
 pub struct Coord {
     /// The 'x' coordinate of the Coord
     pub x: i32,
@@ -87,7 +87,7 @@ impl<T: Into<u128> + TryFrom<u128> + Copy> PCoord<T> {
     pub fn mul(self, other: Self) -> Result<Self, ()> {
         Ok(Self {
             x: T::try_from(self.x.into() * other.x.into()).map_err(|_| ())?,
-            y: T::try_from(self.x.into() * other.x.into()).map_err(|_| ())?,
+            y: T::try_from(self.y.into() * other.y.into()).map_err(|_| ())?,
         })
     }
 }
@@ -8,9 +8,19 @@ use std::fmt;
 pub struct IndexedPixel(pub u8);
 
 impl Pixel for IndexedPixel {
+    const PACKED_LEN: usize = 1;
+
     fn empty() -> Self {
         Self(0)
     }
+
+    fn pack(&self, bytes: &mut [u8]) {
+        bytes[0] = self.0;
+    }
+
+    fn unpack(bytes: &[u8]) -> Self {
+        Self(bytes[0])
+    }
 }
 
 impl fmt::Display for IndexedPixel {
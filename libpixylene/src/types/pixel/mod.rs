@@ -5,5 +5,15 @@ mod indexed_pixel;
 pub use indexed_pixel::IndexedPixel;
 
 pub trait Pixel: Clone + Copy {
+    /// The number of bytes a pixel is packed into when stored in a
+    /// [`Scene`](crate::project::Scene)
+    const PACKED_LEN: usize;
+
     fn empty() -> Self;
+
+    /// Packs the pixel into `bytes`, which is [`PACKED_LEN`](Pixel::PACKED_LEN) long
+    fn pack(&self, bytes: &mut [u8]);
+
+    /// Unpacks a pixel from `bytes` packed by [`pack`](Pixel::pack)
+    fn unpack(bytes: &[u8]) -> Self;
 }
@@ -16,6 +16,8 @@ pub struct TruePixel {
 }
 
 impl Pixel for TruePixel {
+    const PACKED_LEN: usize = 4;
+
     /// Returns an empty #00000000 i.e. (0,0,0,0) pixel
     fn empty() -> Self {
         Self {
@@ -25,6 +27,19 @@ impl Pixel for TruePixel {
             a: 0,
         }
     }
+
+    fn pack(&self, bytes: &mut [u8]) {
+        bytes.copy_from_slice(&[self.r, self.g, self.b, self.a]);
+    }
+
+    fn unpack(bytes: &[u8]) -> Self {
+        Self {
+            r: bytes[0],
+            g: bytes[1],
+            b: bytes[2],
+            a: bytes[3],
+        }
+    }
 }
 
 impl TruePixel {
@@ -144,6 +159,48 @@ impl TruePixel {
                 .unwrap(), //guaranteed to be in range (0,255)
         }
     }
+
+    /// Shifts the perceived lightness of the color by `amount` (from -1.0 to 1.0) in the
+    /// [`OKLab`][ok] color space, keeping its hue & alpha
+    ///
+    /// [ok]: https://bottosson.github.io/posts/oklab
+    pub fn shade(self, amount: f32) -> Self {
+        let to_linear = |c: u8| {
+            let c = c as f64 / 255.0;
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        let from_linear = |c: f64| {
+            let c = if c <= 0.0031308 {
+                c * 12.92
+            } else {
+                1.055 * c.powf(1.0 / 2.4) - 0.055
+            };
+            (c.clamp(0.0, 1.0) * 255.0).round() as u8
+        };
+        let (r, g, b) = (to_linear(self.r), to_linear(self.g), to_linear(self.b));
+
+        let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+        let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+        let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+        let lightness = 0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s;
+        let a = 1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s;
+        let b = 0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s;
+
+        let lightness = (lightness + amount as f64).clamp(0.0, 1.0);
+        let l = (lightness + 0.3963377774 * a + 0.2158037573 * b).powi(3);
+        let m = (lightness - 0.1055613458 * a - 0.0638541728 * b).powi(3);
+        let s = (lightness - 0.0894841775 * a - 1.2914855480 * b).powi(3);
+        Self {
+            r: from_linear(4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s),
+            g: from_linear(-1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s),
+            b: from_linear(-0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s),
+            a: self.a,
+        }
+    }
 }
 
 impl fmt::Display for TruePixel {
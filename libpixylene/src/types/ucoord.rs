@@ -1,9 +1,10 @@
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// An `U`nsigned `Coord`inate type composed of two 16-bit unsigned integers.
 ///
 /// `This type can be constructed directly`.
-#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Savefile)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Savefile, Serialize, Deserialize)]
 pub struct UCoord {
     /// The 'x' coordinate of the UCoord
     pub x: u16,
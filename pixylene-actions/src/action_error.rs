@@ -1,7 +1,7 @@
 use crate::command::ChangeError;
 
 use libpixylene::{
-    project::{LayersError, PaletteError, ProjectError, SceneError},
+    project::{CanvasError, LayersError, PaletteError, ProjectError, SceneError},
     types::{BlendError, TruePixelError},
 };
 
@@ -14,6 +14,7 @@ pub enum ActionError {
     ProjectError(ProjectError),
     LayersError(LayersError),
     BlendError(BlendError),
+    CanvasError(CanvasError),
     OnlyNCursorsSupported(String, usize),
 
     // Custom Errors
@@ -59,6 +60,11 @@ impl From<BlendError> for ActionError {
         ActionError::BlendError(item)
     }
 }
+impl From<CanvasError> for ActionError {
+    fn from(item: CanvasError) -> ActionError {
+        ActionError::CanvasError(item)
+    }
+}
 impl std::fmt::Display for ActionError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         use ActionError::*;
@@ -70,6 +76,7 @@ impl std::fmt::Display for ActionError {
             ProjectError(project_error) => write!(f, "{}", project_error),
             LayersError(layers_error) => write!(f, "{}", layers_error),
             BlendError(blend_error) => write!(f, "{}", blend_error),
+            CanvasError(canvas_error) => write!(f, "{}", canvas_error),
             OnlyNCursorsSupported(supported, supplied) => write!(
                 f,
                 "this action only supports {} cursor/s, found {}",
@@ -0,0 +1,238 @@
+use crate::{ActionError, Console};
+
+use libpixylene::types::TruePixel;
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+/// The type of value a [`Param`] takes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParamType {
+    /// A whole number between `min` & `max`, both included
+    Integer { min: i64, max: i64 },
+
+    /// A number between 0.0 & 1.0, both included
+    Fraction,
+
+    /// A color as `#RRGGBB[AA]` or as a palette index
+    Color,
+
+    /// Any text
+    Text,
+}
+
+impl ParamType {
+    /// Returns an error describing why the value isn't of this type, if it isn't
+    fn check(&self, value: &str) -> Result<(), String> {
+        match self {
+            ParamType::Integer { min, max } => match value.parse::<i64>() {
+                Ok(num) if num >= *min && num <= *max => Ok(()),
+                Ok(num) => Err(format!(
+                    "expecting between {} and {}, found {}",
+                    min, max, num
+                )),
+                Err(err) => Err(err.to_string()),
+            },
+            ParamType::Fraction => match value.parse::<f32>() {
+                Ok(num) if (0.0..=1.0).contains(&num) => Ok(()),
+                Ok(num) => Err(format!("expecting between 0.0 and 1.0, found {}", num)),
+                Err(err) => Err(err.to_string()),
+            },
+            ParamType::Color => match value.parse::<u8>() {
+                Ok(_) => Ok(()),
+                Err(_) => TruePixel::from_hex(value).map(|_| ()).map_err(|_| {
+                    String::from("expecting #RRGGBB[AA] or a palette index from 0 to 255")
+                }),
+            },
+            ParamType::Text => Ok(()),
+        }
+    }
+}
+
+/// A named parameter of an [`Action`](crate::memento::Action), that is given an argument from a
+/// keybind or command or else prompted for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Param {
+    /// The name of the parameter, by which arguments can be given as `name=value`
+    pub name: &'static str,
+
+    /// The type of value the parameter takes
+    pub kind: ParamType,
+
+    /// The message the user is prompted with when no argument is given
+    pub prompt: &'static str,
+
+    /// Whether the parameter can be left empty for the action to use a default instead
+    pub optional: bool,
+}
+
+impl Param {
+    /// The parameter of actions that take a count prefix typed before their key themselves,
+    /// instead of being repeated that many times
+    pub const COUNT: Param = Param {
+        name: "count",
+        kind: ParamType::Integer {
+            min: 1,
+            max: u16::MAX as i64,
+        },
+        prompt: "count: ",
+        optional: true,
+    };
+}
+
+/// The arguments of an [`Action`](crate::memento::Action), validated against its [`Param`]s
+///
+/// Arguments are given in the order of the parameters, or by name as `name=value` in any order.
+/// Parameters that are not given an argument are prompted for when the action asks for them, and
+/// prompted values are validated the same way.
+#[derive(Debug, Clone, Default)]
+pub struct Args {
+    params: &'static [Param],
+    values: HashMap<&'static str, String>,
+}
+
+impl Args {
+    /// Returns the arguments for the parameters with none given, so that each one is prompted for
+    pub fn none(params: &'static [Param]) -> Args {
+        Args {
+            params,
+            values: HashMap::new(),
+        }
+    }
+
+    /// Validates the arguments against the parameters
+    ///
+    /// `Note`: This method may fail with the [`ArgsError`](ActionError::ArgsError) error variant
+    /// only.
+    pub fn parse(params: &'static [Param], args: &[String]) -> Result<Args, ActionError> {
+        use ActionError::ArgsError;
+
+        let mut values = HashMap::new();
+        let mut positional = params.iter();
+        for arg in args {
+            let named = arg
+                .split_once('=')
+                .and_then(|(name, value)| Some((find(params, name)?, value)));
+            let (param, value) = match named {
+                Some(named) => named,
+                None => match positional.next() {
+                    Some(param) => (param, arg.as_str()),
+                    None => {
+                        return Err(ArgsError(format!(
+                            "expecting at most {} argument/s, found '{}'",
+                            params.len(),
+                            arg
+                        )))
+                    }
+                },
+            };
+            if values.contains_key(param.name) {
+                return Err(ArgsError(format!("'{}' is given twice", param.name)));
+            }
+            validate(param, value).map_err(ArgsError)?;
+            values.insert(param.name, value.trim().to_owned());
+        }
+        Ok(Args { params, values })
+    }
+
+    /// Returns the argument of the named parameter parsed, prompting for it if it wasn't given
+    ///
+    /// `Note`: This method may fail with the [`Discarded`][d] or [`InputError`][ie] error variants
+    /// only.
+    ///
+    /// [d]: ActionError::Discarded
+    /// [ie]: ActionError::InputError
+    pub fn get<T>(&self, console: &dyn Console, name: &str) -> Result<T, ActionError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let value = self.value(console, name)?;
+        value
+            .parse::<T>()
+            .map_err(|err| ActionError::InputError(format!("invalid input '{}': {}", value, err)))
+    }
+
+    /// Returns the argument of the named parameter parsed like [`get`](Args::get), or the default
+    /// if it is left empty
+    ///
+    /// `Note`: This method may fail with the [`Discarded`][d] or [`InputError`][ie] error variants
+    /// only.
+    ///
+    /// [d]: ActionError::Discarded
+    /// [ie]: ActionError::InputError
+    pub fn get_or<T>(&self, console: &dyn Console, name: &str, default: T) -> Result<T, ActionError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        Ok(self.get_optional(console, name)?.unwrap_or(default))
+    }
+
+    /// Returns the argument of the named parameter parsed like [`get`](Args::get), or None if it
+    /// is left empty, for defaults that may not exist
+    ///
+    /// `Note`: This method may fail with the [`Discarded`][d] or [`InputError`][ie] error variants
+    /// only.
+    ///
+    /// [d]: ActionError::Discarded
+    /// [ie]: ActionError::InputError
+    pub fn get_optional<T>(
+        &self,
+        console: &dyn Console,
+        name: &str,
+    ) -> Result<Option<T>, ActionError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        match self.value(console, name)?.as_str() {
+            "" => Ok(None),
+            value => value.parse::<T>().map(Some).map_err(|err| {
+                ActionError::InputError(format!("invalid input '{}': {}", value, err))
+            }),
+        }
+    }
+
+    /// Returns whether an argument was given for the named parameter
+    pub fn has(&self, name: &str) -> bool {
+        self.values.contains_key(name)
+    }
+
+    /// Returns the argument of the [`count`](Param::COUNT) parameter, or 1 if it wasn't given
+    /// without prompting for it
+    ///
+    /// `Note`: This method may fail with the [`InputError`](ActionError::InputError) error variant
+    /// only.
+    pub fn count(&self, console: &dyn Console) -> Result<u16, ActionError> {
+        if self.has(Param::COUNT.name) {
+            self.get(console, Param::COUNT.name)
+        } else {
+            Ok(1)
+        }
+    }
+
+    fn value(&self, console: &dyn Console, name: &str) -> Result<String, ActionError> {
+        if let Some(value) = self.values.get(name) {
+            return Ok(value.clone());
+        }
+        //only parameters declared by the action are asked for
+        let param = find(self.params, name).expect("action asked for an undeclared parameter");
+        let value = console.cmdin(param.prompt).ok_or(ActionError::Discarded)?;
+        validate(param, &value).map_err(ActionError::InputError)?;
+        Ok(value.trim().to_owned())
+    }
+}
+
+fn find<'a>(params: &'a [Param], name: &str) -> Option<&'a Param> {
+    params.iter().find(|param| param.name == name)
+}
+
+fn validate(param: &Param, value: &str) -> Result<(), String> {
+    match value.trim() {
+        "" if param.optional => Ok(()),
+        "" => Err(format!("'{}' cannot be empty", param.name)),
+        value => param
+            .kind
+            .check(value)
+            .map_err(|err| format!("invalid {} '{}': {}", param.name, value, err)),
+    }
+}
@@ -1,6 +1,9 @@
 mod console;
 pub use console::{Console, LogType};
 
+mod args;
+pub use args::{Args, Param, ParamType};
+
 pub mod command;
 
 pub mod memento;
@@ -11,3 +14,1089 @@ pub use action_error::ActionError;
 pub mod std_actions;
 
 pub mod utils;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libpixylene::{
+        project::{Canvas, Layers, LayersType, Palette},
+        types::{PCoord, TruePixel, UCoord},
+    };
+    use memento::{ActionManager, HistoryLimits};
+
+    fn canvas() -> Canvas {
+        let mut layers = Layers::new(PCoord::new(8, 8).unwrap());
+        layers.new_layer(None).unwrap();
+        Canvas {
+            layers: LayersType::True(layers),
+            palette: Palette::gruvbox(),
+        }
+    }
+
+    /// Draws a black pixel at the given row of the first column & commits it
+    fn draw(action_manager: &mut ActionManager, canvas: &mut Canvas, x: u16) {
+        canvas.layers.to_true_mut().unwrap()[0]
+            .scene
+            .set_pixel(UCoord { x, y: 0 }, Some(TruePixel::BLACK))
+            .unwrap();
+        assert!(action_manager.commit(canvas, "pencil"));
+    }
+
+    /// Undoes as far as possible, returning the number of steps undone
+    fn undo_all(action_manager: &mut ActionManager, canvas: &mut Canvas) -> usize {
+        let mut steps = 0;
+        while action_manager.state().changes > 0 {
+            action_manager.undo(canvas);
+            steps += 1;
+        }
+        steps
+    }
+
+    #[test]
+    fn history_max_steps() {
+        let mut canvas = canvas();
+        let limits = HistoryLimits {
+            max_steps: Some(3),
+            max_memory: None,
+        };
+        let mut action_manager = ActionManager::new(&canvas, limits);
+        for x in 0..5 {
+            draw(&mut action_manager, &mut canvas, x);
+        }
+        assert_eq!(action_manager.last_seq(), 5);
+        assert_eq!(undo_all(&mut action_manager, &mut canvas), 3);
+
+        //the oldest state kept has the first two pixels drawn
+        let scene = &canvas.layers.to_true().unwrap()[0].scene;
+        for x in 0..5 {
+            assert_eq!(
+                scene.get_pixel(UCoord { x, y: 0 }).unwrap().is_some(),
+                x < 2
+            );
+        }
+        assert!(!action_manager.jump(&mut canvas, 1));
+    }
+
+    #[test]
+    fn history_max_memory() {
+        let mut canvas = canvas();
+        let mut unlimited = ActionManager::new(&canvas, HistoryLimits::default());
+        draw(&mut unlimited, &mut canvas.clone(), 0);
+        let step = unlimited.memory();
+
+        let limits = HistoryLimits {
+            max_steps: None,
+            max_memory: Some(step * 2),
+        };
+        let mut action_manager = ActionManager::new(&canvas, limits);
+        for x in 0..6 {
+            draw(&mut action_manager, &mut canvas, x);
+            assert!(action_manager.memory() <= step * 2);
+        }
+        assert_eq!(undo_all(&mut action_manager, &mut canvas), 2);
+
+        //the latest step is always kept even if it alone is over the limit
+        let limits = HistoryLimits {
+            max_steps: None,
+            max_memory: Some(1),
+        };
+        let mut action_manager = ActionManager::new(&canvas, limits);
+        draw(&mut action_manager, &mut canvas, 7);
+        draw(&mut action_manager, &mut canvas, 6);
+        assert_eq!(action_manager.memory(), step);
+        assert_eq!(undo_all(&mut action_manager, &mut canvas), 1);
+    }
+
+    #[test]
+    fn history_prunes_branches() {
+        let mut canvas = canvas();
+        let limits = HistoryLimits {
+            max_steps: Some(2),
+            max_memory: None,
+        };
+        let mut action_manager = ActionManager::new(&canvas, limits);
+        draw(&mut action_manager, &mut canvas, 0);
+        draw(&mut action_manager, &mut canvas, 1);
+        action_manager.undo(&mut canvas);
+        draw(&mut action_manager, &mut canvas, 2);
+        assert_eq!(action_manager.branches().len(), 2);
+
+        //moving the oldest state past the fork forgets the branch left behind along with its
+        //memory
+        draw(&mut action_manager, &mut canvas, 3);
+        let seqs = action_manager
+            .branches()
+            .iter()
+            .map(|state| state.seq)
+            .collect::<Vec<usize>>();
+        assert_eq!(seqs, vec![4]);
+        assert!(!action_manager.jump(&mut canvas, 2));
+        let history = action_manager.history(None);
+        assert_eq!(
+            history
+                .states
+                .iter()
+                .map(|state| state.seq)
+                .collect::<Vec<_>>(),
+            vec![3, 4]
+        );
+        let mut single = ActionManager::new(&canvas, HistoryLimits::default());
+        draw(&mut single, &mut canvas.clone(), 5);
+        assert_eq!(action_manager.memory(), single.memory());
+
+        //the history saved can be limited further, keeping the states closest to the current one
+        assert_eq!(action_manager.history(Some(0)).states.len(), 1);
+    }
+
+    /// Returns the rows of the first column that have pixels drawn
+    fn drawn(canvas: &Canvas) -> Vec<u16> {
+        let scene = &canvas.layers.to_true().unwrap()[0].scene;
+        (0..8)
+            .filter(|x| scene.get_pixel(UCoord { x: *x, y: 0 }).unwrap().is_some())
+            .collect()
+    }
+
+    #[test]
+    fn undo_tree_branches() {
+        let mut canvas = canvas();
+        let mut action_manager = ActionManager::new(&canvas, HistoryLimits::default());
+        draw(&mut action_manager, &mut canvas, 0);
+        draw(&mut action_manager, &mut canvas, 1);
+        action_manager.undo(&mut canvas);
+        assert_eq!(drawn(&canvas), vec![0]);
+
+        //committing after undoing starts a new branch instead of discarding the undone commit
+        draw(&mut action_manager, &mut canvas, 2);
+        assert_eq!(action_manager.state().seq, 3);
+        assert_eq!(action_manager.state().changes, 2);
+        let branches = action_manager.branches();
+        assert_eq!(
+            branches.iter().map(|state| state.seq).collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        assert_eq!(action_manager.num_branches(), 2);
+
+        //redo goes to the branch last committed or visited
+        action_manager.undo(&mut canvas);
+        action_manager.redo(&mut canvas);
+        assert_eq!(drawn(&canvas), vec![0, 2]);
+        assert!(action_manager.jump(&mut canvas, 2));
+        action_manager.undo(&mut canvas);
+        action_manager.redo(&mut canvas);
+        assert_eq!(action_manager.state().seq, 2);
+        assert_eq!(drawn(&canvas), vec![0, 1]);
+
+        //committing over a branch's latest state keeps the number of branches
+        draw(&mut action_manager, &mut canvas, 3);
+        assert_eq!(action_manager.num_branches(), 2);
+        assert_eq!(action_manager.branches()[1].seq, 4);
+
+        //the branches are the same once the history is saved & loaded back
+        let loaded = ActionManager::from_history(
+            &canvas,
+            action_manager.history(None),
+            HistoryLimits::default(),
+        )
+        .unwrap();
+        assert_eq!(loaded.branches(), action_manager.branches());
+        assert_eq!(loaded.state(), action_manager.state());
+    }
+
+    #[test]
+    fn undo_tree_jumps() {
+        let mut canvas = canvas();
+        let mut action_manager = ActionManager::new(&canvas, HistoryLimits::default());
+        draw(&mut action_manager, &mut canvas, 0);
+        draw(&mut action_manager, &mut canvas, 1);
+        action_manager.undo(&mut canvas);
+        action_manager.undo(&mut canvas);
+        draw(&mut action_manager, &mut canvas, 2);
+        draw(&mut action_manager, &mut canvas, 3);
+
+        //jumping across branches reverts up to the fork & applies down the other branch
+        assert!(action_manager.jump(&mut canvas, 2));
+        assert_eq!(drawn(&canvas), vec![0, 1]);
+        assert!(action_manager.jump(&mut canvas, 4));
+        assert_eq!(drawn(&canvas), vec![2, 3]);
+        assert!(action_manager.jump(&mut canvas, 0));
+        assert_eq!(drawn(&canvas), Vec::<u16>::new());
+        assert!(!action_manager.jump(&mut canvas, 5));
+        assert_eq!(action_manager.state().seq, 0);
+
+        //uncommitted changes are discarded
+        canvas.layers.to_true_mut().unwrap()[0]
+            .scene
+            .set_pixel(UCoord { x: 7, y: 0 }, Some(TruePixel::BLACK))
+            .unwrap();
+        assert!(action_manager.jump(&mut canvas, 3));
+        assert_eq!(drawn(&canvas), vec![2]);
+    }
+
+    #[test]
+    fn undo_tree_earlier_later() {
+        let mut canvas = canvas();
+        let mut action_manager = ActionManager::new(&canvas, HistoryLimits::default());
+        draw(&mut action_manager, &mut canvas, 0);
+        draw(&mut action_manager, &mut canvas, 1);
+        action_manager.undo(&mut canvas);
+        draw(&mut action_manager, &mut canvas, 2);
+
+        //states are visited in the order they were committed regardless of their branches
+        let mut visited = vec![(action_manager.state().seq, drawn(&canvas))];
+        while action_manager.earlier(&mut canvas) {
+            visited.push((action_manager.state().seq, drawn(&canvas)));
+        }
+        assert_eq!(
+            visited,
+            vec![
+                (3, vec![0, 2]),
+                (2, vec![0, 1]),
+                (1, vec![0]),
+                (0, Vec::new())
+            ]
+        );
+        let mut seqs = Vec::new();
+        while action_manager.later(&mut canvas) {
+            seqs.push(action_manager.state().seq);
+        }
+        assert_eq!(seqs, vec![1, 2, 3]);
+        assert_eq!(drawn(&canvas), vec![0, 2]);
+        assert!(!action_manager.later(&mut canvas));
+    }
+
+    #[test]
+    fn jump_list_records_jumps() {
+        use libpixylene::{project::Project, types::Coord};
+        use memento::JumpList;
+
+        let mut canvas = canvas();
+        canvas
+            .layers
+            .to_true_mut()
+            .unwrap()
+            .new_layer(None)
+            .unwrap();
+        let mut project = Project::new(canvas);
+        project.focus = (Coord { x: 0, y: 0 }, 0);
+        let mut jump_list = JumpList::new(&project, Some(3));
+
+        //stepping the focus is never a jump however far it goes in total
+        for y in 1..20 {
+            project.focus.0.y = y;
+            assert!(!jump_list.record(&project));
+        }
+        project.focus.0.x = 10;
+        assert!(jump_list.record(&project));
+        project.focus.1 = 1;
+        assert!(jump_list.record(&project));
+        _ = project.toggle_cursor_at(&(UCoord { x: 0, y: 0 }, 0));
+        assert!(!jump_list.record(&project));
+        _ = project.clear_cursors().count();
+        _ = project.toggle_cursor_at(&(UCoord { x: 0, y: 7 }, 0));
+        assert!(!jump_list.record(&project));
+        _ = project.clear_cursors().count();
+        _ = project.toggle_cursor_at(&(UCoord { x: 0, y: 7 }, 1));
+        assert!(jump_list.record(&project));
+
+        //the oldest views are forgotten past the maximum number of jumps
+        project.focus.0.x = 3;
+        assert!(jump_list.jump_back(&mut project));
+        assert_eq!(project.focus, (Coord { x: 10, y: 19 }, 1));
+        assert_eq!(project.num_cursors(), 1);
+        assert!(jump_list.jump_back(&mut project));
+        assert_eq!(project.focus, (Coord { x: 10, y: 19 }, 1));
+        assert_eq!(project.num_cursors(), 0);
+        assert!(!jump_list.jump_back(&mut project));
+        assert!(jump_list.jump_forward(&mut project));
+        assert!(jump_list.jump_forward(&mut project));
+        assert_eq!(project.focus, (Coord { x: 3, y: 19 }, 1));
+        assert!(!jump_list.jump_forward(&mut project));
+
+        //a view is recorded whatever the move when marked
+        project.focus.0.x = 4;
+        assert!(jump_list.mark(&project));
+        assert!(!jump_list.mark(&project));
+
+        //steps followed are never recorded, even when counted past the jump distance
+        project.focus.0.y = 0;
+        jump_list.follow(&project);
+        assert!(!jump_list.record(&project));
+        project.focus.0.y = 18;
+        assert!(jump_list.record(&project));
+    }
+
+    /// A Console answering prompts with the answers given, in order
+    struct Answers(std::cell::RefCell<Vec<&'static str>>);
+
+    impl Console for Answers {
+        fn cmdin(&self, _message: &str) -> Option<String> {
+            let mut answers = self.0.borrow_mut();
+            (!answers.is_empty()).then(|| answers.remove(0).to_owned())
+        }
+        fn cmdout(&self, _message: &str, _log_type: &LogType) {}
+    }
+
+    #[test]
+    fn action_args() {
+        const PARAMS: &[Param] = &[
+            Param {
+                name: "radius",
+                kind: ParamType::Integer { min: 1, max: 9 },
+                prompt: "radius: ",
+                optional: false,
+            },
+            Param {
+                name: "opacity",
+                kind: ParamType::Fraction,
+                prompt: "opacity: ",
+                optional: true,
+            },
+            Param {
+                name: "color",
+                kind: ParamType::Color,
+                prompt: "color: ",
+                optional: true,
+            },
+        ];
+        let parse = |args: &[&str]| {
+            Args::parse(
+                PARAMS,
+                &args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>(),
+            )
+        };
+        let none = Answers(Default::default());
+
+        //arguments are given in order or by name
+        let args = parse(&["4", "color=#ff0000"]).unwrap();
+        assert_eq!(args.get::<u16>(&none, "radius").unwrap(), 4);
+        assert_eq!(
+            args.get::<String>(&none, "color").unwrap(),
+            String::from("#ff0000")
+        );
+        let args = parse(&["opacity=0.5", "3"]).unwrap();
+        assert_eq!(args.get::<u16>(&none, "radius").unwrap(), 3);
+        assert_eq!(args.get::<f32>(&none, "opacity").unwrap(), 0.5);
+        assert!(args.has("opacity") && !args.has("color"));
+
+        //arguments are validated against the types of the parameters
+        for invalid in [
+            &["0"][..],
+            &["radius=x"],
+            &["1", "1.5"],
+            &["1", "0.5", "#12"],
+            &["1", "0.5", "256"],
+            &["1", "0.5", "3", "extra"],
+            &["1", "radius=2"],
+            &[""],
+        ] {
+            assert!(
+                matches!(parse(invalid), Err(ActionError::ArgsError(_))),
+                "{:?}",
+                invalid
+            );
+        }
+        assert!(parse(&["1", "", "7"]).is_ok());
+
+        //parameters not given are prompted for & validated the same way, empty ones using the
+        //default
+        let args = parse(&["5"]).unwrap();
+        let answers = Answers(std::cell::RefCell::new(vec!["", "2"]));
+        assert_eq!(args.get_or(&answers, "opacity", 1.0).unwrap(), 1.0);
+        assert_eq!(args.get::<u8>(&answers, "color").unwrap(), 2);
+        let args = Args::none(PARAMS);
+        let answers = Answers(std::cell::RefCell::new(vec!["10"]));
+        assert!(matches!(
+            args.get::<u16>(&answers, "radius"),
+            Err(ActionError::InputError(_))
+        ));
+        assert!(matches!(
+            args.get::<u16>(&none, "radius"),
+            Err(ActionError::Discarded)
+        ));
+        //actions take their arguments by name
+        use memento::Action;
+        let mut project = libpixylene::project::Project::new(canvas());
+        let mut new = std_actions::layer::New;
+        let args = Args::parse(new.params(), &[String::from("color=#123456")]).unwrap();
+        new.perform_with(&mut project, &none, &args).unwrap();
+        assert_eq!(project.focus.1, 1);
+        assert_eq!(
+            project.canvas.layers.to_true().unwrap()[1]
+                .scene
+                .get_pixel(UCoord { x: 0, y: 0 })
+                .unwrap(),
+            Some(TruePixel::from_hex("#123456").unwrap())
+        );
+        assert!(Args::parse(new.params(), &[String::from("blue")]).is_err());
+    }
+
+    #[test]
+    fn action_count() {
+        use libpixylene::{project::Project, types::Coord};
+        use memento::Action;
+        use std_actions::{cursors::MoveAllCursors, project::MoveFocus};
+
+        let none = Answers(Default::default());
+        let mut project = Project::new(canvas());
+        _ = project.toggle_cursor_at(&(UCoord { x: 1, y: 1 }, 0));
+
+        //actions taking a count run once with it instead of being repeated
+        let mut right = MoveAllCursors::new(Coord { x: 0, y: 1 });
+        assert!(right.params().contains(&Param::COUNT));
+        let args = Args::parse(right.params(), &[String::from("count=5")]).unwrap();
+        right.perform_with(&mut project, &none, &args).unwrap();
+        assert_eq!(project.cursors().next(), Some(&(UCoord { x: 1, y: 6 }, 0)));
+        right.perform(&mut project, &none).unwrap();
+        assert_eq!(project.cursors().next(), Some(&(UCoord { x: 1, y: 7 }, 0)));
+
+        //moves past the edge fail as a whole
+        let args = Args::parse(right.params(), &[String::from("count=2")]).unwrap();
+        assert!(right.perform_with(&mut project, &none, &args).is_err());
+        assert_eq!(project.cursors().next(), Some(&(UCoord { x: 1, y: 7 }, 0)));
+
+        let mut up = MoveFocus::new(Coord { x: -1, y: 0 });
+        let args = Args::parse(up.params(), &[String::from("count=3")]).unwrap();
+        let focus = project.focus.0;
+        up.perform_with(&mut project, &none, &args).unwrap();
+        assert_eq!(
+            project.focus.0,
+            Coord {
+                x: focus.x - 3,
+                y: focus.y
+            }
+        );
+        assert!(Args::parse(up.params(), &[String::from("count=0")]).is_err());
+    }
+
+    /// A project with a black 3x3 block at rows & columns 2 to 4 of its only layer
+    fn blocked() -> libpixylene::project::Project {
+        let mut project = libpixylene::project::Project::new(canvas());
+        for x in 2..5 {
+            for y in 2..5 {
+                project.canvas.layers.to_true_mut().unwrap()[0]
+                    .scene
+                    .set_pixel(UCoord { x, y }, Some(TruePixel::BLACK))
+                    .unwrap();
+            }
+        }
+        project
+    }
+
+    fn sorted(mut pixels: Vec<UCoord>) -> Vec<(u16, u16)> {
+        pixels.sort_by_key(|pixel| (pixel.x, pixel.y));
+        pixels.into_iter().map(|pixel| (pixel.x, pixel.y)).collect()
+    }
+
+    #[test]
+    fn motions() {
+        use memento::{cover, Motion};
+        use std_actions::motions::{Column, Region, Row, ToBoundary, WholeLayer};
+        use utils::Direction;
+
+        let mut project = blocked();
+        let at = |x, y| (UCoord { x, y }, 0);
+
+        //a region doesn't spread diagonally or across colors
+        let block = Region.pixels(&project, &at(3, 3), 1).unwrap();
+        assert_eq!(
+            sorted(block),
+            (2..5)
+                .flat_map(|x| (2..5).map(move |y| (x, y)))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(Region.pixels(&project, &at(0, 7), 1).unwrap().len(), 64 - 9);
+
+        //the count is the number of color changes to stop at, stopping at the edge otherwise
+        let right = ToBoundary::new(Direction::Right);
+        let span = |count| sorted(right.pixels(&project, &at(3, 0), count).unwrap());
+        assert_eq!(span(1), vec![(3, 0), (3, 1)]);
+        assert_eq!(span(2), (0..5).map(|y| (3, y)).collect::<Vec<_>>());
+        assert_eq!(span(3), (0..8).map(|y| (3, y)).collect::<Vec<_>>());
+        assert_eq!(span(9), span(3));
+        let up = ToBoundary::new(Direction::Up);
+        assert_eq!(
+            sorted(up.pixels(&project, &at(4, 3), 1).unwrap()),
+            vec![(2, 3), (3, 3), (4, 3)]
+        );
+
+        assert_eq!(
+            sorted(Row.pixels(&project, &at(5, 6), 1).unwrap()),
+            (0..8).map(|y| (5, y)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            sorted(Column.pixels(&project, &at(5, 6), 1).unwrap()),
+            (0..8).map(|x| (x, 6)).collect::<Vec<_>>()
+        );
+        assert_eq!(WholeLayer.pixels(&project, &at(5, 6), 1).unwrap().len(), 64);
+
+        //pixels covered from several cursors are covered once
+        _ = project.toggle_cursor_at(&at(1, 1));
+        _ = project.toggle_cursor_at(&at(1, 6));
+        assert_eq!(cover(&Row, &project, 1).unwrap().len(), 8);
+        _ = project.toggle_cursor_at(&at(6, 6));
+        assert_eq!(cover(&Column, &project, 1).unwrap().len(), 16);
+    }
+
+    #[test]
+    fn operators() {
+        use memento::{Action, Operator};
+        use std_actions::operators::{Clipboard, Erase, Paste, Recolor, Yank};
+        use utils::{get_pixel, OptionalTrueOrIndexed::True};
+
+        let none = Answers(Default::default());
+        let mut project = blocked();
+        let pixel = |project: &libpixylene::project::Project, x, y| match get_pixel(
+            project,
+            UCoord { x, y },
+            0,
+        )
+        .unwrap()
+        {
+            True(pixel) => pixel,
+            _ => unreachable!(),
+        };
+
+        //yanked pixels are kept at their offsets from the top-left of the yanked ones
+        let clipboard = Clipboard::default();
+        let yanked = [(3, 3), (3, 4), (4, 5), (5, 5)]
+            .map(|(x, y)| (UCoord { x, y }, 0))
+            .to_vec();
+        Yank::new(clipboard.clone())
+            .operate(&mut project, &yanked, &none)
+            .unwrap();
+        let mut offsets = clipboard
+            .borrow()
+            .iter()
+            .map(|(offset, pixel)| (offset.x, offset.y, *pixel != True(None)))
+            .collect::<Vec<_>>();
+        offsets.sort();
+        assert_eq!(
+            offsets,
+            vec![(0, 0, true), (0, 1, true), (1, 2, false), (2, 2, false)]
+        );
+
+        //pasting leaves out the empty pixels & the ones outside the canvas
+        _ = project.toggle_cursor_at(&(UCoord { x: 7, y: 6 }, 0));
+        _ = project.toggle_cursor_at(&(UCoord { x: 0, y: 0 }, 0));
+        project.canvas.layers.to_true_mut().unwrap()[0]
+            .scene
+            .set_pixel(UCoord { x: 1, y: 2 }, Some(TruePixel::FAVOURITE))
+            .unwrap();
+        Paste::new(clipboard.clone())
+            .perform(&mut project, &none)
+            .unwrap();
+        assert_eq!(pixel(&project, 0, 0), Some(TruePixel::BLACK));
+        assert_eq!(pixel(&project, 0, 1), Some(TruePixel::BLACK));
+        assert_eq!(pixel(&project, 1, 2), Some(TruePixel::FAVOURITE));
+        assert_eq!(pixel(&project, 7, 6), Some(TruePixel::BLACK));
+        assert_eq!(pixel(&project, 7, 7), Some(TruePixel::BLACK));
+        assert_eq!(pixel(&project, 6, 6), None);
+
+        //recoloring only changes the non-empty pixels
+        let row = (0..8).map(|y| (UCoord { x: 2, y }, 0)).collect::<Vec<_>>();
+        let equipped = *project.canvas.palette.get_equipped();
+        assert_ne!(equipped, TruePixel::BLACK);
+        Recolor.operate(&mut project, &row, &none).unwrap();
+        for y in 0..8 {
+            let expected = (2..5).contains(&y).then_some(equipped);
+            assert_eq!(pixel(&project, 2, y), expected);
+        }
+
+        Erase.operate(&mut project, &row[..3], &none).unwrap();
+        assert_eq!(pixel(&project, 2, 2), None);
+        assert_eq!(pixel(&project, 2, 3), Some(equipped));
+    }
+
+    #[test]
+    fn secondary_unset() {
+        use libpixylene::project::{PaletteError, Project};
+        use memento::Action;
+        use std_actions::scene::{Pencil, Replace, ReplaceIn};
+
+        let none = Answers(Default::default());
+        let mut project = Project::new(canvas());
+        _ = project.toggle_cursor_at(&(UCoord { x: 1, y: 0 }, 0));
+
+        //nothing is drawn with a secondary color that hasn't been chosen
+        assert!(matches!(
+            Pencil::secondary().perform(&mut project, &none),
+            Err(ActionError::PaletteError(PaletteError::NoSecondary))
+        ));
+        assert!(drawn(&project.canvas).is_empty());
+        let replace = |project: &mut Project| {
+            let mut replace = Replace::new(ReplaceIn::All);
+            let args = Args::parse(replace.params(), &[]).unwrap();
+            replace.perform_with(project, &Answers(vec!["", "", ""].into()), &args)
+        };
+        assert!(matches!(
+            replace(&mut project),
+            Err(ActionError::PaletteError(PaletteError::NoSecondary))
+        ));
+
+        project.canvas.palette.equip_secondary(2).unwrap();
+        Pencil::secondary().perform(&mut project, &none).unwrap();
+        assert_eq!(drawn(&project.canvas), vec![1]);
+        assert!(replace(&mut project).is_err());
+        Pencil::new(None).perform(&mut project, &none).unwrap();
+        replace(&mut project).unwrap();
+    }
+
+    #[test]
+    fn eyedropper_true() {
+        use libpixylene::project::Project;
+        use memento::Action;
+        use std_actions::palette::Eyedropper;
+
+        let mut canvas = canvas();
+        let third = *canvas.palette.get_color(3).unwrap();
+        let new = TruePixel::from_hex("#123456").unwrap();
+        let layers = canvas.layers.to_true_mut().unwrap();
+        layers.new_layer(None).unwrap();
+        layers[0]
+            .scene
+            .set_pixel(UCoord { x: 0, y: 0 }, Some(third))
+            .unwrap();
+        layers[1]
+            .scene
+            .set_pixel(UCoord { x: 0, y: 1 }, Some(new))
+            .unwrap();
+        let mut project = Project::new(canvas);
+        project.focus.1 = 0;
+        let none = Answers(Default::default());
+        let pick = |project: &mut Project, y, merged, console: &Answers| {
+            Eyedropper::new(Some(UCoord { x: 0, y }), merged).perform(project, console)
+        };
+
+        //colors in the palette are equipped, others are set at the first free index
+        pick(&mut project, 0, false, &none).unwrap();
+        assert_eq!(project.canvas.palette.equipped(), 3);
+        assert!(pick(&mut project, 1, false, &none).is_err());
+        pick(&mut project, 1, true, &none).unwrap();
+        assert_eq!(project.canvas.palette.equipped(), 9);
+        assert_eq!(*project.canvas.palette.get_equipped(), new);
+        pick(&mut project, 0, true, &none).unwrap();
+        pick(&mut project, 1, true, &none).unwrap();
+        assert_eq!(project.canvas.palette.equipped(), 9);
+        assert_eq!(project.canvas.palette.colors().count(), 9);
+
+        //a full palette has the equipped color replaced only if agreed to
+        project.canvas.palette.unset_color(9);
+        for index in 9..=u8::MAX {
+            project.canvas.palette.set_color(index, "#ffffff").unwrap();
+        }
+        project.canvas.palette.equip(5).unwrap();
+        assert!(matches!(
+            pick(&mut project, 1, true, &Answers(vec!["n"].into())),
+            Err(ActionError::Discarded)
+        ));
+        assert_ne!(*project.canvas.palette.get_color(5).unwrap(), new);
+        pick(&mut project, 1, true, &Answers(vec!["y"].into())).unwrap();
+        assert_eq!(project.canvas.palette.equipped(), 5);
+        assert_eq!(*project.canvas.palette.get_color(5).unwrap(), new);
+    }
+
+    #[test]
+    fn eyedropper_indexed() {
+        use libpixylene::{project::Project, types::IndexedPixel};
+        use memento::Action;
+        use std_actions::palette::Eyedropper;
+
+        let mut layers = Layers::<IndexedPixel>::new(PCoord::new(8, 8).unwrap());
+        layers.new_layer(None).unwrap();
+        layers.new_layer(None).unwrap();
+        layers[0]
+            .scene
+            .set_pixel(UCoord { x: 0, y: 0 }, Some(IndexedPixel(4)))
+            .unwrap();
+        layers[1]
+            .scene
+            .set_pixel(UCoord { x: 0, y: 1 }, Some(IndexedPixel(6)))
+            .unwrap();
+        layers[1]
+            .scene
+            .set_pixel(UCoord { x: 0, y: 2 }, Some(IndexedPixel(200)))
+            .unwrap();
+        let mut project = Project::new(Canvas {
+            layers: LayersType::Indexed(layers),
+            palette: Palette::gruvbox(),
+        });
+        project.focus.1 = 0;
+        let none = Answers(Default::default());
+
+        //the index of the pixel is equipped as is
+        Eyedropper::new(Some(UCoord { x: 0, y: 0 }), false)
+            .perform(&mut project, &none)
+            .unwrap();
+        assert_eq!(project.canvas.palette.equipped(), 4);
+        assert!(matches!(
+            Eyedropper::new(Some(UCoord { x: 0, y: 1 }), false).perform(&mut project, &none),
+            Err(ActionError::OperationError(_))
+        ));
+        Eyedropper::new(Some(UCoord { x: 0, y: 1 }), true)
+            .perform(&mut project, &none)
+            .unwrap();
+        assert_eq!(project.canvas.palette.equipped(), 6);
+
+        //from the single cursor when no coordinate is given
+        _ = project.toggle_cursor_at(&(UCoord { x: 0, y: 0 }, 0));
+        Eyedropper::new(None, false)
+            .perform(&mut project, &none)
+            .unwrap();
+        assert_eq!(project.canvas.palette.equipped(), 4);
+
+        //indexes that aren't in the palette can't be equipped
+        project.focus.1 = 1;
+        assert!(Eyedropper::new(Some(UCoord { x: 0, y: 2 }), false)
+            .perform(&mut project, &none)
+            .is_err());
+        assert_eq!(project.canvas.palette.equipped(), 4);
+    }
+
+    #[test]
+    fn brushes_and_lines() {
+        use memento::Brush;
+        use utils::line;
+
+        let dim = PCoord::new(8, 8).unwrap();
+        let at = |x, y| UCoord { x, y };
+        let covered = |brush: Brush, x, y| sorted(brush.pixels(at(x, y), dim));
+
+        //lines include both their ends & only ever step to a neighbouring pixel
+        assert_eq!(line(at(2, 2), at(2, 2)), vec![at(2, 2)]);
+        assert_eq!(
+            line(at(2, 5), at(2, 1)),
+            vec![at(2, 5), at(2, 4), at(2, 3), at(2, 2), at(2, 1)]
+        );
+        for (from, to) in [
+            (at(0, 0), at(3, 1)),
+            (at(7, 0), at(0, 7)),
+            (at(5, 6), at(1, 0)),
+        ] {
+            let line = line(from, to);
+            assert_eq!((line[0], line[line.len() - 1]), (from, to));
+            assert_eq!(
+                line.len(),
+                from.x.abs_diff(to.x).max(from.y.abs_diff(to.y)) as usize + 1
+            );
+            assert!(line.windows(2).all(
+                |pair| pair[0].x.abs_diff(pair[1].x) <= 1 && pair[0].y.abs_diff(pair[1].y) <= 1
+            ));
+        }
+
+        //brushes grow down & right of their centers when even, & are clipped by the canvas
+        assert_eq!(covered(Brush::Square(0), 3, 3), vec![(3, 3)]);
+        assert_eq!(covered(Brush::Square(1), 3, 3), vec![(3, 3)]);
+        assert_eq!(
+            covered(Brush::Square(2), 3, 3),
+            vec![(3, 3), (3, 4), (4, 3), (4, 4)]
+        );
+        assert_eq!(covered(Brush::Square(3), 3, 3).len(), 9);
+        assert_eq!(
+            covered(Brush::Square(3), 0, 0),
+            vec![(0, 0), (0, 1), (1, 0), (1, 1)]
+        );
+        assert_eq!(
+            covered(Brush::Circle(2), 3, 3),
+            covered(Brush::Square(2), 3, 3)
+        );
+        assert_eq!(
+            covered(Brush::Circle(3), 3, 3),
+            vec![(2, 3), (3, 2), (3, 3), (3, 4), (4, 3)]
+        );
+        let circle = covered(Brush::Circle(5), 3, 3);
+        assert_eq!(circle.len(), 21);
+        assert!(!circle.contains(&(1, 1)) && circle.contains(&(1, 2)));
+        assert!(Brush::Square(1).is_pixel() && !Brush::Circle(2).is_pixel());
+
+        assert_eq!("circle:5".parse(), Ok(Brush::Circle(5)));
+        assert_eq!("s:2".parse(), Ok(Brush::Square(2)));
+        assert_eq!("3".parse(), Ok(Brush::Square(3)));
+        assert!("triangle:3".parse::<Brush>().is_err());
+        assert!("circle:300".parse::<Brush>().is_err());
+        assert_eq!(Brush::Circle(5).to_string().parse(), Ok(Brush::Circle(5)));
+    }
+
+    #[test]
+    fn strokes() {
+        use libpixylene::project::Project;
+        use memento::{Stroke, Tool, ToolOptions};
+        use std::{cell::RefCell, rc::Rc};
+        use std_actions::tools::{Line, Pencil};
+
+        let console = Answers(Default::default());
+        let move_to = |project: &mut Project, cursors: &[(u16, u16)]| {
+            _ = project.clear_cursors().count();
+            for (x, y) in cursors {
+                project
+                    .toggle_cursor_at(&(UCoord { x: *x, y: *y }, 0))
+                    .unwrap();
+            }
+        };
+        let painted = |project: &Project| {
+            let scene = &project.canvas.layers.to_true().unwrap()[0].scene;
+            let mut pixels = Vec::new();
+            for x in 0..8 {
+                for y in 0..8 {
+                    if scene.get_pixel(UCoord { x, y }).unwrap().is_some() {
+                        pixels.push((x, y));
+                    }
+                }
+            }
+            pixels
+        };
+        let draw = |tool: Rc<RefCell<dyn Tool>>, options: &ToolOptions, moves: &[&[(u16, u16)]]| {
+            let mut project = Project::new(canvas());
+            move_to(&mut project, moves[0]);
+            let mut stroke = Stroke::new(tool, options.clone(), &mut project, &console).unwrap();
+            for cursors in &moves[1..] {
+                move_to(&mut project, cursors);
+                stroke.extend(&mut project, &console).unwrap();
+            }
+            painted(&project)
+        };
+        let mut options = ToolOptions::default();
+
+        //the pencil follows the cursors, skipping across when they jump
+        let turn: &[&[(u16, u16)]] = &[&[(0, 0)], &[(0, 1)], &[(1, 1)], &[(1, 4)]];
+        assert_eq!(
+            draw(Rc::new(RefCell::new(Pencil)), &options, turn),
+            vec![(0, 0), (0, 1), (1, 1), (1, 2), (1, 3), (1, 4)]
+        );
+
+        //pixel-perfect strokes leave out the corners of L-shaped turns only
+        options.pixel_perfect = true;
+        assert_eq!(
+            draw(Rc::new(RefCell::new(Pencil)), &options, turn),
+            vec![(0, 0), (1, 1), (1, 2), (1, 3), (1, 4)]
+        );
+        let straight: &[&[(u16, u16)]] = &[&[(0, 0)], &[(0, 1)], &[(0, 2)], &[(1, 3)]];
+        assert_eq!(
+            draw(Rc::new(RefCell::new(Pencil)), &options, straight),
+            vec![(0, 0), (0, 1), (0, 2), (1, 3)]
+        );
+        //but not with wider brushes
+        options.brush = memento::Brush::Square(2);
+        assert_eq!(
+            draw(Rc::new(RefCell::new(Pencil)), &options, &turn[..3]),
+            vec![
+                (0, 0),
+                (0, 1),
+                (0, 2),
+                (1, 0),
+                (1, 1),
+                (1, 2),
+                (2, 1),
+                (2, 2)
+            ]
+        );
+        options = ToolOptions::default();
+
+        //more cursors start the paths over, keeping what was drawn
+        assert_eq!(
+            draw(
+                Rc::new(RefCell::new(Pencil)),
+                &options,
+                &[&[(0, 0)], &[(0, 2)], &[(0, 2), (3, 3)], &[(0, 3), (3, 4)]]
+            ),
+            vec![(0, 0), (0, 1), (0, 2), (0, 3), (3, 3), (3, 4)]
+        );
+
+        //lines are redrawn from where the pen was put down every time the cursors move
+        let mut project = Project::new(canvas());
+        move_to(&mut project, &[(0, 0)]);
+        let mut stroke =
+            Stroke::new(Rc::new(RefCell::new(Line)), options, &mut project, &console).unwrap();
+        move_to(&mut project, &[(0, 3)]);
+        stroke.extend(&mut project, &console).unwrap();
+        assert_eq!(painted(&project), vec![(0, 0), (0, 1), (0, 2), (0, 3)]);
+        //leaving pixels that were changed otherwise meanwhile as they are
+        let other = TruePixel::from_hex("#123456").unwrap();
+        project.canvas.layers.to_true_mut().unwrap()[0]
+            .scene
+            .set_pixel(UCoord { x: 0, y: 2 }, Some(other))
+            .unwrap();
+        move_to(&mut project, &[(2, 0)]);
+        stroke.extend(&mut project, &console).unwrap();
+        assert_eq!(painted(&project), vec![(0, 0), (0, 2), (1, 0), (2, 0)]);
+        assert_eq!(
+            project.canvas.layers.to_true().unwrap()[0]
+                .scene
+                .get_pixel(UCoord { x: 0, y: 2 })
+                .unwrap(),
+            Some(other)
+        );
+    }
+
+    #[test]
+    fn opacity() {
+        use libpixylene::{project::Project, types::BlendMode};
+        use memento::{Action, Tool, ToolOptions};
+        use std_actions::{scene, tools};
+
+        let console = Answers(Default::default());
+        let mut project = Project::new(canvas());
+        let at = (UCoord { x: 0, y: 0 }, 0);
+        project.toggle_cursor_at(&at).unwrap();
+        let alpha = |project: &Project| {
+            project.canvas.layers.to_true().unwrap()[0]
+                .scene
+                .get_pixel(at.0)
+                .unwrap()
+                .map(|color| color.a)
+        };
+
+        //pencils scale the alpha of their color & erasers take away from it alike
+        scene::Pencil::new(None)
+            .with_options(128, BlendMode::Overwrite)
+            .perform(&mut project, &console)
+            .unwrap();
+        assert_eq!(alpha(&project), Some(128));
+        scene::Eraser::new(Some(64))
+            .perform(&mut project, &console)
+            .unwrap();
+        assert_eq!(alpha(&project), Some(95));
+        let options = ToolOptions {
+            opacity: 64,
+            ..ToolOptions::default()
+        };
+        tools::Eraser
+            .apply(&mut project, &[at], &options, &console)
+            .unwrap();
+        assert_eq!(alpha(&project), Some(71));
+
+        //until none is left
+        scene::Eraser::new(Some(254))
+            .perform(&mut project, &console)
+            .unwrap();
+        assert_eq!(alpha(&project), None);
+        scene::Pencil::new(None)
+            .perform(&mut project, &console)
+            .unwrap();
+        scene::Eraser::new(None)
+            .perform(&mut project, &console)
+            .unwrap();
+        assert_eq!(alpha(&project), None);
+    }
+
+    #[test]
+    fn gradients() {
+        use libpixylene::{
+            project::{Pattern, Project},
+            types::IndexedPixel,
+        };
+        use memento::Action;
+        use std_actions::scene::{Dithering, Gradient, GradientShape};
+
+        let console = Answers(Default::default());
+        let at = |x, y| UCoord { x, y };
+        //draws a smooth black to white gradient over the bounding box of the cursors, returning the
+        //grey of the pixels by row
+        let smooth = |shape, cursors: &[UCoord]| {
+            let mut project = Project::new(canvas());
+            project.canvas.palette.set_color(20, "#000000").unwrap();
+            project.canvas.palette.set_color(21, "#ffffff").unwrap();
+            for cursor in cursors {
+                project.toggle_cursor_at(&(*cursor, 0)).unwrap();
+            }
+            Gradient::new(Some(shape), Dithering::Smooth)
+                .with_colors(20, 21)
+                .bounded()
+                .perform(&mut project, &console)
+                .unwrap();
+            let scene = &project.canvas.layers.to_true().unwrap()[0].scene;
+            (0..8)
+                .map(|x| {
+                    (0..8)
+                        .map(|y| scene.get_pixel(at(x, y)).unwrap().map(|color| color.r))
+                        .collect()
+                })
+                .collect::<Vec<Vec<Option<u8>>>>()
+        };
+
+        //linear gradients go from their first color to their second exactly at the ends
+        let across = smooth(GradientShape::Linear(0), &[at(0, 0), at(0, 7)]);
+        assert_eq!(
+            (0..8).map(|y| across[0][y].unwrap()).collect::<Vec<u8>>(),
+            vec![0, 36, 73, 109, 146, 182, 219, 255]
+        );
+        assert_eq!(across[1][0], None);
+        let down = smooth(GradientShape::Linear(90), &[at(0, 0), at(7, 0)]);
+        assert_eq!(
+            (down[0][0], down[3][0], down[7][0]),
+            (Some(0), Some(109), Some(255))
+        );
+        let back = smooth(GradientShape::Linear(180), &[at(0, 0), at(0, 7)]);
+        assert_eq!((back[0][0], back[0][7]), (Some(255), Some(0)));
+        let diagonal = smooth(GradientShape::Linear(45), &[at(0, 0), at(3, 3)]);
+        assert_eq!((diagonal[0][0], diagonal[3][3]), (Some(0), Some(255)));
+        assert_eq!((diagonal[0][3], diagonal[3][0]), (Some(128), Some(128)));
+
+        //radial gradients go from the center to the farthest corners
+        let radial = smooth(GradientShape::Radial, &[at(0, 0), at(4, 4)]);
+        assert_eq!(radial[2][2], Some(0));
+        for (x, y) in [(0, 0), (0, 4), (4, 0), (4, 4)] {
+            assert_eq!(radial[x][y], Some(255));
+        }
+        assert_eq!(
+            (radial[2][0], radial[0][2], radial[2][4]),
+            (Some(180), Some(180), Some(180))
+        );
+
+        //every threshold of a Bayer matrix appears once, alternating between its quadrants
+        assert_eq!(Pattern::bayer(1, 0, 0), 0);
+        for size in [2u16, 4, 8, 16] {
+            let mut thresholds = (0..size)
+                .flat_map(|x| (0..size).map(move |y| Pattern::bayer(size, x, y)))
+                .collect::<Vec<u16>>();
+            assert_eq!(
+                (0..4)
+                    .map(|quadrant| Pattern::bayer(
+                        size,
+                        quadrant / 2 * size / 2,
+                        quadrant % 2 * size / 2
+                    ))
+                    .collect::<Vec<u16>>(),
+                vec![0, 2, 3, 1]
+            );
+            thresholds.sort();
+            assert_eq!(thresholds, (0..size * size).collect::<Vec<u16>>());
+        }
+        assert_eq!(
+            (0..4)
+                .map(|y| Pattern::bayer(4, 1, y))
+                .collect::<Vec<u16>>(),
+            vec![12, 4, 14, 6]
+        );
+
+        //indexed canvases are dithered between the two indices, & can't be smooth
+        let mut layers = Layers::<IndexedPixel>::new(PCoord::new(8, 8).unwrap());
+        layers.new_layer(None).unwrap();
+        let mut project = Project::new(Canvas {
+            layers: LayersType::Indexed(layers),
+            palette: Palette::gruvbox(),
+        });
+        project.toggle_cursor_at(&(at(0, 0), 0)).unwrap();
+        project.toggle_cursor_at(&(at(0, 7), 0)).unwrap();
+        assert!(matches!(
+            Gradient::new(Some(GradientShape::Linear(0)), Dithering::Smooth)
+                .bounded()
+                .with_colors(1, 2)
+                .perform(&mut project, &console),
+            Err(ActionError::InvalidCanvasType {
+                expecting_indexed: false
+            })
+        ));
+        Gradient::new(Some(GradientShape::Linear(0)), Dithering::Bayer(2))
+            .bounded()
+            .with_colors(1, 2)
+            .perform(&mut project, &console)
+            .unwrap();
+        let scene = &project.canvas.layers.to_indexed().unwrap()[0].scene;
+        assert_eq!(
+            (0..8)
+                .map(|y| scene.get_pixel(at(0, y)).unwrap().unwrap().0)
+                .collect::<Vec<u8>>(),
+            vec![1, 1, 2, 1, 2, 2, 2, 2]
+        );
+    }
+}
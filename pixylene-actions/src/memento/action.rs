@@ -1,11 +1,43 @@
 use super::ActionResult;
-use crate::Console;
+use crate::{Args, Console, Param};
 
 use libpixylene::project::Project;
 use std::fmt;
 
 pub trait Action {
     fn perform(&mut self, project: &mut Project, console: &dyn Console) -> ActionResult;
+
+    /// Returns the named parameters of the action, which arguments from keybinds & commands are
+    /// validated against
+    fn params(&self) -> &'static [Param] {
+        &[]
+    }
+
+    /// Performs the action with the arguments given for its parameters, prompting for the ones
+    /// not given
+    ///
+    /// Actions that take parameters implement this, & [`perform`](Action::perform) as this with
+    /// no arguments.
+    fn perform_with(
+        &mut self,
+        project: &mut Project,
+        console: &dyn Console,
+        _args: &Args,
+    ) -> ActionResult {
+        self.perform(project, console)
+    }
+
+    /// Returns whether the action may change the Canvas, so that the Canvas is diffed & committed
+    /// after it
+    fn changes_canvas(&self) -> bool {
+        true
+    }
+
+    /// Returns whether the action only steps the focus or cursors, which is never recorded as a
+    /// jump however far it goes, like vim's `j`
+    fn is_step(&self) -> bool {
+        false
+    }
     fn has_ended(&self) -> bool {
         true
     }
@@ -1,43 +1,100 @@
 use super::{Action, ActionResult};
-use crate::Console;
+use crate::{Args, Console};
 
-use libpixylene::project::{Canvas, Project};
+use libpixylene::project::{Canvas, CanvasDelta, History, HistoryState, Project};
 use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::rc::Rc;
-use undo::{Edit, History};
+use std::time::SystemTime;
 
-/// A stored edit to the Canvas
-struct TransformCanvas(
-    /// The old canvas
-    pub Canvas,
-    /// The new canvas
-    pub Canvas,
-);
+/// Limits to the history kept by an [`ActionManager`], past which the oldest steps are forgotten
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct HistoryLimits {
+    /// The maximum number of steps that can be undone
+    pub max_steps: Option<usize>,
 
-impl Edit for TransformCanvas {
-    type Target = Canvas;
-    type Output = ();
+    /// The maximum approximate number of bytes taken by the history, though the latest step is
+    /// always kept
+    pub max_memory: Option<usize>,
+}
 
-    fn edit(&mut self, canvas: &mut Canvas) {
-        *canvas = self.1.clone();
-    }
+/// A state of the Canvas in the undo tree of an [`ActionManager`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct UndoState {
+    /// The sequence number of the commit that led to this state, `0` being the initial state
+    pub seq: usize,
 
-    fn undo(&mut self, canvas: &mut Canvas) {
-        *canvas = self.0.clone();
-    }
+    /// The name of the action that led to this state
+    pub name: String,
+
+    /// The number of commits between the oldest state kept & this state
+    pub changes: usize,
+
+    /// When this state was committed
+    pub time: SystemTime,
 }
 
+/// A state in the undo tree
+struct Node {
+    /// The sequence number of the previous state
+    parent: Option<usize>,
+    /// The delta from the previous state, absent for the oldest state kept
+    delta: Option<CanvasDelta>,
+    name: String,
+    time: SystemTime,
+    /// The sequence numbers of the states committed over this one
+    children: Vec<usize>,
+    /// The child that redo goes to, the one last committed or visited
+    redo: Option<usize>,
+    /// The number of commits between the initial state & this state, including forgotten ones
+    depth: usize,
+}
+
+/// Manages the undo tree of a Canvas, in which every commit is a state whose parent is the state
+/// that was current when it was committed
+///
+/// Undoing after a commit & committing again starts a new branch rather than discarding the undone
+/// commits, and all the states remain reachable by their sequence numbers or chronologically.
 pub struct ActionManager {
     canvas_state: Canvas,
-    canvas_history: History<TransformCanvas>,
+    /// The states of the tree by their sequence numbers
+    nodes: BTreeMap<usize, Node>,
+    /// The sequence number of the oldest state kept
+    root: usize,
+    /// The sequence number of the state that the canvas state is at
+    current: usize,
+    /// The sequence number of the latest commit
+    last_seq: usize,
+    /// The sequence numbers of the latest state of every branch, kept as the tree changes
+    leaves: BTreeSet<usize>,
+    /// The approximate number of bytes taken by the deltas
+    memory: usize,
+    limits: HistoryLimits,
 }
 
 impl ActionManager {
     /// Creates a new ActionManager and uses the Canvas passed to create the initial commit
-    pub fn new(canvas: &Canvas) -> ActionManager {
+    pub fn new(canvas: &Canvas, limits: HistoryLimits) -> ActionManager {
         ActionManager {
             canvas_state: canvas.clone(),
-            canvas_history: History::new(),
+            nodes: BTreeMap::from([(
+                0,
+                Node {
+                    parent: None,
+                    delta: None,
+                    name: String::new(),
+                    time: SystemTime::now(),
+                    children: Vec::new(),
+                    redo: None,
+                    depth: 0,
+                },
+            )]),
+            root: 0,
+            current: 0,
+            last_seq: 0,
+            leaves: BTreeSet::from([0]),
+            memory: 0,
+            limits,
         }
     }
 
@@ -46,43 +103,381 @@ impl ActionManager {
         project: &mut Project,
         console: &dyn Console,
         action: Rc<RefCell<dyn Action>>,
+        args: &Args,
     ) -> ActionResult {
-        action.borrow_mut().perform(project, console)?;
+        action.borrow_mut().perform_with(project, console, args)?;
         Ok(())
     }
 
-    /// Commits the Canvas state only if it has changed, returning whether the Canvas has changed
-    pub fn commit(&mut self, canvas: &Canvas) -> bool {
-        let Self {
-            ref mut canvas_state,
-            ref mut canvas_history,
-        } = self;
-        if *canvas != *canvas_state {
-            let transform = TransformCanvas(canvas_state.clone(), canvas.clone());
-            canvas_history.edit(canvas_state, transform);
+    /// Creates an ActionManager from an undo [`History`] whose current state is the Canvas passed,
+    /// failing if the history is malformed or does not lead to the Canvas
+    ///
+    /// Every state of the history is checked by replaying its delta, so that undoing & redoing
+    /// can never reach a state that doesn't fit the Canvas.
+    pub fn from_history(
+        canvas: &Canvas,
+        history: History,
+        limits: HistoryLimits,
+    ) -> Option<ActionManager> {
+        let mut nodes = BTreeMap::<usize, Node>::new();
+        let mut root = None;
+        let mut memory = 0;
+        for state in history.states {
+            let valid = !nodes.contains_key(&state.seq)
+                && state.seq <= history.last_seq
+                && match (state.parent, &state.delta) {
+                    //states are in the order they were committed, so parents come first
+                    (Some(parent), Some(_)) => nodes.contains_key(&parent),
+                    (None, None) => root.replace(state.seq).is_none(),
+                    _ => false,
+                };
+            if !valid {
+                return None;
+            }
+            let mut depth = 0;
+            if let Some(parent) = state.parent {
+                let parent = nodes.get_mut(&parent).unwrap(); //wont fail because checked above
+                parent.children.push(state.seq);
+                parent.redo = Some(state.seq);
+                depth = parent.depth + 1;
+            }
+            memory += state.delta.as_ref().map(|delta| delta.size()).unwrap_or(0);
+            nodes.insert(
+                state.seq,
+                Node {
+                    parent: state.parent,
+                    delta: state.delta,
+                    name: state.name,
+                    time: state.time,
+                    children: Vec::new(),
+                    redo: None,
+                    depth,
+                },
+            );
+        }
+        if !nodes.contains_key(&history.current) {
+            return None;
+        }
+
+        let leaves = nodes
+            .iter()
+            .filter(|(_, node)| node.children.is_empty())
+            .map(|(&seq, _)| seq)
+            .collect();
+        let mut action_manager = ActionManager {
+            canvas_state: canvas.clone(),
+            leaves,
+            nodes,
+            root: root?,
+            current: history.current,
+            last_seq: history.last_seq,
+            memory,
+            limits,
+        };
+        if !action_manager.replays(canvas.clone()) {
+            return None;
+        }
+        let path = action_manager.ancestors(action_manager.current);
+        for pair in path.windows(2) {
+            action_manager.node_mut(pair[1]).redo = Some(pair[0]);
+        }
+        action_manager.enforce_limits();
+        Some(action_manager)
+    }
+
+    /// Returns the undo [`History`] of the Canvas, keeping only the states closest to the current
+    /// one if the deltas take more than `max_memory` bytes
+    pub fn history(&self, max_memory: Option<usize>) -> History {
+        let mut kept = BTreeSet::new();
+        let mut memory = 0;
+        let mut queue = VecDeque::from([self.current]);
+        while let Some(seq) = queue.pop_front() {
+            if kept.contains(&seq) {
+                continue;
+            }
+            let node = self.node(seq);
+            let size = node.delta.as_ref().map(|delta| delta.size()).unwrap_or(0);
+            if seq != self.current && max_memory.is_some_and(|max| memory + size > max) {
+                continue;
+            }
+            kept.insert(seq);
+            memory += size;
+            queue.extend(node.parent);
+            queue.extend(node.children.iter().rev());
+        }
+
+        History {
+            current: self.current,
+            last_seq: self.last_seq,
+            states: kept
+                .iter()
+                .map(|seq| {
+                    let node = self.node(*seq);
+                    let parent = node.parent.filter(|parent| kept.contains(parent));
+                    HistoryState {
+                        seq: *seq,
+                        parent,
+                        name: node.name.clone(),
+                        time: node.time,
+                        delta: parent.and(node.delta.clone()),
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    /// Commits the Canvas state as the result of the named action only if it has changed,
+    /// returning whether the Canvas has changed
+    ///
+    /// Only the difference from the current state is stored, as a new state in the tree.
+    pub fn commit(&mut self, canvas: &Canvas, name: &str) -> bool {
+        match CanvasDelta::between(&self.canvas_state, canvas) {
+            Some(delta) => {
+                delta.apply(&mut self.canvas_state);
+                debug_assert!(self.canvas_state == *canvas);
+                self.last_seq += 1;
+                self.memory += delta.size();
+                let depth = self.node(self.current).depth + 1;
+                self.nodes.insert(
+                    self.last_seq,
+                    Node {
+                        parent: Some(self.current),
+                        delta: Some(delta),
+                        name: name.to_owned(),
+                        time: SystemTime::now(),
+                        children: Vec::new(),
+                        redo: None,
+                        depth,
+                    },
+                );
+                let seq = self.last_seq;
+                self.leaves.remove(&self.current);
+                self.leaves.insert(seq);
+                let parent = self.node_mut(self.current);
+                parent.children.push(seq);
+                parent.redo = Some(seq);
+                self.current = self.last_seq;
+                self.enforce_limits();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Reverts the Canvas to the previous state, discarding any uncommitted changes
+    pub fn undo(&mut self, canvas: &mut Canvas) {
+        let target = self.node(self.current).parent.unwrap_or(self.current);
+        self.go_to(canvas, target);
+    }
+
+    /// Restores the Canvas to the state last committed or visited over the current one, discarding
+    /// any uncommitted changes
+    pub fn redo(&mut self, canvas: &mut Canvas) {
+        let target = self.node(self.current).redo.unwrap_or(self.current);
+        self.go_to(canvas, target);
+    }
+
+    /// Moves the Canvas to the state with the given sequence number across branches, discarding
+    /// any uncommitted changes, returning whether such a state exists
+    pub fn jump(&mut self, canvas: &mut Canvas, seq: usize) -> bool {
+        if self.nodes.contains_key(&seq) {
+            self.go_to(canvas, seq);
             true
         } else {
             false
         }
     }
 
-    pub fn undo(&mut self, canvas: &mut Canvas) {
-        let Self {
-            ref mut canvas_state,
-            ref mut canvas_history,
-        } = self;
-        canvas_history.undo(canvas_state);
-        *canvas = canvas_state.clone();
+    /// Moves the Canvas to the chronologically previous state, i.e., the one committed just before
+    /// the current one regardless of its branch, returning whether there is one
+    pub fn earlier(&mut self, canvas: &mut Canvas) -> bool {
+        match self.nodes.range(..self.current).next_back() {
+            Some((&seq, _)) => self.jump(canvas, seq),
+            None => false,
+        }
     }
 
-    pub fn redo(&mut self, canvas: &mut Canvas) {
-        let Self {
-            ref mut canvas_state,
-            ref mut canvas_history,
-        } = self;
-        canvas_history.redo(canvas_state);
-        *canvas = canvas_state.clone();
+    /// Moves the Canvas to the chronologically next state, i.e., the one committed just after the
+    /// current one regardless of its branch, returning whether there is one
+    pub fn later(&mut self, canvas: &mut Canvas) -> bool {
+        match self.nodes.range(self.current + 1..).next() {
+            Some((&seq, _)) => self.jump(canvas, seq),
+            None => false,
+        }
+    }
+
+    /// Returns the current state
+    pub fn state(&self) -> UndoState {
+        self.undo_state(self.current)
     }
 
-    //todo: add methods to go to next/previous branch
+    /// Returns the sequence number of the latest commit
+    pub fn last_seq(&self) -> usize {
+        self.last_seq
+    }
+
+    /// Returns the latest state of every branch, i.e., every state with nothing committed over it,
+    /// in the order they were committed
+    pub fn branches(&self) -> Vec<UndoState> {
+        self.leaves
+            .iter()
+            .map(|seq| self.undo_state(*seq))
+            .collect()
+    }
+
+    /// Returns the number of branches, i.e., the number of states with nothing committed over them
+    pub fn num_branches(&self) -> usize {
+        self.leaves.len()
+    }
+
+    /// Returns the approximate number of bytes taken by the history
+    pub fn memory(&self) -> usize {
+        self.memory
+    }
+
+    fn node(&self, seq: usize) -> &Node {
+        self.nodes.get(&seq).unwrap() //wont fail because only sequence numbers of kept states
+                                      //are used
+    }
+
+    fn node_mut(&mut self, seq: usize) -> &mut Node {
+        self.nodes.get_mut(&seq).unwrap() //wont fail because only sequence numbers of kept
+                                          //states are used
+    }
+
+    /// Returns the sequence numbers of the state & all its previous states up to the root
+    fn ancestors(&self, mut seq: usize) -> Vec<usize> {
+        let mut ancestors = vec![seq];
+        while let Some(parent) = self.node(seq).parent {
+            ancestors.push(parent);
+            seq = parent;
+        }
+        ancestors
+    }
+
+    fn undo_state(&self, seq: usize) -> UndoState {
+        let node = self.node(seq);
+        UndoState {
+            seq,
+            name: node.name.clone(),
+            changes: node.depth - self.node(self.root).depth,
+            time: node.time,
+        }
+    }
+
+    /// Returns whether every delta of the tree fits the state it is applied to, starting from the
+    /// Canvas at the current state
+    fn replays(&self, mut canvas: Canvas) -> bool {
+        for seq in self.ancestors(self.current) {
+            if let Some(delta) = &self.node(seq).delta {
+                if !delta.can_revert(&canvas) {
+                    return false;
+                }
+                delta.revert(&mut canvas);
+            }
+        }
+
+        //walks the tree depth-first, applying deltas on entering a state & reverting them on
+        //leaving it
+        let mut stack = vec![(self.root, true)];
+        while let Some((seq, entering)) = stack.pop() {
+            let node = self.node(seq);
+            if let Some(delta) = &node.delta {
+                if entering {
+                    if !delta.can_apply(&canvas) {
+                        return false;
+                    }
+                    delta.apply(&mut canvas);
+                } else {
+                    delta.revert(&mut canvas);
+                }
+            }
+            if entering {
+                stack.push((seq, false));
+                stack.extend(node.children.iter().map(|child| (*child, true)));
+            }
+        }
+        true
+    }
+
+    /// Moves the canvas state to the target state by reverting up to the common ancestor & applying
+    /// down from it, moving the Canvas along if it was in sync or resetting it otherwise
+    fn go_to(&mut self, canvas: &mut Canvas, target: usize) {
+        let in_sync = *canvas == self.canvas_state;
+        let from = self.ancestors(self.current);
+        let to = self.ancestors(target);
+        let common = *to.iter().find(|seq| from.contains(seq)).unwrap(); //wont fail because
+                                                                         //both have the root
+
+        let ups = from.iter().position(|seq| *seq == common).unwrap(); //wont fail because it
+                                                                       //was found in both
+        let downs = to.iter().position(|seq| *seq == common).unwrap();
+
+        for seq in &from[..ups] {
+            if let Some(delta) = &self.nodes[seq].delta {
+                delta.revert(&mut self.canvas_state);
+                if in_sync {
+                    delta.revert(canvas);
+                }
+            }
+        }
+        for seq in to[..downs].iter().rev() {
+            if let Some(delta) = &self.nodes[seq].delta {
+                delta.apply(&mut self.canvas_state);
+                if in_sync {
+                    delta.apply(canvas);
+                }
+            }
+            if let Some(parent) = self.node(*seq).parent {
+                self.node_mut(parent).redo = Some(*seq);
+            }
+        }
+        self.current = target;
+
+        if !in_sync {
+            *canvas = self.canvas_state.clone();
+        }
+    }
+
+    /// Forgets the oldest states until the history is within its limits, by moving the root
+    /// towards the current state & dropping the branches left behind
+    fn enforce_limits(&mut self) {
+        let HistoryLimits {
+            max_steps,
+            max_memory,
+        } = self.limits;
+        loop {
+            let path = self.ancestors(self.current);
+            let over_steps = max_steps.is_some_and(|max| self.nodes.len() - 1 > max);
+            //the latest step is always kept
+            let over_memory = max_memory.is_some_and(|max| self.memory > max && path.len() > 2);
+            if path.len() < 2 || !(over_steps || over_memory) {
+                break;
+            }
+
+            let new_root = path[path.len() - 2];
+            let old_root = self.root;
+            let mut forgotten = self
+                .node(old_root)
+                .children
+                .iter()
+                .copied()
+                .filter(|seq| *seq != new_root)
+                .collect::<Vec<usize>>();
+            self.nodes.remove(&old_root);
+            while let Some(seq) = forgotten.pop() {
+                if let Some(node) = self.nodes.remove(&seq) {
+                    self.leaves.remove(&seq);
+                    self.memory -= node.delta.map(|delta| delta.size()).unwrap_or(0);
+                    forgotten.extend(node.children);
+                }
+            }
+
+            let root = self.node_mut(new_root);
+            root.parent = None;
+            let delta = root.delta.take();
+            self.memory -= delta.map(|delta| delta.size()).unwrap_or(0);
+            self.root = new_root;
+        }
+    }
 }
@@ -0,0 +1,172 @@
+use libpixylene::{
+    project::Project,
+    types::{Coord, UCoord},
+};
+use std::collections::VecDeque;
+
+/// The view of a Project, i.e., its focus, cursors & output multiplier
+#[derive(Debug, Clone, PartialEq)]
+struct View {
+    focus: (Coord, u16),
+    /// The cursors sorted by layer & then coordinates
+    cursors: Vec<(UCoord, u16)>,
+    out_mul: u8,
+}
+
+impl View {
+    fn of(project: &Project) -> View {
+        let mut cursors = project.cursors().copied().collect::<Vec<(UCoord, u16)>>();
+        cursors.sort_by_key(|(coord, layer)| (*layer, coord.x, coord.y));
+        View {
+            focus: project.focus,
+            cursors,
+            out_mul: project.get_out_mul(),
+        }
+    }
+
+    /// Restores the view, leaving out the cursors & focused layer that no longer fit the Canvas
+    fn restore(&self, project: &mut Project) {
+        project.focus = self.focus;
+        project.focus.1 = self
+            .focus
+            .1
+            .min(project.canvas.layers.len().saturating_sub(1));
+        _ = project.clear_cursors().count();
+        for cursor in &self.cursors {
+            _ = project.toggle_cursor_at(cursor);
+        }
+        _ = project.set_out_mul(self.out_mul);
+    }
+}
+
+/// Where a Project is looking, i.e., its focus & first cursor, cheap enough to be compared after
+/// every action to tell jumps apart from small moves
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Position {
+    focus: (Coord, u16),
+    /// The cursor on the lowest layer at the lowest coordinates
+    cursor: Option<(UCoord, u16)>,
+}
+
+impl Position {
+    fn of(project: &Project) -> Position {
+        Position {
+            focus: project.focus,
+            cursor: project
+                .cursors()
+                .min_by_key(|(coord, layer)| (*layer, coord.x, coord.y))
+                .copied(),
+        }
+    }
+
+    /// Returns whether moving from this position to the other one is a jump, i.e., whether the
+    /// focus or the cursors move to another layer or by at least [`JUMP_DISTANCE`] rows or columns
+    fn jumps_to(&self, other: &Position) -> bool {
+        let far = |a: Coord, b: Coord| {
+            a.x.abs_diff(b.x) >= JUMP_DISTANCE || a.y.abs_diff(b.y) >= JUMP_DISTANCE
+        };
+        self.focus.1 != other.focus.1
+            || far(self.focus.0, other.focus.0)
+            || match (self.cursor, other.cursor) {
+                (Some((a, a_layer)), Some((b, b_layer))) => {
+                    a_layer != b_layer || far(Coord::from(&a), Coord::from(&b))
+                }
+                _ => false,
+            }
+    }
+}
+
+/// The least number of rows or columns the focus or the cursors have to move by in a single action
+/// for the move to be recorded as a jump
+const JUMP_DISTANCE: u32 = 8;
+
+/// Records the views of a Project so that they can be jumped back & forth between like vim's
+/// jumplist, apart from the undo history of the Canvas
+///
+/// Only jumps are recorded, i.e., actions that move the focus or cursors far or to another layer,
+/// so that small moves like stepping the focus don't fill the list. Recording after jumping back
+/// keeps the views jumped over, adding the new view to the end of the list instead.
+pub struct JumpList {
+    views: VecDeque<View>,
+    /// The index of the view being visited
+    position: usize,
+    /// The position of the Project when last recorded or checked
+    last: Position,
+    max_jumps: Option<usize>,
+}
+
+impl JumpList {
+    /// The number of views kept by default, like vim
+    pub const DEFAULT_MAX_JUMPS: usize = 100;
+
+    /// Creates a new JumpList that records the current view of the Project & keeps at most
+    /// `max_jumps` views if provided, though the latest view is always kept
+    pub fn new(project: &Project, max_jumps: Option<usize>) -> JumpList {
+        JumpList {
+            views: VecDeque::from([View::of(project)]),
+            position: 0,
+            last: Position::of(project),
+            max_jumps,
+        }
+    }
+
+    /// Records the view of the Project only if it was reached by a jump since it was last recorded
+    /// or checked, returning whether it was recorded
+    pub fn record(&mut self, project: &Project) -> bool {
+        let position = Position::of(project);
+        let jumped = self.last.jumps_to(&position);
+        self.last = position;
+        jumped && self.mark(project)
+    }
+
+    /// Updates the position of the Project that the next recording is checked against without
+    /// recording its view, after steps that are never jumps
+    pub fn follow(&mut self, project: &Project) {
+        self.last = Position::of(project);
+    }
+
+    /// Records the view of the Project whether or not it was reached by a jump, e.g., before
+    /// switching sessions, only if it differs from the one being visited, returning whether it was
+    /// recorded
+    pub fn mark(&mut self, project: &Project) -> bool {
+        let view = View::of(project);
+        self.last = Position::of(project);
+        if self.views[self.position] == view {
+            return false;
+        }
+        if self.views.back() != Some(&view) {
+            self.views.push_back(view);
+        }
+        while self.views.len() > self.max_jumps.unwrap_or(usize::MAX).max(1) {
+            self.views.pop_front();
+        }
+        self.position = self.views.len() - 1;
+        true
+    }
+
+    /// Restores the previous view, first recording the current one, returning whether there was a
+    /// previous view
+    pub fn jump_back(&mut self, project: &mut Project) -> bool {
+        self.mark(project);
+        if self.position == 0 {
+            return false;
+        }
+        self.position -= 1;
+        self.views[self.position].restore(project);
+        self.last = Position::of(project);
+        true
+    }
+
+    /// Restores the next view after having jumped back, first recording the current one, returning
+    /// whether there was a next view
+    pub fn jump_forward(&mut self, project: &mut Project) -> bool {
+        self.mark(project);
+        if self.position + 1 >= self.views.len() {
+            return false;
+        }
+        self.position += 1;
+        self.views[self.position].restore(project);
+        self.last = Position::of(project);
+        true
+    }
+}
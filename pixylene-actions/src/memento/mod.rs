@@ -2,6 +2,18 @@ mod action;
 pub use action::Action;
 
 mod action_manager;
-pub use action_manager::ActionManager;
+pub use action_manager::{ActionManager, HistoryLimits, UndoState};
+
+mod jump_list;
+pub use jump_list::JumpList;
+
+mod motion;
+pub use motion::{cover, Motion};
+
+mod operator;
+pub use operator::{Operation, Operator};
+
+mod tool;
+pub use tool::{Brush, Stroke, Tool, ToolOptions};
 
 pub type ActionResult = Result<(), crate::ActionError>;
@@ -0,0 +1,36 @@
+use crate::ActionError;
+
+use libpixylene::{project::Project, types::UCoord};
+use std::collections::HashSet;
+
+/// A Motion finds the pixels of a layer that it covers from a cursor, either by moving away from
+/// it (e.g. to the edge of the canvas) or as an object containing it (e.g. its row)
+///
+/// Motions are applied from every cursor of the project & the pixels they cover are handed to an
+/// [`Operator`](super::Operator).
+pub trait Motion {
+    fn pixels(
+        &self,
+        project: &Project,
+        cursor: &(UCoord, u16),
+        count: usize,
+    ) -> Result<Vec<UCoord>, ActionError>;
+}
+
+/// Returns the pixels covered by the motion from every cursor of the project, each only once
+pub fn cover(
+    motion: &dyn Motion,
+    project: &Project,
+    count: usize,
+) -> Result<Vec<(UCoord, u16)>, ActionError> {
+    let mut covered = HashSet::new();
+    let mut pixels = Vec::new();
+    for cursor in project.cursors() {
+        for coord in motion.pixels(project, cursor, count)? {
+            if covered.insert((coord, cursor.1)) {
+                pixels.push((coord, cursor.1));
+            }
+        }
+    }
+    Ok(pixels)
+}
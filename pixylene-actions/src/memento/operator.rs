@@ -0,0 +1,37 @@
+use super::{Action, ActionResult};
+use crate::Console;
+
+use libpixylene::{project::Project, types::UCoord};
+use std::{cell::RefCell, rc::Rc};
+
+/// An Operator acts on the set of pixels covered by a [`Motion`](super::Motion), e.g. filling or
+/// erasing them
+pub trait Operator {
+    fn operate(
+        &mut self,
+        project: &mut Project,
+        pixels: &[(UCoord, u16)],
+        console: &dyn Console,
+    ) -> ActionResult;
+}
+
+/// An [`Action`] that applies an [`Operator`] over a set of pixels, so that operations are
+/// performed & undone like any other action
+pub struct Operation {
+    operator: Rc<RefCell<dyn Operator>>,
+    pixels: Vec<(UCoord, u16)>,
+}
+
+impl Operation {
+    pub fn new(operator: Rc<RefCell<dyn Operator>>, pixels: Vec<(UCoord, u16)>) -> Self {
+        Operation { operator, pixels }
+    }
+}
+
+impl Action for Operation {
+    fn perform(&mut self, project: &mut Project, console: &dyn Console) -> ActionResult {
+        self.operator
+            .borrow_mut()
+            .operate(project, &self.pixels, console)
+    }
+}
@@ -0,0 +1,421 @@
+use super::ActionResult;
+use crate::{
+    utils::{get_pixel, line, set_pixel, OptionalTrueOrIndexed},
+    ActionError, Console,
+};
+
+use libpixylene::{
+    project::{Pattern, Project},
+    types::{BlendMode, PCoord, UCoord},
+};
+use std::{cell::RefCell, collections::HashMap, fmt, rc::Rc, str::FromStr};
+
+/// The shape of the pixels that a [`Tool`] covers around every point of a stroke
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Brush {
+    /// A square with sides of the given number of pixels
+    Square(u8),
+
+    /// A circle with a diameter of the given number of pixels
+    Circle(u8),
+}
+
+impl Brush {
+    /// Returns the coordinates inside the canvas that the brush covers when centered at a
+    /// coordinate
+    pub fn pixels(&self, center: UCoord, dim: PCoord) -> Vec<UCoord> {
+        let size = match self {
+            Brush::Square(size) | Brush::Circle(size) => (*size).max(1) as i32,
+        };
+        let (low, high) = (-(size - 1) / 2, size / 2);
+        let middle = (low + high) as f32 / 2.0;
+
+        let mut pixels = Vec::new();
+        for x in low..=high {
+            for y in low..=high {
+                if let Brush::Circle(_) = self {
+                    //leaves out the corners, e.g. a circle 3 pixels wide is a plus
+                    let distance = (x as f32 - middle).powi(2) + (y as f32 - middle).powi(2);
+                    if size > 2 && distance > (size * size) as f32 / 4.0 - 0.5 {
+                        continue;
+                    }
+                }
+                let (x, y) = (center.x as i32 + x, center.y as i32 + y);
+                if x >= 0 && y >= 0 && x < dim.x() as i32 && y < dim.y() as i32 {
+                    pixels.push(UCoord {
+                        x: x as u16,
+                        y: y as u16,
+                    });
+                }
+            }
+        }
+        pixels
+    }
+
+    /// Returns whether the brush covers a single pixel
+    pub fn is_pixel(&self) -> bool {
+        match self {
+            Brush::Square(size) | Brush::Circle(size) => *size <= 1,
+        }
+    }
+}
+
+/// Parses a brush written as its shape & size, e.g. `circle:5`, a bare size being a square
+impl FromStr for Brush {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Brush, String> {
+        let (shape, size) = s.split_once(':').unwrap_or(("square", s));
+        let size = size
+            .parse::<u8>()
+            .map_err(|err| format!("invalid brush size '{}': {}", size, err))?;
+        match shape {
+            "square" | "s" => Ok(Brush::Square(size)),
+            "circle" | "c" => Ok(Brush::Circle(size)),
+            _ => Err(format!(
+                "invalid brush shape '{}', expecting square or circle",
+                shape
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Brush {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Brush::Square(size) => write!(f, "square:{}", size),
+            Brush::Circle(size) => write!(f, "circle:{}", size),
+        }
+    }
+}
+
+/// The options that every [`Tool`] of a session is used with
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolOptions {
+    /// How opaque the color drawn is, scaling its alpha, or how much of the alpha is erased
+    pub opacity: u8,
+
+    /// How the color drawn is blended with the pixels under it on true-color canvases
+    pub blend_mode: BlendMode,
+
+    /// The pixels covered around every point of a stroke
+    pub brush: Brush,
+
+    /// Whether strokes leave out the corners of their L-shaped turns, so that lines drawn with a
+    /// single pixel stay a single pixel thin
+    pub pixel_perfect: bool,
+
+    /// The pattern that the color is drawn in, tiled from the top-left of the canvas
+    pub pattern: Option<Pattern>,
+
+    /// Whether the pixels left off by the pattern are drawn with the secondary color, rather than
+    /// being left as they are
+    pub mix_secondary: bool,
+}
+
+impl Default for ToolOptions {
+    fn default() -> Self {
+        ToolOptions {
+            opacity: 255,
+            blend_mode: BlendMode::Normal,
+            brush: Brush::Square(1),
+            pixel_perfect: false,
+            pattern: None,
+            mix_secondary: false,
+        }
+    }
+}
+
+impl ToolOptions {
+    /// The names of the options that can be [`set`](ToolOptions::set)
+    pub const NAMES: [&'static str; 6] = [
+        "opacity",
+        "blend",
+        "brush",
+        "pixelperfect",
+        "pattern",
+        "mix",
+    ];
+
+    /// Returns whether the pattern of the options leaves a coordinate off, which isn't acted on
+    /// unless mixing the secondary color in
+    pub fn leaves_off(&self, coord: UCoord) -> bool {
+        self.pattern
+            .as_ref()
+            .is_some_and(|pattern| !pattern.is_on(coord))
+    }
+
+    /// Sets an option by its name from a string
+    ///
+    /// `Note`: This method may fail with the [`InputError`](ActionError::InputError) error variant
+    /// only.
+    pub fn set(&mut self, option: &str, value: &str) -> Result<(), ActionError> {
+        let invalid = |err: String| {
+            ActionError::InputError(format!("invalid value '{}' for {}: {}", value, option, err))
+        };
+        match option {
+            "opacity" => {
+                self.opacity = value.parse().map_err(|err| invalid(format!("{}", err)))?;
+            }
+            "blend" => {
+                self.blend_mode = value.parse().map_err(invalid)?;
+            }
+            "brush" => {
+                self.brush = value.parse().map_err(invalid)?;
+            }
+            "pixelperfect" => {
+                self.pixel_perfect = value.parse().map_err(|err| invalid(format!("{}", err)))?;
+            }
+            "pattern" => {
+                self.pattern = parse_pattern(value).map_err(invalid)?;
+            }
+            "mix" => {
+                self.mix_secondary = match value {
+                    "secondary" => true,
+                    "transparent" => false,
+                    _ => return Err(invalid(String::from("expecting secondary or transparent"))),
+                };
+            }
+            _ => {
+                return Err(ActionError::InputError(format!(
+                    "tool option '{}' doesn't exist, expecting one of {}",
+                    option,
+                    Self::NAMES.join(", ")
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ToolOptions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}% {} {}{}",
+            self.opacity as u16 * 100 / 255,
+            self.blend_mode,
+            self.brush,
+            if self.pixel_perfect {
+                " pixel-perfect"
+            } else {
+                ""
+            },
+        )?;
+        if let Some(pattern) = &self.pattern {
+            write!(
+                f,
+                " pattern {}{}",
+                pattern,
+                if self.mix_secondary {
+                    " over secondary"
+                } else {
+                    ""
+                },
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// A Tool acts on the pixels that its brush covers along the paths of the cursors while the pen is
+/// down, e.g. drawing or erasing them
+///
+/// Tools are used through a [`Stroke`], so that a whole stroke is performed & undone like any other
+/// action.
+pub trait Tool {
+    /// Acts on pixels that haven't been acted on yet in the stroke
+    fn apply(
+        &mut self,
+        project: &mut Project,
+        pixels: &[(UCoord, u16)],
+        options: &ToolOptions,
+        console: &dyn Console,
+    ) -> ActionResult;
+
+    /// Returns whether the stroke is redrawn from where the pen was put down to the cursors every
+    /// time they move, like a line, rather than being added to
+    fn redraws(&self) -> bool {
+        false
+    }
+
+    /// Called when the pen is put down, before the tool is first applied
+    fn begin(&mut self) {}
+}
+
+/// A stroke of a [`Tool`] from when the pen is put down until it is lifted, applying the tool along
+/// the paths that the cursors move on
+///
+/// Cursors are told apart by their order, so their paths are only followed while their number &
+/// layers stay the same, & start over from them otherwise.
+pub struct Stroke {
+    tool: Rc<RefCell<dyn Tool>>,
+    pub options: ToolOptions,
+    //the layer & path of every cursor, in the order of the cursors
+    paths: Vec<(u16, Vec<UCoord>)>,
+    //the pixels acted on so far, so that none is acted on twice, with what they were before & after
+    //the tool acted on them, to restore them from
+    done: HashMap<(UCoord, u16), (OptionalTrueOrIndexed, OptionalTrueOrIndexed)>,
+}
+
+impl Stroke {
+    /// Puts the pen down & applies the tool at every cursor
+    pub fn new(
+        tool: Rc<RefCell<dyn Tool>>,
+        options: ToolOptions,
+        project: &mut Project,
+        console: &dyn Console,
+    ) -> Result<Stroke, ActionError> {
+        tool.borrow_mut().begin();
+        let mut stroke = Stroke {
+            tool,
+            options,
+            paths: Vec::new(),
+            done: HashMap::new(),
+        };
+        let cursors = sorted_cursors(project);
+        stroke.paths = cursors
+            .iter()
+            .map(|(coord, layer)| (*layer, vec![*coord]))
+            .collect();
+        stroke.paint(project, cursors, console)?;
+        Ok(stroke)
+    }
+
+    /// Applies the tool along the paths that the cursors moved on since it was last applied, doing
+    /// nothing if they haven't moved
+    pub fn extend(&mut self, project: &mut Project, console: &dyn Console) -> ActionResult {
+        let cursors = sorted_cursors(project);
+        let followed = cursors.len() == self.paths.len()
+            && cursors
+                .iter()
+                .zip(&self.paths)
+                .all(|((_, layer), (path_layer, _))| layer == path_layer);
+        if followed
+            && cursors
+                .iter()
+                .zip(&self.paths)
+                .all(|((coord, _), (_, path))| path.last() == Some(coord))
+        {
+            return Ok(());
+        }
+
+        if !followed {
+            //whatever was drawn is kept as the cursors can't be matched to their paths
+            self.done.clear();
+            self.paths = cursors
+                .iter()
+                .map(|(coord, layer)| (*layer, vec![*coord]))
+                .collect();
+            return self.paint(project, cursors, console);
+        }
+
+        let mut points = Vec::new();
+        if self.tool.borrow().redraws() {
+            for (pixel, (original, painted)) in std::mem::take(&mut self.done) {
+                restore(project, pixel, original, painted)?;
+            }
+            for ((coord, layer), (_, path)) in cursors.iter().zip(self.paths.iter_mut()) {
+                *path = line(path[0], *coord);
+                points.extend(path.iter().map(|coord| (*coord, *layer)));
+            }
+            return self.paint(project, points, console);
+        }
+
+        let pixel_perfect = self.options.pixel_perfect && self.options.brush.is_pixel();
+        for ((coord, layer), (_, path)) in cursors.iter().zip(self.paths.iter_mut()) {
+            let last = *path.last().unwrap(); //wont fail because paths start with their cursors
+            for next in line(last, *coord).into_iter().skip(1) {
+                path.push(next);
+                let n = path.len();
+                if pixel_perfect && n >= 3 && is_corner(path[n - 3], path[n - 2], path[n - 1]) {
+                    let corner = (path.remove(n - 2), *layer);
+                    points.retain(|point| *point != corner);
+                    if let Some((original, painted)) = self.done.remove(&corner) {
+                        restore(project, corner, original, painted)?;
+                    }
+                }
+                points.push((next, *layer));
+            }
+        }
+        self.paint(project, points, console)
+    }
+
+    //applies the tool at the pixels covered by the brush at the points
+    fn paint(
+        &mut self,
+        project: &mut Project,
+        points: Vec<(UCoord, u16)>,
+        console: &dyn Console,
+    ) -> ActionResult {
+        let dim = project.canvas.layers.dim();
+        let mut pixels = Vec::new();
+        for (point, layer) in points {
+            for coord in self.options.brush.pixels(point, dim) {
+                if !self.done.contains_key(&(coord, layer)) {
+                    let original = get_pixel(project, coord, layer)?;
+                    self.done
+                        .insert((coord, layer), (original.clone(), original));
+                    pixels.push((coord, layer));
+                }
+            }
+        }
+        let applied = self
+            .tool
+            .borrow_mut()
+            .apply(project, &pixels, &self.options, console);
+        for pixel in &pixels {
+            //wont fail because the pixels were just inserted
+            self.done.get_mut(pixel).unwrap().1 = get_pixel(project, pixel.0, pixel.1)?;
+        }
+        applied
+    }
+}
+
+//parses an ordered-dither pattern written as its level of sixteenths, e.g. 8/16 or 8, or as a
+//percentage rounded to the nearest level, e.g. 50%, none being no pattern
+fn parse_pattern(value: &str) -> Result<Option<Pattern>, String> {
+    if value == "none" {
+        return Ok(None);
+    }
+    let level = if let Some(percent) = value.strip_suffix('%') {
+        let percent = percent.parse::<u8>().map_err(|err| format!("{}", err))?;
+        ((percent as u16 * Pattern::LEVELS as u16 + 50) / 100) as u8
+    } else {
+        let level = value
+            .strip_suffix(&format!("/{}", Pattern::LEVELS))
+            .unwrap_or(value);
+        level.parse::<u8>().map_err(|err| format!("{}", err))?
+    };
+    Pattern::ordered(level)
+        .map(Some)
+        .map_err(|err| err.to_string())
+}
+
+//sets a pixel back to what it was before the tool acted on it, unless something else has changed
+//it since
+fn restore(
+    project: &mut Project,
+    pixel: (UCoord, u16),
+    original: OptionalTrueOrIndexed,
+    painted: OptionalTrueOrIndexed,
+) -> ActionResult {
+    if get_pixel(project, pixel.0, pixel.1)? == painted {
+        set_pixel(project, pixel.0, pixel.1, original)?;
+    }
+    Ok(())
+}
+
+//the cursors of the project in a stable order, that is kept when they all move alike
+fn sorted_cursors(project: &Project) -> Vec<(UCoord, u16)> {
+    let mut cursors = project.cursors().cloned().collect::<Vec<(UCoord, u16)>>();
+    cursors.sort_by_key(|(coord, layer)| (*layer, coord.x, coord.y));
+    cursors
+}
+
+//whether b is the corner of an L-shaped turn from a to c
+fn is_corner(a: UCoord, b: UCoord, c: UCoord) -> bool {
+    a.x.abs_diff(c.x) == 1
+        && a.y.abs_diff(c.y) == 1
+        && ((b.x == a.x && b.y == c.y) || (b.x == c.x && b.y == a.y))
+}
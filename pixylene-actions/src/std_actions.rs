@@ -1,6 +1,9 @@
 pub mod cursors;
 pub mod layer;
+pub mod motions;
+pub mod operators;
 pub mod palette;
 pub mod project;
 pub mod scene;
 pub mod shapes;
+pub mod tools;
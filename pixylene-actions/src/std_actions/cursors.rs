@@ -9,3 +9,27 @@ pub use reset_cursors::ResetCursors;
 
 mod go_to_single_cursor;
 pub use go_to_single_cursor::GoToSingleCursor;
+
+mod cursors_at_color;
+pub use cursors_at_color::CursorsAtColor;
+
+mod cursors_at_content;
+pub use cursors_at_content::CursorsAtContent;
+
+mod invert_cursors;
+pub use invert_cursors::InvertCursors;
+
+mod grow_cursors;
+pub use grow_cursors::GrowCursors;
+
+mod shrink_cursors;
+pub use shrink_cursors::ShrinkCursors;
+
+mod bound_cursors;
+pub use bound_cursors::BoundCursors;
+
+mod save_cursors;
+pub use save_cursors::SaveCursors;
+
+mod restore_cursors;
+pub use restore_cursors::RestoreCursors;
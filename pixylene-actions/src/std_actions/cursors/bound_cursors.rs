@@ -0,0 +1,12 @@
+use crate::{memento, Console};
+
+use libpixylene::project::Project;
+
+pub struct BoundCursors;
+
+impl memento::Action for BoundCursors {
+    fn perform(&mut self, project: &mut Project, _console: &dyn Console) -> memento::ActionResult {
+        project.bound_cursors();
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use crate::{memento, ActionError, Args, Console, Param, ParamType};
+
+use libpixylene::{
+    project::{LayersType, Project},
+    types::TruePixel,
+};
+
+/// Adds cursors at every pixel of the focused layer that is of the color given as input, either
+/// as a hex or as a palette index, the equipped color being used when nothing is given
+pub struct CursorsAtColor;
+
+impl CursorsAtColor {
+    const PARAMS: &'static [Param] = &[Param {
+        name: "color",
+        kind: ParamType::Color,
+        prompt: "color (#RRGGBB[AA] or palette index, equipped if empty): ",
+        optional: true,
+    }];
+}
+
+impl memento::Action for CursorsAtColor {
+    fn perform(&mut self, project: &mut Project, console: &dyn Console) -> memento::ActionResult {
+        self.perform_with(project, console, &Args::none(Self::PARAMS))
+    }
+
+    fn params(&self) -> &'static [Param] {
+        Self::PARAMS
+    }
+
+    fn perform_with(
+        &mut self,
+        project: &mut Project,
+        console: &dyn Console,
+        args: &Args,
+    ) -> memento::ActionResult {
+        let input: String = args.get(console, "color")?;
+        let input = input.as_str();
+        let layer = project.focus.1;
+
+        let added = if input.is_empty() {
+            match project.canvas.layers {
+                LayersType::True(_) => {
+                    let color = *project.canvas.palette.get_equipped();
+                    project.add_cursors_at_color(Some(color), layer)?
+                }
+                LayersType::Indexed(_) => {
+                    let index = project.canvas.palette.equipped();
+                    project.add_cursors_at_index(index, layer)?
+                }
+            }
+        } else if let Ok(index) = input.parse::<u8>() {
+            match project.canvas.layers {
+                LayersType::True(_) => {
+                    let color = *project.canvas.palette.get_color(index)?;
+                    project.add_cursors_at_color(Some(color), layer)?
+                }
+                LayersType::Indexed(_) => project.add_cursors_at_index(index, layer)?,
+            }
+        } else {
+            let color = TruePixel::from_hex(input)?;
+            project.add_cursors_at_color(Some(color), layer)?
+        };
+
+        if added == 0 {
+            return Err(ActionError::OperationError(Some(String::from(
+                "no pixels of that color were found",
+            ))));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,25 @@
+use crate::{memento, Console};
+
+use libpixylene::project::Project;
+
+/// Adds cursors at every non-empty pixel of the focused layer, or of every layer
+pub struct CursorsAtContent {
+    all_layers: bool,
+}
+
+impl CursorsAtContent {
+    pub fn new(all_layers: bool) -> Self {
+        CursorsAtContent { all_layers }
+    }
+}
+
+impl memento::Action for CursorsAtContent {
+    fn perform(&mut self, project: &mut Project, _console: &dyn Console) -> memento::ActionResult {
+        if self.all_layers {
+            _ = project.add_cursors_at_all_content();
+        } else {
+            _ = project.add_cursors_at_content(project.focus.1)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,12 @@
+use crate::{memento, Console};
+
+use libpixylene::project::Project;
+
+pub struct GrowCursors;
+
+impl memento::Action for GrowCursors {
+    fn perform(&mut self, project: &mut Project, _console: &dyn Console) -> memento::ActionResult {
+        project.grow_cursors();
+        Ok(())
+    }
+}
@@ -0,0 +1,12 @@
+use crate::{memento, Console};
+
+use libpixylene::project::Project;
+
+pub struct InvertCursors;
+
+impl memento::Action for InvertCursors {
+    fn perform(&mut self, project: &mut Project, _console: &dyn Console) -> memento::ActionResult {
+        project.invert_cursors(project.focus.1)?;
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{memento, ActionError, Console};
+use crate::{memento, ActionError, Args, Console, Param};
 
 use libpixylene::{
     project::Project,
@@ -11,18 +11,46 @@ pub struct MoveAllCursors {
 }
 
 impl MoveAllCursors {
+    const PARAMS: &'static [Param] = &[Param::COUNT];
+
     pub fn new(displacement: Coord) -> Self {
         MoveAllCursors { displacement }
     }
 }
 
 impl memento::Action for MoveAllCursors {
-    fn perform(&mut self, project: &mut Project, _console: &dyn Console) -> memento::ActionResult {
+    fn perform(&mut self, project: &mut Project, console: &dyn Console) -> memento::ActionResult {
+        self.perform_with(project, console, &Args::none(Self::PARAMS))
+    }
+
+    fn params(&self) -> &'static [Param] {
+        Self::PARAMS
+    }
+
+    fn changes_canvas(&self) -> bool {
+        false
+    }
+
+    fn is_step(&self) -> bool {
+        true
+    }
+
+    fn perform_with(
+        &mut self,
+        project: &mut Project,
+        console: &dyn Console,
+        args: &Args,
+    ) -> memento::ActionResult {
+        let count = i32::from(args.count(console)?);
+        let displacement = Coord {
+            x: self.displacement.x * count,
+            y: self.displacement.y * count,
+        };
         let mut new_cursors: HashMap<(UCoord, u16), ()> = HashMap::new();
         let dim = project.canvas.layers.dim();
         let cursors = project.cursors().map(|cursor| cursor.clone());
         for cursor in cursors {
-            let displaced_cursor = Coord::from(&cursor.0).add(self.displacement);
+            let displaced_cursor = Coord::from(&cursor.0).add(displacement);
             if displaced_cursor.x < 0
                 || displaced_cursor.y < 0
                 || displaced_cursor.x >= dim.x() as i32
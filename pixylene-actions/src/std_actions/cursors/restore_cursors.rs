@@ -0,0 +1,35 @@
+use crate::{memento, Args, Console, Param, ParamType};
+
+use libpixylene::project::Project;
+
+pub struct RestoreCursors;
+
+impl RestoreCursors {
+    const PARAMS: &'static [Param] = &[Param {
+        name: "register",
+        kind: ParamType::Text,
+        prompt: "register: ",
+        optional: false,
+    }];
+}
+
+impl memento::Action for RestoreCursors {
+    fn perform(&mut self, project: &mut Project, console: &dyn Console) -> memento::ActionResult {
+        self.perform_with(project, console, &Args::none(Self::PARAMS))
+    }
+
+    fn params(&self) -> &'static [Param] {
+        Self::PARAMS
+    }
+
+    fn perform_with(
+        &mut self,
+        project: &mut Project,
+        console: &dyn Console,
+        args: &Args,
+    ) -> memento::ActionResult {
+        let register: String = args.get(console, "register")?;
+        project.restore_cursors(&register)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{memento, Args, Console, Param, ParamType};
+
+use libpixylene::project::Project;
+
+pub struct SaveCursors;
+
+impl SaveCursors {
+    const PARAMS: &'static [Param] = &[Param {
+        name: "register",
+        kind: ParamType::Text,
+        prompt: "register: ",
+        optional: false,
+    }];
+}
+
+impl memento::Action for SaveCursors {
+    fn perform(&mut self, project: &mut Project, console: &dyn Console) -> memento::ActionResult {
+        self.perform_with(project, console, &Args::none(Self::PARAMS))
+    }
+
+    fn params(&self) -> &'static [Param] {
+        Self::PARAMS
+    }
+
+    fn perform_with(
+        &mut self,
+        project: &mut Project,
+        console: &dyn Console,
+        args: &Args,
+    ) -> memento::ActionResult {
+        //wont be empty because the register is not optional
+        let register: String = args.get(console, "register")?;
+        project.save_cursors(&register);
+        Ok(())
+    }
+}
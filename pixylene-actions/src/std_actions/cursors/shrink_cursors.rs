@@ -0,0 +1,12 @@
+use crate::{memento, Console};
+
+use libpixylene::project::Project;
+
+pub struct ShrinkCursors;
+
+impl memento::Action for ShrinkCursors {
+    fn perform(&mut self, project: &mut Project, _console: &dyn Console) -> memento::ActionResult {
+        project.shrink_cursors();
+        Ok(())
+    }
+}
@@ -1,38 +1,47 @@
-use crate::{memento, ActionError, Console};
+use crate::{memento, Args, Console, Param, ParamType};
 
 use libpixylene::project::{LayersType, Project};
 
 #[derive(Debug)]
 pub struct ChangeOpacity;
 
+impl ChangeOpacity {
+    const PARAMS: &'static [Param] = &[Param {
+        name: "opacity",
+        kind: ParamType::Fraction,
+        prompt: "opacity (0.0 to 1.0): ",
+        optional: false,
+    }];
+}
+
 impl memento::Action for ChangeOpacity {
     fn perform(&mut self, project: &mut Project, console: &dyn Console) -> memento::ActionResult {
-        use ActionError::{Discarded, InputError};
+        self.perform_with(project, console, &Args::none(Self::PARAMS))
+    }
 
-        let input = console.cmdin("opacity (0.0 to 1.0): ").ok_or(Discarded)?;
-        let opacity: Result<u8, ActionError> = match input.parse::<f32>() {
-            Ok(num) => {
-                if num >= 0.0 && num <= 1.0 {
-                    Ok((num * 255.0).round() as u8)
-                } else {
-                    Err(InputError(format!(
-                        "expecting between 0.0 and 1.0, found '{}'",
-                        num
-                    )))
-                }
-            }
-            Err(err) => Err(InputError(err.to_string())),
-        };
+    fn params(&self) -> &'static [Param] {
+        Self::PARAMS
+    }
+
+    fn perform_with(
+        &mut self,
+        project: &mut Project,
+        console: &dyn Console,
+        args: &Args,
+    ) -> memento::ActionResult {
+        //wont be out of range because the argument is validated as a fraction
+        let num: f32 = args.get(console, "opacity")?;
+        let opacity = (num * 255.0).round() as u8;
 
         //temporary solution (doing same exact thing shouldn't need match)
         //i dont think i have a solution for that honestly
         let layer = project.focus.1;
         match project.canvas.layers {
             LayersType::True(ref mut layers) => {
-                layers.get_layer_mut(layer)?.opacity = opacity?;
+                layers.get_layer_mut(layer)?.opacity = opacity;
             }
             LayersType::Indexed(ref mut layers) => {
-                layers.get_layer_mut(layer)?.opacity = opacity?;
+                layers.get_layer_mut(layer)?.opacity = opacity;
             }
         }
         Ok(())
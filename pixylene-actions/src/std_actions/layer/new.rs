@@ -1,4 +1,4 @@
-use crate::{memento, utils::OptionalTrueOrIndexed, ActionError, Console};
+use crate::{memento, utils::OptionalTrueOrIndexed, ActionError, Args, Console, Param, ParamType};
 
 use libpixylene::{
     project::{LayersType, Project},
@@ -8,46 +8,50 @@ use libpixylene::{
 #[derive(Debug)]
 pub struct New;
 
+impl New {
+    const PARAMS: &'static [Param] = &[Param {
+        name: "color",
+        kind: ParamType::Color,
+        prompt: "color (#hex or palette index, no color if empty): ",
+        optional: true,
+    }];
+}
+
 impl memento::Action for New {
     fn perform(&mut self, project: &mut Project, console: &dyn Console) -> memento::ActionResult {
-        use ActionError::{Discarded, InputError, InvalidCanvasType};
+        self.perform_with(project, console, &Args::none(Self::PARAMS))
+    }
+
+    fn params(&self) -> &'static [Param] {
+        Self::PARAMS
+    }
+
+    fn perform_with(
+        &mut self,
+        project: &mut Project,
+        console: &dyn Console,
+        args: &Args,
+    ) -> memento::ActionResult {
+        use ActionError::{InputError, InvalidCanvasType};
         use OptionalTrueOrIndexed::*;
 
+        let input: String = args.get(console, "color")?;
         let color: OptionalTrueOrIndexed = match &project.canvas.layers {
-            LayersType::True(_) => {
-                let input = console
-                    .cmdin("color (#hex or palette) (default: empty): ")
-                    .ok_or(Discarded)?;
-                True(match input.len() {
-                    0 => None,
-                    _ => match input.as_bytes()[0] {
-                        b'#' => Some(TruePixel::from_hex(&input)?),
-                        b'0'..=b'9' => match str::parse::<u8>(&input) {
-                            Ok(index) => Some(project.canvas.palette.get_color(index)?.clone()),
-                            Err(err) => {
-                                return Err(InputError(err.to_string()));
-                            }
-                        },
-                        _ => {
-                            return Err(InputError(format!("don't know how to parse '{}'", input)));
-                        }
-                    },
-                })
-            }
-            LayersType::Indexed(_) => {
-                let input = console
-                    .cmdin("color index (default: empty): ")
-                    .ok_or(Discarded)?;
-                Indexed(match input.len() {
-                    0 => None,
-                    _ => match str::parse::<u8>(&input) {
-                        Ok(index) => Some(IndexedPixel(index)),
-                        Err(err) => {
-                            return Err(InputError(err.to_string()));
-                        }
-                    },
-                })
-            }
+            LayersType::True(_) => True(match input.as_str() {
+                "" => None,
+                input => match input.parse::<u8>() {
+                    Ok(index) => Some(*project.canvas.palette.get_color(index)?),
+                    Err(_) => Some(TruePixel::from_hex(input)?),
+                },
+            }),
+            LayersType::Indexed(_) => Indexed(match input.as_str() {
+                "" => None,
+                input => Some(IndexedPixel(input.parse::<u8>().map_err(|_| {
+                    InputError(String::from(
+                        "expecting a palette index on an indexed canvas",
+                    ))
+                })?)),
+            }),
         };
 
         match (&mut project.canvas.layers, color.clone()) {
@@ -0,0 +1,37 @@
+use crate::utils::Direction;
+
+use libpixylene::types::{Coord, PCoord, UCoord};
+
+mod steps;
+pub use steps::Steps;
+
+mod to_edge;
+pub use to_edge::ToEdge;
+
+mod to_boundary;
+pub use to_boundary::ToBoundary;
+
+mod region;
+pub use region::Region;
+
+mod row;
+pub use row::Row;
+
+mod column;
+pub use column::Column;
+
+mod whole_layer;
+pub use whole_layer::WholeLayer;
+
+//the neighbouring coordinate in the direction, if it is inside the canvas
+fn step(coord: UCoord, direction: &Direction, dim: PCoord) -> Option<UCoord> {
+    let next = Coord::from(&coord).add(direction.unit());
+    if next.x < 0 || next.y < 0 || next.x >= dim.x() as i32 || next.y >= dim.y() as i32 {
+        None
+    } else {
+        Some(UCoord {
+            x: next.x as u16,
+            y: next.y as u16,
+        })
+    }
+}
@@ -0,0 +1,19 @@
+use crate::{memento::Motion, ActionError};
+
+use libpixylene::{project::Project, types::UCoord};
+
+/// An object of the whole column of the cursor
+pub struct Column;
+
+impl Motion for Column {
+    fn pixels(
+        &self,
+        project: &Project,
+        cursor: &(UCoord, u16),
+        _count: usize,
+    ) -> Result<Vec<UCoord>, ActionError> {
+        Ok((0..project.canvas.layers.dim().x())
+            .map(|x| UCoord { x, y: cursor.0.y })
+            .collect())
+    }
+}
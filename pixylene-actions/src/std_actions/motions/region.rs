@@ -0,0 +1,41 @@
+use super::step;
+use crate::{
+    memento::Motion,
+    utils::{get_pixel, Direction},
+    ActionError,
+};
+
+use libpixylene::{project::Project, types::UCoord};
+use std::collections::HashSet;
+
+/// An object of the pixels with the same color as the cursor that are connected to it, not
+/// diagonally
+pub struct Region;
+
+impl Motion for Region {
+    fn pixels(
+        &self,
+        project: &Project,
+        cursor: &(UCoord, u16),
+        _count: usize,
+    ) -> Result<Vec<UCoord>, ActionError> {
+        use Direction::*;
+
+        let dim = project.canvas.layers.dim();
+        let color = get_pixel(project, cursor.0, cursor.1)?;
+        let mut visited = HashSet::from([cursor.0]);
+        let mut pixels = vec![cursor.0];
+        let mut index = 0;
+        while index < pixels.len() {
+            for direction in [Up, Down, Left, Right] {
+                if let Some(next) = step(pixels[index], &direction, dim) {
+                    if visited.insert(next) && get_pixel(project, next, cursor.1)? == color {
+                        pixels.push(next);
+                    }
+                }
+            }
+            index += 1;
+        }
+        Ok(pixels)
+    }
+}
@@ -0,0 +1,19 @@
+use crate::{memento::Motion, ActionError};
+
+use libpixylene::{project::Project, types::UCoord};
+
+/// An object of the whole row of the cursor
+pub struct Row;
+
+impl Motion for Row {
+    fn pixels(
+        &self,
+        project: &Project,
+        cursor: &(UCoord, u16),
+        _count: usize,
+    ) -> Result<Vec<UCoord>, ActionError> {
+        Ok((0..project.canvas.layers.dim().y())
+            .map(|y| UCoord { x: cursor.0.x, y })
+            .collect())
+    }
+}
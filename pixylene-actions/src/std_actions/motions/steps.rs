@@ -0,0 +1,35 @@
+use super::step;
+use crate::{memento::Motion, utils::Direction, ActionError};
+
+use libpixylene::{project::Project, types::UCoord};
+
+/// A motion that covers `count` pixels in a direction starting at the cursor, stopping early at
+/// the edge of the canvas
+pub struct Steps {
+    direction: Direction,
+}
+
+impl Steps {
+    pub fn new(direction: Direction) -> Self {
+        Steps { direction }
+    }
+}
+
+impl Motion for Steps {
+    fn pixels(
+        &self,
+        project: &Project,
+        cursor: &(UCoord, u16),
+        count: usize,
+    ) -> Result<Vec<UCoord>, ActionError> {
+        let dim = project.canvas.layers.dim();
+        let mut pixels = vec![cursor.0];
+        while pixels.len() < count {
+            match step(pixels[pixels.len() - 1], &self.direction, dim) {
+                Some(next) => pixels.push(next),
+                None => break,
+            }
+        }
+        Ok(pixels)
+    }
+}
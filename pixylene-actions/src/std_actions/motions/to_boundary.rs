@@ -0,0 +1,46 @@
+use super::step;
+use crate::{
+    memento::Motion,
+    utils::{get_pixel, Direction},
+    ActionError,
+};
+
+use libpixylene::{project::Project, types::UCoord};
+
+/// A motion that covers the pixels from the cursor in a direction up to the `count`th change of
+/// color, excluding the pixel that changes, or up to the edge of the canvas
+pub struct ToBoundary {
+    direction: Direction,
+}
+
+impl ToBoundary {
+    pub fn new(direction: Direction) -> Self {
+        ToBoundary { direction }
+    }
+}
+
+impl Motion for ToBoundary {
+    fn pixels(
+        &self,
+        project: &Project,
+        cursor: &(UCoord, u16),
+        count: usize,
+    ) -> Result<Vec<UCoord>, ActionError> {
+        let dim = project.canvas.layers.dim();
+        let mut pixels = vec![cursor.0];
+        let mut color = get_pixel(project, cursor.0, cursor.1)?;
+        let mut boundaries = 0;
+        while let Some(next) = step(pixels[pixels.len() - 1], &self.direction, dim) {
+            let next_color = get_pixel(project, next, cursor.1)?;
+            if next_color != color {
+                boundaries += 1;
+                if boundaries >= count {
+                    break;
+                }
+                color = next_color;
+            }
+            pixels.push(next);
+        }
+        Ok(pixels)
+    }
+}
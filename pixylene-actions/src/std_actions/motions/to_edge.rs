@@ -0,0 +1,31 @@
+use super::step;
+use crate::{memento::Motion, utils::Direction, ActionError};
+
+use libpixylene::{project::Project, types::UCoord};
+
+/// A motion that covers the pixels from the cursor to the edge of the canvas in a direction
+pub struct ToEdge {
+    direction: Direction,
+}
+
+impl ToEdge {
+    pub fn new(direction: Direction) -> Self {
+        ToEdge { direction }
+    }
+}
+
+impl Motion for ToEdge {
+    fn pixels(
+        &self,
+        project: &Project,
+        cursor: &(UCoord, u16),
+        _count: usize,
+    ) -> Result<Vec<UCoord>, ActionError> {
+        let dim = project.canvas.layers.dim();
+        let mut pixels = vec![cursor.0];
+        while let Some(next) = step(pixels[pixels.len() - 1], &self.direction, dim) {
+            pixels.push(next);
+        }
+        Ok(pixels)
+    }
+}
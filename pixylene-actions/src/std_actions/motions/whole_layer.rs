@@ -0,0 +1,20 @@
+use crate::{memento::Motion, ActionError};
+
+use libpixylene::{project::Project, types::UCoord};
+
+/// An object of every pixel of the cursor's layer
+pub struct WholeLayer;
+
+impl Motion for WholeLayer {
+    fn pixels(
+        &self,
+        project: &Project,
+        _cursor: &(UCoord, u16),
+        _count: usize,
+    ) -> Result<Vec<UCoord>, ActionError> {
+        let dim = project.canvas.layers.dim();
+        Ok((0..dim.x())
+            .flat_map(|x| (0..dim.y()).map(move |y| UCoord { x, y }))
+            .collect())
+    }
+}
@@ -0,0 +1,26 @@
+use crate::utils::OptionalTrueOrIndexed;
+
+use libpixylene::types::Coord;
+use std::{cell::RefCell, rc::Rc};
+
+mod fill;
+pub use fill::Fill;
+
+mod erase;
+pub use erase::Erase;
+
+mod recolor;
+pub use recolor::Recolor;
+
+mod select;
+pub use select::Select;
+
+mod yank;
+pub use yank::Yank;
+
+mod paste;
+pub use paste::Paste;
+
+/// Pixels yanked by [`Yank`] to be pasted by [`Paste`], each at its offset from the top-left of
+/// the yanked pixels
+pub type Clipboard = Rc<RefCell<Vec<(Coord, OptionalTrueOrIndexed)>>>;
@@ -0,0 +1,31 @@
+use crate::{
+    memento::{ActionResult, Operator},
+    utils::{set_pixel, OptionalTrueOrIndexed},
+    Console,
+};
+
+use libpixylene::{
+    project::{LayersType, Project},
+    types::UCoord,
+};
+
+/// An operator that empties the pixels
+pub struct Erase;
+
+impl Operator for Erase {
+    fn operate(
+        &mut self,
+        project: &mut Project,
+        pixels: &[(UCoord, u16)],
+        _console: &dyn Console,
+    ) -> ActionResult {
+        let empty = match project.canvas.layers {
+            LayersType::True(_) => OptionalTrueOrIndexed::True(None),
+            LayersType::Indexed(_) => OptionalTrueOrIndexed::Indexed(None),
+        };
+        for pixel in pixels {
+            set_pixel(project, pixel.0, pixel.1, empty.clone())?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+use crate::{
+    memento::{Action, ActionResult, Operator},
+    std_actions::scene::Draw,
+    utils::equipped_pixel,
+    Console,
+};
+
+use libpixylene::{
+    project::Project,
+    types::{BlendMode, UCoord},
+};
+
+/// An operator that draws the equipped color over the pixels, blending it normally
+pub struct Fill;
+
+impl Operator for Fill {
+    fn operate(
+        &mut self,
+        project: &mut Project,
+        pixels: &[(UCoord, u16)],
+        console: &dyn Console,
+    ) -> ActionResult {
+        let color = equipped_pixel(project);
+        for pixel in pixels {
+            Draw::new(*pixel, color.clone(), BlendMode::Normal).perform(project, console)?;
+        }
+        Ok(())
+    }
+}
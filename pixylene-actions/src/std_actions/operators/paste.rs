@@ -0,0 +1,64 @@
+use super::Clipboard;
+use crate::{
+    memento,
+    utils::{set_pixel, OptionalTrueOrIndexed},
+    ActionError, Console,
+};
+
+use libpixylene::{
+    project::Project,
+    types::{Coord, UCoord},
+};
+
+/// An action that pastes the pixels of a [`Clipboard`] with their top-left at each cursor,
+/// skipping the empty ones & the ones that fall outside the canvas
+pub struct Paste {
+    clipboard: Clipboard,
+}
+
+impl Paste {
+    pub fn new(clipboard: Clipboard) -> Self {
+        Paste { clipboard }
+    }
+}
+
+impl memento::Action for Paste {
+    fn perform(&mut self, project: &mut Project, _console: &dyn Console) -> memento::ActionResult {
+        use OptionalTrueOrIndexed::*;
+
+        if self.clipboard.borrow().is_empty() {
+            return Err(ActionError::OperationError(Some(String::from(
+                "nothing has been yanked",
+            ))));
+        }
+        let dim = project.canvas.layers.dim();
+        let cursors = project
+            .cursors()
+            .map(|cursor| cursor.clone())
+            .collect::<Vec<(UCoord, u16)>>();
+        for cursor in cursors {
+            for (offset, pixel) in self.clipboard.borrow().iter() {
+                if let True(None) | Indexed(None) = pixel {
+                    continue;
+                }
+                let coord = Coord::from(&cursor.0).add(*offset);
+                if coord.x >= 0
+                    && coord.y >= 0
+                    && coord.x < dim.x() as i32
+                    && coord.y < dim.y() as i32
+                {
+                    set_pixel(
+                        project,
+                        UCoord {
+                            x: coord.x as u16,
+                            y: coord.y as u16,
+                        },
+                        cursor.1,
+                        pixel.clone(),
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
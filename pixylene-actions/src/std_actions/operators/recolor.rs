@@ -0,0 +1,31 @@
+use crate::{
+    memento::{ActionResult, Operator},
+    utils::{equipped_pixel, get_pixel, set_pixel, OptionalTrueOrIndexed},
+    Console,
+};
+
+use libpixylene::{project::Project, types::UCoord};
+
+/// An operator that replaces the color of the non-empty pixels with the equipped color, leaving
+/// the empty ones empty
+pub struct Recolor;
+
+impl Operator for Recolor {
+    fn operate(
+        &mut self,
+        project: &mut Project,
+        pixels: &[(UCoord, u16)],
+        _console: &dyn Console,
+    ) -> ActionResult {
+        use OptionalTrueOrIndexed::*;
+
+        let color = equipped_pixel(project);
+        for pixel in pixels {
+            match get_pixel(project, pixel.0, pixel.1)? {
+                True(None) | Indexed(None) => (),
+                _ => set_pixel(project, pixel.0, pixel.1, color.clone())?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,24 @@
+use crate::{
+    memento::{ActionResult, Operator},
+    Console,
+};
+
+use libpixylene::{project::Project, types::UCoord};
+
+/// An operator that replaces the cursors with ones at the pixels
+pub struct Select;
+
+impl Operator for Select {
+    fn operate(
+        &mut self,
+        project: &mut Project,
+        pixels: &[(UCoord, u16)],
+        _console: &dyn Console,
+    ) -> ActionResult {
+        _ = project.clear_cursors();
+        for pixel in pixels {
+            project.toggle_cursor_at(pixel)?;
+        }
+        Ok(())
+    }
+}
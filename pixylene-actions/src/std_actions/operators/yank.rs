@@ -0,0 +1,58 @@
+use super::Clipboard;
+use crate::{
+    memento::{ActionResult, Operator},
+    utils::get_pixel,
+    Console,
+};
+
+use libpixylene::{
+    project::Project,
+    types::{Coord, UCoord},
+};
+
+/// An operator that copies the pixels into a [`Clipboard`] without changing them
+pub struct Yank {
+    clipboard: Clipboard,
+}
+
+impl Yank {
+    pub fn new(clipboard: Clipboard) -> Self {
+        Yank { clipboard }
+    }
+}
+
+impl Operator for Yank {
+    fn operate(
+        &mut self,
+        project: &mut Project,
+        pixels: &[(UCoord, u16)],
+        _console: &dyn Console,
+    ) -> ActionResult {
+        let top_left = Coord {
+            x: pixels
+                .iter()
+                .map(|pixel| pixel.0.x)
+                .min()
+                .unwrap_or(0)
+                .into(),
+            y: pixels
+                .iter()
+                .map(|pixel| pixel.0.y)
+                .min()
+                .unwrap_or(0)
+                .into(),
+        };
+        let mut yanked = Vec::new();
+        for pixel in pixels {
+            yanked.push((
+                Coord::from(&pixel.0).add(Coord {
+                    x: -top_left.x,
+                    y: -top_left.y,
+                }),
+                get_pixel(project, pixel.0, pixel.1)?,
+            ));
+        }
+        *self.clipboard.borrow_mut() = yanked;
+        Ok(())
+    }
+}
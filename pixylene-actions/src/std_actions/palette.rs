@@ -1,2 +1,17 @@
 mod equip;
 pub use equip::Equip;
+
+mod equip_secondary;
+pub use equip_secondary::EquipSecondary;
+
+mod swap;
+pub use swap::Swap;
+
+mod eyedropper;
+pub use eyedropper::Eyedropper;
+
+mod add_ramp;
+pub use add_ramp::AddRamp;
+
+mod clear_ramps;
+pub use clear_ramps::ClearRamps;
@@ -0,0 +1,50 @@
+use crate::{memento, ActionError, Args, Console, Param, ParamType};
+
+use libpixylene::project::Project;
+
+/// An action that adds a ramp to the color palette, asking for its indexes from dark to light
+/// separated by spaces or commas
+pub struct AddRamp;
+
+impl AddRamp {
+    const PARAMS: &'static [Param] = &[Param {
+        name: "indexes",
+        kind: ParamType::Text,
+        prompt: "Ramp (indexes from dark to light): ",
+        optional: false,
+    }];
+}
+
+impl memento::Action for AddRamp {
+    fn perform(&mut self, project: &mut Project, console: &dyn Console) -> memento::ActionResult {
+        self.perform_with(project, console, &Args::none(Self::PARAMS))
+    }
+
+    fn params(&self) -> &'static [Param] {
+        Self::PARAMS
+    }
+
+    fn perform_with(
+        &mut self,
+        project: &mut Project,
+        console: &dyn Console,
+        args: &Args,
+    ) -> memento::ActionResult {
+        let indexes: String = args.get(console, "indexes")?;
+        let ramp = indexes
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|index| !index.is_empty())
+            .map(|index| {
+                index.parse::<u8>().map_err(|err| {
+                    ActionError::InputError(format!("invalid index '{}': {}", index, err))
+                })
+            })
+            .collect::<Result<Vec<u8>, ActionError>>()?;
+        if ramp.len() < 2 {
+            return Err(ActionError::InputError(String::from(
+                "a ramp needs at least 2 indexes",
+            )));
+        }
+        Ok(project.canvas.palette.add_ramp(ramp)?)
+    }
+}
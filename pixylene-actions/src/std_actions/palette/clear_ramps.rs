@@ -0,0 +1,13 @@
+use crate::{memento, Console};
+
+use libpixylene::project::Project;
+
+/// An action that removes all the ramps of the color palette
+pub struct ClearRamps;
+
+impl memento::Action for ClearRamps {
+    fn perform(&mut self, project: &mut Project, _console: &dyn Console) -> memento::ActionResult {
+        project.canvas.palette.clear_ramps();
+        Ok(())
+    }
+}
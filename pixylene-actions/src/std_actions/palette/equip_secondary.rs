@@ -0,0 +1,20 @@
+use crate::{memento, Console};
+
+use libpixylene::project::Project;
+
+/// An action that chooses the given index as the secondary color of the color palette
+pub struct EquipSecondary {
+    palette_index: u8,
+}
+
+impl EquipSecondary {
+    pub fn new(palette_index: u8) -> Self {
+        EquipSecondary { palette_index }
+    }
+}
+
+impl memento::Action for EquipSecondary {
+    fn perform(&mut self, project: &mut Project, _console: &dyn Console) -> memento::ActionResult {
+        Ok(project.canvas.palette.equip_secondary(self.palette_index)?)
+    }
+}
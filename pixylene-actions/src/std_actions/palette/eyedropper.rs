@@ -0,0 +1,110 @@
+use crate::{memento, ActionError, Console};
+
+use libpixylene::{
+    project::{LayersType, Project},
+    types::UCoord,
+};
+
+/// An action that equips the palette color of the pixel at a coordinate, or at the single cursor
+/// or the focus when no coordinate is given, sampled from the layer or from the merged canvas
+///
+/// A color of a true-color canvas that isn't in the palette is set at its first free index, or
+/// replaces the equipped color after asking if the palette is full.
+pub struct Eyedropper {
+    coord: Option<UCoord>,
+    merged: bool,
+}
+
+impl Eyedropper {
+    pub fn new(coord: Option<UCoord>, merged: bool) -> Self {
+        Eyedropper { coord, merged }
+    }
+}
+
+impl memento::Action for Eyedropper {
+    fn perform(&mut self, project: &mut Project, console: &dyn Console) -> memento::ActionResult {
+        use ActionError::{Discarded, OperationError};
+
+        let (coord, layer) = match self.coord {
+            Some(coord) => (coord, project.focus.1),
+            None if project.num_cursors() == 1 => *project.cursors().next().unwrap(), //wont
+            //fail
+            None => {
+                let dim = project.canvas.layers.dim();
+                let focus = project.focus.0;
+                if focus.x < 0
+                    || focus.y < 0
+                    || focus.x >= i32::from(dim.x())
+                    || focus.y >= i32::from(dim.y())
+                {
+                    return Err(OperationError(Some(String::from(
+                        "focus is outside the canvas",
+                    ))));
+                }
+                (
+                    UCoord {
+                        x: focus.x as u16,
+                        y: focus.y as u16,
+                    },
+                    project.focus.1,
+                )
+            }
+        };
+
+        let nothing = || OperationError(Some(String::from("no color to pick here")));
+        match &project.canvas.layers {
+            LayersType::Indexed(layers) => {
+                let pixel = if self.merged {
+                    project
+                        .canvas
+                        .merged_indexed_scene(None)
+                        .unwrap() //wont fail because canvas is indexed
+                        .get_pixel(coord)?
+                } else {
+                    layers.get_layer(layer)?.scene.get_pixel(coord)?
+                };
+                project.canvas.palette.equip(pixel.ok_or_else(nothing)?.0)?;
+            }
+            LayersType::True(layers) => {
+                let pixel = if self.merged {
+                    project.canvas.merged_true_scene(None).get_pixel(coord)?
+                } else {
+                    layers.get_layer(layer)?.scene.get_pixel(coord)?
+                }
+                .ok_or_else(nothing)?;
+
+                let palette = &mut project.canvas.palette;
+                let existing = palette
+                    .colors()
+                    .filter(|(_, color, _)| **color == pixel)
+                    .map(|(index, ..)| *index)
+                    .min();
+                let index = match existing {
+                    Some(index) => index,
+                    None => match (1..=u8::MAX).find(|index| palette.get_color(*index).is_err()) {
+                        Some(index) => {
+                            palette.set_color(index, &pixel.to_string())?;
+                            index
+                        }
+                        None => {
+                            let equipped = palette.equipped();
+                            let answer = console
+                                .cmdin(&format!(
+                                    "palette is full, replace color {} with {}? (y/n): ",
+                                    equipped, pixel
+                                ))
+                                .ok_or(Discarded)?;
+                            if !answer.trim().eq_ignore_ascii_case("y") {
+                                return Err(Discarded);
+                            }
+                            palette.set_color(equipped, &pixel.to_string())?;
+                            equipped
+                        }
+                    },
+                };
+                palette.equip(index)?;
+            }
+        }
+        Ok(())
+    }
+}
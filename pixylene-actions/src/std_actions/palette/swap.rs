@@ -0,0 +1,12 @@
+use crate::{memento, Console};
+
+use libpixylene::project::Project;
+
+/// An action that swaps the equipped & the secondary colors of the color palette
+pub struct Swap;
+
+impl memento::Action for Swap {
+    fn perform(&mut self, project: &mut Project, _console: &dyn Console) -> memento::ActionResult {
+        Ok(project.canvas.palette.swap()?)
+    }
+}
@@ -1,2 +1,5 @@
 mod multiplier;
 pub use multiplier::Multiplier;
+
+mod move_focus;
+pub use move_focus::MoveFocus;
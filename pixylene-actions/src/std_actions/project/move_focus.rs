@@ -0,0 +1,46 @@
+use crate::{memento, Args, Console, Param};
+use libpixylene::{project::Project, types::Coord};
+
+pub struct MoveFocus {
+    displacement: Coord,
+}
+
+impl MoveFocus {
+    const PARAMS: &'static [Param] = &[Param::COUNT];
+
+    pub fn new(displacement: Coord) -> Self {
+        MoveFocus { displacement }
+    }
+}
+
+impl memento::Action for MoveFocus {
+    fn perform(&mut self, project: &mut Project, console: &dyn Console) -> memento::ActionResult {
+        self.perform_with(project, console, &Args::none(Self::PARAMS))
+    }
+
+    fn params(&self) -> &'static [Param] {
+        Self::PARAMS
+    }
+
+    fn changes_canvas(&self) -> bool {
+        false
+    }
+
+    fn is_step(&self) -> bool {
+        true
+    }
+
+    fn perform_with(
+        &mut self,
+        project: &mut Project,
+        console: &dyn Console,
+        args: &Args,
+    ) -> memento::ActionResult {
+        let count = i32::from(args.count(console)?);
+        project.focus.0 = project.focus.0.add(Coord {
+            x: self.displacement.x * count,
+            y: self.displacement.y * count,
+        });
+        Ok(())
+    }
+}
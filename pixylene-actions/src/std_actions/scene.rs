@@ -3,3 +3,15 @@ pub use draw::Draw;
 
 mod pencil;
 pub use pencil::Pencil;
+
+mod eraser;
+pub use eraser::Eraser;
+
+mod gradient;
+pub use gradient::{Dithering, Gradient, GradientShape};
+
+mod replace;
+pub use replace::{Replace, ReplaceIn};
+
+mod swap_indexes;
+pub use swap_indexes::SwapIndexes;
@@ -0,0 +1,49 @@
+use crate::{
+    memento,
+    utils::{get_pixel, scale_opacity, set_pixel, OptionalTrueOrIndexed},
+    Console,
+};
+
+use libpixylene::{project::Project, types::UCoord};
+
+/// An action that empties the pixel at each cursor, or only takes the given amount out of 255 of
+/// its alpha away on true-color canvases, emptying it once none is left
+pub struct Eraser {
+    amount: u8,
+}
+
+impl Eraser {
+    pub fn new(amount: Option<u8>) -> Self {
+        Eraser {
+            amount: amount.unwrap_or(u8::MAX),
+        }
+    }
+
+    /// Erases the pixel at the coordinate of a layer, taking the amount out of 255 of its alpha away
+    /// on true-color canvases, & emptying it on indexed ones
+    ///
+    /// `Note`: This function may fail with the [`LayersError`][le] or [`SceneError`][se] error
+    /// variants only.
+    ///
+    /// [le]: crate::ActionError::LayersError
+    /// [se]: crate::ActionError::SceneError
+    pub fn erase(project: &mut Project, pixel: (UCoord, u16), amount: u8) -> memento::ActionResult {
+        use OptionalTrueOrIndexed::*;
+
+        let erased = match scale_opacity(get_pixel(project, pixel.0, pixel.1)?, u8::MAX - amount) {
+            True(color) => True(color.filter(|color| color.a > 0)),
+            Indexed(_) => Indexed(None),
+        };
+        set_pixel(project, pixel.0, pixel.1, erased)
+    }
+}
+
+impl memento::Action for Eraser {
+    fn perform(&mut self, project: &mut Project, _console: &dyn Console) -> memento::ActionResult {
+        let cursors = project.cursors().cloned().collect::<Vec<(UCoord, u16)>>();
+        for cursor in cursors {
+            Eraser::erase(project, cursor, self.amount)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,287 @@
+use super::Draw;
+use crate::{memento, utils::OptionalTrueOrIndexed, ActionError, Args, Console, Param, ParamType};
+
+use libpixylene::{
+    project::{LayersType, Pattern, Project},
+    types::{BlendMode, IndexedPixel, TruePixel, UCoord},
+};
+use std::{fmt, str::FromStr};
+
+/// The shape in which a [`Gradient`] goes from its first color to its second
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GradientShape {
+    /// Straight across at the given angle in degrees, 0 going from left to right & 90 from top to
+    /// bottom
+    Linear(u16),
+
+    /// Outwards from the center
+    Radial,
+}
+
+/// Parses a shape written as `radial` or `linear:<degrees>`, a bare `linear` going from left to
+/// right
+impl FromStr for GradientShape {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<GradientShape, String> {
+        match s.split_once(':') {
+            None if s == "radial" || s == "r" => Ok(GradientShape::Radial),
+            None if s == "linear" || s == "l" => Ok(GradientShape::Linear(0)),
+            Some(("linear" | "l", angle)) => angle
+                .parse::<u16>()
+                .map(|angle| GradientShape::Linear(angle % 360))
+                .map_err(|err| format!("invalid gradient angle '{}': {}", angle, err)),
+            _ => Err(format!(
+                "invalid gradient '{}', expecting radial or linear:<degrees>",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for GradientShape {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GradientShape::Linear(angle) => write!(f, "linear:{}", angle),
+            GradientShape::Radial => write!(f, "radial"),
+        }
+    }
+}
+
+/// How a [`Gradient`] mixes its two colors
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Dithering {
+    /// Ordered dithering with a Bayer matrix of the given size, drawing only the two palette
+    /// colors & keeping to the palette
+    Bayer(u8),
+
+    /// Smooth interpolation between the two colors, only possible on true-color canvases
+    Smooth,
+}
+
+impl Dithering {
+    //whether the pixel at the coordinate takes the second color at the fraction of the gradient,
+    //the threshold matrix being aligned to the canvas
+    fn second(size: u8, coord: UCoord, fraction: f32) -> bool {
+        let size = size as u16;
+        let threshold = (Pattern::bayer(size, coord.x % size, coord.y % size) as f32 + 0.5)
+            / (size * size) as f32;
+        fraction > threshold
+    }
+}
+
+/// Parses a dithering written as `smooth` or `bayer:<size>`, the size being 2, 4, 8 or 16 & a bare
+/// `bayer` being 4
+impl FromStr for Dithering {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Dithering, String> {
+        let size = match s.split_once(':') {
+            None if s == "smooth" => return Ok(Dithering::Smooth),
+            None if s == "bayer" => "4",
+            Some(("bayer", size)) => size,
+            _ => {
+                return Err(format!(
+                    "invalid dithering '{}', expecting smooth or bayer:<size>",
+                    s
+                ))
+            }
+        };
+        match size.parse::<u8>() {
+            Ok(size @ (2 | 4 | 8 | 16)) => Ok(Dithering::Bayer(size)),
+            _ => Err(format!(
+                "invalid bayer matrix size '{}', expecting 2, 4, 8 or 16",
+                size
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Dithering {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Dithering::Bayer(size) => write!(f, "bayer:{}", size),
+            Dithering::Smooth => write!(f, "smooth"),
+        }
+    }
+}
+
+/// An action that draws a gradient between two palette colors at the cursors, spanning the
+/// bounding box of the cursors of each layer, or over the whole of that bounding box if bounded
+///
+/// The gradient goes from the equipped color to the secondary one unless given other palette
+/// indices, & its shape is asked for if not given.
+pub struct Gradient {
+    shape: Option<GradientShape>,
+    dithering: Dithering,
+    colors: Option<(u8, u8)>,
+    bounded: bool,
+}
+
+impl Gradient {
+    const PARAMS: &'static [Param] = &[Param {
+        name: "shape",
+        kind: ParamType::Text,
+        prompt: "Gradient (linear:<degrees> or radial): ",
+        optional: false,
+    }];
+
+    pub fn new(shape: Option<GradientShape>, dithering: Dithering) -> Self {
+        Gradient {
+            shape,
+            dithering,
+            colors: None,
+            bounded: false,
+        }
+    }
+
+    /// Returns the gradient going between the colors at the given palette indices
+    pub fn with_colors(self, from: u8, to: u8) -> Self {
+        Gradient {
+            colors: Some((from, to)),
+            ..self
+        }
+    }
+
+    /// Returns the gradient drawing over the whole bounding box of the cursors of each layer
+    pub fn bounded(self) -> Self {
+        Gradient {
+            bounded: true,
+            ..self
+        }
+    }
+}
+
+impl memento::Action for Gradient {
+    fn perform(&mut self, project: &mut Project, console: &dyn Console) -> memento::ActionResult {
+        self.perform_with(project, console, &Args::none(Self::PARAMS))
+    }
+
+    fn params(&self) -> &'static [Param] {
+        Self::PARAMS
+    }
+
+    fn perform_with(
+        &mut self,
+        project: &mut Project,
+        console: &dyn Console,
+        args: &Args,
+    ) -> memento::ActionResult {
+        use OptionalTrueOrIndexed::*;
+
+        //a shape given as argument overrides the one the gradient was made with
+        let shape = match self.shape {
+            Some(shape) if !args.has("shape") => shape,
+            _ => args.get(console, "shape")?,
+        };
+        let (from, to) = match self.colors {
+            Some(colors) => colors,
+            None => (
+                project.canvas.palette.equipped(),
+                project.canvas.palette.secondary()?,
+            ),
+        };
+        let true_colors = (
+            *project.canvas.palette.get_color(from)?,
+            *project.canvas.palette.get_color(to)?,
+        );
+        let colors = match project.canvas.layers {
+            LayersType::True(_) => (True(Some(true_colors.0)), True(Some(true_colors.1))),
+            LayersType::Indexed(_) => {
+                if self.dithering == Dithering::Smooth {
+                    return Err(ActionError::InvalidCanvasType {
+                        expecting_indexed: false,
+                    });
+                }
+                (
+                    Indexed(Some(IndexedPixel(from))),
+                    Indexed(Some(IndexedPixel(to))),
+                )
+            }
+        };
+
+        let boxes = project.cursor_bounds();
+        let pixels: Vec<(UCoord, u16)> = if self.bounded {
+            boxes
+                .iter()
+                .flat_map(|(layer, (start, end))| {
+                    (start.x..=end.x).flat_map(move |x| {
+                        (start.y..=end.y).map(move |y| (UCoord { x, y }, *layer))
+                    })
+                })
+                .collect()
+        } else {
+            project.cursors().copied().collect()
+        };
+
+        for (coord, layer) in pixels {
+            let (start, end) = boxes[&layer];
+            let fraction = fraction(shape, coord, start, end);
+            let color = match self.dithering {
+                Dithering::Bayer(size) => {
+                    if Dithering::second(size, coord, fraction) {
+                        colors.1.clone()
+                    } else {
+                        colors.0.clone()
+                    }
+                }
+                Dithering::Smooth => {
+                    True(Some(interpolate(true_colors.0, true_colors.1, fraction)))
+                }
+            };
+            Draw::new((coord, layer), color, BlendMode::Normal).perform(project, console)?;
+        }
+        Ok(())
+    }
+}
+
+//how far along the gradient from start to end the coordinate is, from 0 to 1
+fn fraction(shape: GradientShape, coord: UCoord, start: UCoord, end: UCoord) -> f32 {
+    let corners = [
+        (start.x, start.y),
+        (start.x, end.y),
+        (end.x, start.y),
+        (end.x, end.y),
+    ];
+    let (value, max) = match shape {
+        GradientShape::Linear(angle) => {
+            let (sin, cos) = (angle as f32).to_radians().sin_cos();
+            //x goes down the rows & y along the columns
+            let project = |x: u16, y: u16| (x - start.x) as f32 * sin + (y - start.y) as f32 * cos;
+            let (min, max) = corners
+                .iter()
+                .map(|(x, y)| project(*x, *y))
+                .fold((f32::MAX, f32::MIN), |(min, max), p| {
+                    (min.min(p), max.max(p))
+                });
+            (project(coord.x, coord.y) - min, max - min)
+        }
+        GradientShape::Radial => {
+            let center = (
+                (start.x + end.x) as f32 / 2.0,
+                (start.y + end.y) as f32 / 2.0,
+            );
+            let distance = |x: u16, y: u16| (x as f32 - center.0).hypot(y as f32 - center.1);
+            (
+                distance(coord.x, coord.y),
+                distance(start.x, start.y).max(distance(end.x, end.y)),
+            )
+        }
+    };
+    if max > 0.0 {
+        (value / max).clamp(0.0, 1.0)
+    } else {
+        0.0
+    }
+}
+
+//the color at the fraction of the way from one color to the other
+fn interpolate(from: TruePixel, to: TruePixel, fraction: f32) -> TruePixel {
+    let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * fraction).round() as u8;
+    TruePixel {
+        r: mix(from.r, to.r),
+        g: mix(from.g, to.g),
+        b: mix(from.b, to.b),
+        a: mix(from.a, to.a),
+    }
+}
@@ -1,5 +1,9 @@
 use super::Draw;
-use crate::{memento, utils::OptionalTrueOrIndexed, Console};
+use crate::{
+    memento,
+    utils::{scale_opacity, OptionalTrueOrIndexed},
+    Console,
+};
 
 use libpixylene::{
     project::{LayersType, Project},
@@ -7,15 +11,42 @@ use libpixylene::{
 };
 
 /// An action that extends Draw to dynamically use the project's color at a specificed
-/// palette index and blend it normally with the existing color at each cursor, taking the equipped
-/// pencil if index not specified
+/// palette index and blend it with the existing color at each cursor, taking the equipped
+/// pencil if index not specified, or the secondary one if the pencil is secondary
+///
+/// Pencils blend normally & opaquely unless given other options, the opacity scaling the alpha of
+/// the color drawn on true-color canvases.
 pub struct Pencil {
     palette_index: Option<u8>,
+    secondary: bool,
+    opacity: u8,
+    blend_mode: BlendMode,
 }
 
 impl Pencil {
     pub fn new(palette_index: Option<u8>) -> Self {
-        Pencil { palette_index }
+        Pencil {
+            palette_index,
+            secondary: false,
+            opacity: 255,
+            blend_mode: BlendMode::Normal,
+        }
+    }
+
+    pub fn secondary() -> Self {
+        Pencil {
+            secondary: true,
+            ..Pencil::new(None)
+        }
+    }
+
+    /// Returns the pencil drawing with the given opacity & blend-mode
+    pub fn with_options(self, opacity: u8, blend_mode: BlendMode) -> Self {
+        Pencil {
+            opacity,
+            blend_mode,
+            ..self
+        }
     }
 }
 
@@ -50,26 +81,30 @@ impl memento::Action for Pencil {
     fn perform(&mut self, project: &mut Project, console: &dyn Console) -> memento::ActionResult {
         use OptionalTrueOrIndexed::*;
 
+        let palette = &project.canvas.palette;
+        let index = match (self.palette_index, self.secondary) {
+            (Some(index), _) => index,
+            (None, false) => palette.equipped(),
+            (None, true) => palette.secondary()?,
+        };
+        let pixel = match &project.canvas.layers {
+            LayersType::True(_) => {
+                let color = match (self.palette_index, self.secondary) {
+                    (Some(index), _) => *palette.get_color(index)?,
+                    (None, false) => *palette.get_equipped(),
+                    (None, true) => *palette.get_secondary()?,
+                };
+                scale_opacity(True(Some(color)), self.opacity)
+            }
+            LayersType::Indexed(_) => Indexed(Some(IndexedPixel(index))),
+        };
+
         let cursors = project
             .cursors()
             .map(|a| a.clone())
             .collect::<Vec<(UCoord, u16)>>();
         for cursor in cursors {
-            Draw::new(
-                cursor,
-                match &project.canvas.layers {
-                    LayersType::True(_) => True(Some(match &self.palette_index {
-                        Some(index) => *project.canvas.palette.get_color(*index)?,
-                        None => *project.canvas.palette.get_equipped(),
-                    })),
-                    LayersType::Indexed(_) => Indexed(Some(IndexedPixel(
-                        self.palette_index
-                            .unwrap_or(project.canvas.palette.equipped()),
-                    ))),
-                },
-                BlendMode::Normal,
-            )
-            .perform(project, console)?;
+            Draw::new(cursor, pixel.clone(), self.blend_mode).perform(project, console)?;
         }
         Ok(())
     }
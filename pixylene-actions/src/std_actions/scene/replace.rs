@@ -0,0 +1,176 @@
+use crate::{memento, ActionError, Args, Console, Param, ParamType};
+
+use libpixylene::{
+    project::{LayersType, PaletteError, Project, ReplaceScope},
+    types::TruePixel,
+};
+use std::{fmt, str::FromStr};
+
+/// Where a [`Replace`] or a [`SwapIndexes`](super::SwapIndexes) changes the pixels of the canvas
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReplaceIn {
+    /// Every layer of the canvas
+    All,
+
+    /// The focused layer
+    Layer,
+
+    /// The pixels at the cursors
+    Selection,
+}
+
+impl ReplaceIn {
+    /// Returns the pixels of the project's canvas that this covers
+    pub fn scope(&self, project: &Project) -> ReplaceScope {
+        match self {
+            ReplaceIn::All => ReplaceScope::All,
+            ReplaceIn::Layer => ReplaceScope::Layer(project.focus.1),
+            ReplaceIn::Selection => ReplaceScope::Pixels(project.cursors().copied().collect()),
+        }
+    }
+}
+
+impl FromStr for ReplaceIn {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<ReplaceIn, String> {
+        match s {
+            "all" => Ok(ReplaceIn::All),
+            "layer" => Ok(ReplaceIn::Layer),
+            "selection" => Ok(ReplaceIn::Selection),
+            _ => Err(format!(
+                "invalid scope '{}', expecting all, layer or selection",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ReplaceIn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReplaceIn::All => write!(f, "all"),
+            ReplaceIn::Layer => write!(f, "layer"),
+            ReplaceIn::Selection => write!(f, "selection"),
+        }
+    }
+}
+
+/// An action that replaces one color with another everywhere it covers, asking for both colors
+/// & defaulting to the equipped & the secondary ones
+///
+/// On true-color canvases, colors may be given as hex or as palette indices, & a tolerance is
+/// asked for so that colors differing by at most that much in every channel are replaced too.
+/// On indexed canvases, one palette index is remapped to the other.
+pub struct Replace {
+    within: ReplaceIn,
+}
+
+impl Replace {
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "from",
+            kind: ParamType::Color,
+            prompt: "replace color (#RRGGBB[AA] or palette index, equipped if empty): ",
+            optional: true,
+        },
+        Param {
+            name: "to",
+            kind: ParamType::Color,
+            prompt: "with color (#RRGGBB[AA] or palette index, secondary if empty): ",
+            optional: true,
+        },
+        Param {
+            name: "tolerance",
+            kind: ParamType::Integer { min: 0, max: 255 },
+            prompt: "tolerance (0-255, 0 if empty): ",
+            optional: true,
+        },
+    ];
+
+    pub fn new(within: ReplaceIn) -> Self {
+        Replace { within }
+    }
+}
+
+impl memento::Action for Replace {
+    fn perform(&mut self, project: &mut Project, console: &dyn Console) -> memento::ActionResult {
+        self.perform_with(project, console, &Args::none(Self::PARAMS))
+    }
+
+    fn params(&self) -> &'static [Param] {
+        Self::PARAMS
+    }
+
+    fn perform_with(
+        &mut self,
+        project: &mut Project,
+        console: &dyn Console,
+        args: &Args,
+    ) -> memento::ActionResult {
+        let scope = self.within.scope(project);
+        let (equipped, secondary) = (
+            Ok(project.canvas.palette.equipped()),
+            project.canvas.palette.secondary(),
+        );
+
+        let replaced = match project.canvas.layers {
+            LayersType::True(_) => {
+                let from = color_arg(args, console, project, "from", equipped)?;
+                let to = color_arg(args, console, project, "to", secondary)?;
+                let tolerance = args.get_or(console, "tolerance", 0)?;
+                project.canvas.replace_color(from, to, tolerance, &scope)?
+            }
+            LayersType::Indexed(_) => {
+                let from = index_arg(args, console, "from", equipped)?;
+                let to = index_arg(args, console, "to", secondary)?;
+                project.canvas.remap_index(from, to, &scope)?
+            }
+        };
+
+        if replaced == 0 {
+            return Err(ActionError::OperationError(Some(String::from(
+                "no pixels of that color were found",
+            ))));
+        }
+        Ok(())
+    }
+}
+
+//returns the color given as a hex or a palette index, or the color at the default index if left
+//empty, failing if there is no default index
+fn color_arg(
+    args: &Args,
+    console: &dyn Console,
+    project: &Project,
+    name: &str,
+    default: Result<u8, PaletteError>,
+) -> Result<TruePixel, ActionError> {
+    let input: String = args.get(console, name)?;
+    if input.is_empty() {
+        Ok(*project.canvas.palette.get_color(default?)?)
+    } else if let Ok(index) = input.parse::<u8>() {
+        Ok(*project.canvas.palette.get_color(index)?)
+    } else {
+        Ok(TruePixel::from_hex(&input)?)
+    }
+}
+
+//returns the palette index given, or the default if left empty, failing if there is no default
+fn index_arg(
+    args: &Args,
+    console: &dyn Console,
+    name: &str,
+    default: Result<u8, PaletteError>,
+) -> Result<u8, ActionError> {
+    let index = args.get_optional(console, name).map_err(|err| match err {
+        ActionError::InputError(_) => ActionError::InputError(String::from(
+            "expecting a palette index on an indexed canvas",
+        )),
+        err => err,
+    })?;
+    match index {
+        Some(index) => Ok(index),
+        None => Ok(default?),
+    }
+}
@@ -0,0 +1,67 @@
+use super::ReplaceIn;
+use crate::{memento, ActionError, Args, Console, Param, ParamType};
+
+use libpixylene::project::{LayersType, Project};
+
+/// An action that swaps the pixels of two palette indices with each other everywhere it covers on
+/// an indexed canvas, asking for both indices & defaulting to the equipped & the secondary ones
+pub struct SwapIndexes {
+    within: ReplaceIn,
+}
+
+impl SwapIndexes {
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "a",
+            kind: ParamType::Integer { min: 0, max: 255 },
+            prompt: "swap index (equipped if empty): ",
+            optional: true,
+        },
+        Param {
+            name: "b",
+            kind: ParamType::Integer { min: 0, max: 255 },
+            prompt: "with index (secondary if empty): ",
+            optional: true,
+        },
+    ];
+
+    pub fn new(within: ReplaceIn) -> Self {
+        SwapIndexes { within }
+    }
+}
+
+impl memento::Action for SwapIndexes {
+    fn perform(&mut self, project: &mut Project, console: &dyn Console) -> memento::ActionResult {
+        self.perform_with(project, console, &Args::none(Self::PARAMS))
+    }
+
+    fn params(&self) -> &'static [Param] {
+        Self::PARAMS
+    }
+
+    fn perform_with(
+        &mut self,
+        project: &mut Project,
+        console: &dyn Console,
+        args: &Args,
+    ) -> memento::ActionResult {
+        if let LayersType::True(_) = project.canvas.layers {
+            return Err(ActionError::InvalidCanvasType {
+                expecting_indexed: true,
+            });
+        }
+        let scope = self.within.scope(project);
+        let a = args.get_or(console, "a", project.canvas.palette.equipped())?;
+        let b = match args.get_optional(console, "b")? {
+            Some(b) => b,
+            None => project.canvas.palette.secondary()?,
+        };
+
+        if project.canvas.swap_indexes(a, b, &scope)? == 0 {
+            return Err(ActionError::OperationError(Some(String::from(
+                "no pixels of those indices were found",
+            ))));
+        }
+        Ok(())
+    }
+}
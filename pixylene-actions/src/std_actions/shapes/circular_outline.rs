@@ -1,5 +1,5 @@
 use super::super::scene::Draw;
-use crate::{memento, utils::OptionalTrueOrIndexed, ActionError, Console};
+use crate::{memento, utils::OptionalTrueOrIndexed, ActionError, Args, Console, Param, ParamType};
 
 use libpixylene::{
     project::{LayersType, Project},
@@ -11,6 +11,16 @@ pub struct CircularOutline {
 }
 
 impl CircularOutline {
+    const PARAMS: &'static [Param] = &[Param {
+        name: "radius",
+        kind: ParamType::Integer {
+            min: 1,
+            max: u16::MAX as i64,
+        },
+        prompt: "Radius: ",
+        optional: false,
+    }];
+
     pub fn new(palette_index: Option<u8>) -> Self {
         Self { palette_index }
     }
@@ -18,7 +28,20 @@ impl CircularOutline {
 
 impl memento::Action for CircularOutline {
     fn perform(&mut self, project: &mut Project, console: &dyn Console) -> memento::ActionResult {
-        use ActionError::{Discarded, InputError, OnlyNCursorsSupported, OperationError};
+        self.perform_with(project, console, &Args::none(Self::PARAMS))
+    }
+
+    fn params(&self) -> &'static [Param] {
+        Self::PARAMS
+    }
+
+    fn perform_with(
+        &mut self,
+        project: &mut Project,
+        console: &dyn Console,
+        args: &Args,
+    ) -> memento::ActionResult {
+        use ActionError::{OnlyNCursorsSupported, OperationError};
         use OptionalTrueOrIndexed::*;
 
         let num_cursors = project.num_cursors();
@@ -29,12 +52,7 @@ impl memento::Action for CircularOutline {
             ));
         }
 
-        let radius_input = console.cmdin("Radius: ").ok_or(Discarded)?;
-        let radius: u16 = str::parse::<u16>(&radius_input)
-            .map_err(|_| InputError(format!("invalid radius: {}", radius_input)))?;
-        if radius == 0 {
-            return Err(InputError("radius cannot be 0".to_owned()));
-        }
+        let radius: u16 = args.get(console, "radius")?;
 
         let (center, layer) = project
             .cursors()
@@ -0,0 +1,52 @@
+use crate::{
+    memento::{Action, ActionResult, ToolOptions},
+    std_actions::scene::Draw,
+    utils::{equipped_pixel, scale_opacity, secondary_pixel},
+    Console,
+};
+
+use libpixylene::{project::Project, types::UCoord};
+
+mod pencil;
+pub use pencil::Pencil;
+
+mod eraser;
+pub use eraser::Eraser;
+
+mod fill;
+pub use fill::Fill;
+
+mod line;
+pub use line::Line;
+
+mod shade;
+pub use shade::Shade;
+
+//draws the equipped color over the pixels with the opacity & blend mode of the options, in the
+//pattern of the options if any, the pixels it leaves off taking the secondary color if mixed with it
+fn draw(
+    project: &mut Project,
+    pixels: &[(UCoord, u16)],
+    options: &ToolOptions,
+    console: &dyn Console,
+) -> ActionResult {
+    let scale = |pixel| scale_opacity(pixel, options.opacity);
+    let color = scale(equipped_pixel(project));
+    //only needed, & so only required to be chosen, when mixing it into a pattern
+    let secondary = (options.mix_secondary && options.pattern.is_some())
+        .then(|| secondary_pixel(project))
+        .transpose()?
+        .map(scale);
+    for pixel in pixels {
+        let color = if !options.leaves_off(pixel.0) {
+            &color
+        } else {
+            match &secondary {
+                Some(secondary) => secondary,
+                None => continue,
+            }
+        };
+        Draw::new(*pixel, color.clone(), options.blend_mode).perform(project, console)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use crate::{
+    memento::{ActionResult, Tool, ToolOptions},
+    std_actions::scene,
+    Console,
+};
+
+use libpixylene::{project::Project, types::UCoord};
+
+/// A tool that empties pixels, or only takes away as much of their alpha as its opacity on
+/// true-color canvases, leaving the pixels that its pattern leaves off as they are
+pub struct Eraser;
+
+impl Tool for Eraser {
+    fn apply(
+        &mut self,
+        project: &mut Project,
+        pixels: &[(UCoord, u16)],
+        options: &ToolOptions,
+        _console: &dyn Console,
+    ) -> ActionResult {
+        for pixel in pixels {
+            if options.leaves_off(pixel.0) {
+                continue;
+            }
+            scene::Eraser::erase(project, *pixel, options.opacity)?;
+        }
+        Ok(())
+    }
+}
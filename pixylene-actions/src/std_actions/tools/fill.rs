@@ -0,0 +1,43 @@
+use crate::{
+    memento::{ActionResult, Motion, Tool, ToolOptions},
+    std_actions::motions::Region,
+    Console,
+};
+
+use libpixylene::{project::Project, types::UCoord};
+use std::collections::HashSet;
+
+/// A tool that draws the equipped color over the regions of same-colored pixels that it touches,
+/// filling every region only once per stroke
+#[derive(Default)]
+pub struct Fill {
+    filled: HashSet<(UCoord, u16)>,
+}
+
+impl Tool for Fill {
+    fn apply(
+        &mut self,
+        project: &mut Project,
+        pixels: &[(UCoord, u16)],
+        options: &ToolOptions,
+        console: &dyn Console,
+    ) -> ActionResult {
+        for pixel in pixels {
+            if self.filled.contains(pixel) {
+                continue;
+            }
+            let region = Region
+                .pixels(project, pixel, 1)?
+                .into_iter()
+                .map(|coord| (coord, pixel.1))
+                .collect::<Vec<(UCoord, u16)>>();
+            self.filled.extend(region.iter().cloned());
+            super::draw(project, &region, options, console)?;
+        }
+        Ok(())
+    }
+
+    fn begin(&mut self) {
+        self.filled.clear();
+    }
+}
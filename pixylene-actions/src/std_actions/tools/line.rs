@@ -0,0 +1,26 @@
+use crate::{
+    memento::{ActionResult, Tool, ToolOptions},
+    Console,
+};
+
+use libpixylene::{project::Project, types::UCoord};
+
+/// A tool that draws the equipped color along straight lines from where the pen was put down to
+/// the cursors
+pub struct Line;
+
+impl Tool for Line {
+    fn apply(
+        &mut self,
+        project: &mut Project,
+        pixels: &[(UCoord, u16)],
+        options: &ToolOptions,
+        console: &dyn Console,
+    ) -> ActionResult {
+        super::draw(project, pixels, options, console)
+    }
+
+    fn redraws(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,21 @@
+use crate::{
+    memento::{ActionResult, Tool, ToolOptions},
+    Console,
+};
+
+use libpixylene::{project::Project, types::UCoord};
+
+/// A tool that draws the equipped color
+pub struct Pencil;
+
+impl Tool for Pencil {
+    fn apply(
+        &mut self,
+        project: &mut Project,
+        pixels: &[(UCoord, u16)],
+        options: &ToolOptions,
+        console: &dyn Console,
+    ) -> ActionResult {
+        super::draw(project, pixels, options, console)
+    }
+}
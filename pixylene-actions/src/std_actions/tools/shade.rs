@@ -0,0 +1,70 @@
+use crate::{
+    memento::{ActionResult, Tool, ToolOptions},
+    utils::{get_pixel, set_pixel, OptionalTrueOrIndexed},
+    Console,
+};
+
+use libpixylene::{
+    project::Project,
+    types::{IndexedPixel, UCoord},
+};
+
+/// How much the lightness of a color that isn't in any ramp is shifted by a step of shading
+const LIGHTNESS_STEP: f32 = 0.1;
+
+/// A tool that shades pixels one step lighter or darker along the palette ramp that their color is
+/// in, leaving the pixels that aren't in any ramp as they are
+///
+/// On true-color canvases, colors that aren't in any ramp have their lightness shifted instead.
+pub struct Shade {
+    lighter: bool,
+}
+
+impl Shade {
+    pub fn new(lighter: bool) -> Self {
+        Shade { lighter }
+    }
+}
+
+impl Tool for Shade {
+    fn apply(
+        &mut self,
+        project: &mut Project,
+        pixels: &[(UCoord, u16)],
+        options: &ToolOptions,
+        _console: &dyn Console,
+    ) -> ActionResult {
+        use OptionalTrueOrIndexed::*;
+
+        for pixel in pixels {
+            if options.leaves_off(pixel.0) {
+                continue;
+            }
+            let palette = &project.canvas.palette;
+            let shaded = match get_pixel(project, pixel.0, pixel.1)? {
+                Indexed(Some(IndexedPixel(index))) => match palette.shade(index, self.lighter) {
+                    Some(index) => Indexed(Some(IndexedPixel(index))),
+                    None => continue,
+                },
+                True(Some(color)) => {
+                    let shaded = palette
+                        .colors()
+                        .filter(|(_, palette_color, _)| **palette_color == color)
+                        .filter_map(|(index, ..)| palette.shade(*index, self.lighter))
+                        .min();
+                    match shaded {
+                        Some(index) => True(Some(*palette.get_color(index)?)),
+                        None => True(Some(color.shade(if self.lighter {
+                            LIGHTNESS_STEP
+                        } else {
+                            -LIGHTNESS_STEP
+                        }))),
+                    }
+                }
+                True(None) | Indexed(None) => continue,
+            };
+            set_pixel(project, pixel.0, pixel.1, shaded)?;
+        }
+        Ok(())
+    }
+}
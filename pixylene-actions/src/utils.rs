@@ -1,4 +1,9 @@
-use libpixylene::types::{Coord, IndexedPixel, TruePixel};
+use crate::ActionError;
+
+use libpixylene::{
+    project::{LayersType, Project},
+    types::{Coord, IndexedPixel, TruePixel, UCoord},
+};
 
 pub enum AbsOrRel<A, B> {
     Abs(A),
@@ -27,14 +32,142 @@ impl Direction {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum TrueOrIndexed {
     True(TruePixel),
     Indexed(IndexedPixel),
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum OptionalTrueOrIndexed {
     True(Option<TruePixel>),
     Indexed(Option<IndexedPixel>),
 }
+
+/// Returns the pixel at the coordinate of a layer, of whichever type the canvas is
+///
+/// `Note`: This function may fail with the [`LayersError`][le] or [`SceneError`][se] error
+/// variants only.
+///
+/// [le]: ActionError::LayersError
+/// [se]: ActionError::SceneError
+pub fn get_pixel(
+    project: &Project,
+    coord: UCoord,
+    layer: u16,
+) -> Result<OptionalTrueOrIndexed, ActionError> {
+    Ok(match &project.canvas.layers {
+        LayersType::True(layers) => {
+            OptionalTrueOrIndexed::True(layers.get_layer(layer)?.scene.get_pixel(coord)?)
+        }
+        LayersType::Indexed(layers) => {
+            OptionalTrueOrIndexed::Indexed(layers.get_layer(layer)?.scene.get_pixel(coord)?)
+        }
+    })
+}
+
+/// Replaces the pixel at the coordinate of a layer, without blending
+///
+/// `Note`: This function may fail with the [`LayersError`][le], [`SceneError`][se] or
+/// [`InvalidCanvasType`][ict] error variants only.
+///
+/// [le]: ActionError::LayersError
+/// [se]: ActionError::SceneError
+/// [ict]: ActionError::InvalidCanvasType
+pub fn set_pixel(
+    project: &mut Project,
+    coord: UCoord,
+    layer: u16,
+    pixel: OptionalTrueOrIndexed,
+) -> Result<(), ActionError> {
+    match (&mut project.canvas.layers, pixel) {
+        (LayersType::True(layers), OptionalTrueOrIndexed::True(pixel)) => {
+            Ok(layers.get_layer_mut(layer)?.scene.set_pixel(coord, pixel)?)
+        }
+        (LayersType::Indexed(layers), OptionalTrueOrIndexed::Indexed(pixel)) => {
+            Ok(layers.get_layer_mut(layer)?.scene.set_pixel(coord, pixel)?)
+        }
+        (LayersType::True(_), OptionalTrueOrIndexed::Indexed(_)) => {
+            Err(ActionError::InvalidCanvasType {
+                expecting_indexed: false,
+            })
+        }
+        (LayersType::Indexed(_), OptionalTrueOrIndexed::True(_)) => {
+            Err(ActionError::InvalidCanvasType {
+                expecting_indexed: true,
+            })
+        }
+    }
+}
+
+/// Returns the pixel with its alpha scaled by an opacity, 255 keeping it as it is, leaving empty &
+/// indexed pixels as they are
+pub fn scale_opacity(pixel: OptionalTrueOrIndexed, opacity: u8) -> OptionalTrueOrIndexed {
+    match pixel {
+        OptionalTrueOrIndexed::True(Some(mut color)) => {
+            color.a = (color.a as u16 * opacity as u16 / 255) as u8;
+            OptionalTrueOrIndexed::True(Some(color))
+        }
+        pixel => pixel,
+    }
+}
+
+/// Returns the equipped color of the palette as a pixel of whichever type the canvas is
+pub fn equipped_pixel(project: &Project) -> OptionalTrueOrIndexed {
+    match &project.canvas.layers {
+        LayersType::True(_) => {
+            OptionalTrueOrIndexed::True(Some(*project.canvas.palette.get_equipped()))
+        }
+        LayersType::Indexed(_) => {
+            OptionalTrueOrIndexed::Indexed(Some(IndexedPixel(project.canvas.palette.equipped())))
+        }
+    }
+}
+
+/// Returns the secondary color of the palette as a pixel of whichever type the canvas is, failing
+/// if no secondary color has been chosen
+///
+/// `Note`: This method may fail with the [`PaletteError`](ActionError::PaletteError) error variant
+/// only.
+pub fn secondary_pixel(project: &Project) -> Result<OptionalTrueOrIndexed, ActionError> {
+    Ok(match &project.canvas.layers {
+        LayersType::True(_) => {
+            OptionalTrueOrIndexed::True(Some(*project.canvas.palette.get_secondary()?))
+        }
+        LayersType::Indexed(_) => {
+            OptionalTrueOrIndexed::Indexed(Some(IndexedPixel(project.canvas.palette.secondary()?)))
+        }
+    })
+}
+
+/// Returns the coordinates of the straight line between two coordinates, both included, stepping
+/// diagonally where needed
+pub fn line(from: UCoord, to: UCoord) -> Vec<UCoord> {
+    let (mut x, mut y) = (from.x as i32, from.y as i32);
+    let (dx, dy) = ((to.x as i32 - x).abs(), -(to.y as i32 - y).abs());
+    let (sx, sy) = (
+        if from.x < to.x { 1 } else { -1 },
+        if from.y < to.y { 1 } else { -1 },
+    );
+    let mut err = dx + dy;
+    let mut coords = Vec::new();
+    loop {
+        coords.push(UCoord {
+            x: x as u16,
+            y: y as u16,
+        });
+        if x == to.x as i32 && y == to.y as i32 {
+            break;
+        }
+        let double = 2 * err;
+        if double >= dy {
+            err += dy;
+            x += sx;
+        }
+        if double <= dx {
+            err += dx;
+            y += sy;
+        }
+    }
+    coords
+}
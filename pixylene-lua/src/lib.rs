@@ -55,6 +55,7 @@ impl LuaActionManager {
         let mut lua_ctx = mlua::Lua::new();
 
         Self::add_actions_table(&mut lua_ctx)?;
+        Self::add_motions_table(&mut lua_ctx)?;
         Self::add_types(&mut lua_ctx)?;
         Ok(Self(lua_ctx))
     }
@@ -65,9 +66,12 @@ impl LuaActionManager {
         Ok(())
     }
 
+    /// Invokes the `perform` function of the action, passing it the arguments as a sequence of
+    /// strings after the project & console
     pub fn invoke_action(
         &mut self,
         action_name: &str,
+        args: &[String],
         pixylene: Rc<RefCell<libpixylene::Pixylene>>,
         console: Rc<dyn pixylene_actions::Console>,
     ) -> Result<(), mlua::Error> {
@@ -76,15 +80,19 @@ impl LuaActionManager {
         let project_lua = Project(pixylene);
         self.0.globals().set("Project", project_lua).unwrap();
         self.0.globals().set("Console", Console(console)).unwrap();
+        self.0
+            .globals()
+            .set("Args", self.0.create_sequence_from(args.iter().cloned())?)?;
         self.0
             .load(format!(
-                "actions.{0}.perform(actions.{0}, Project, Console)",
+                "actions.{0}.perform(actions.{0}, Project, Console, Args)",
                 action_name
             ))
             .set_name("action invocation")
             .exec()?;
         self.0.globals().set("Project", Value::Nil)?;
         self.0.globals().set("Console", Value::Nil)?;
+        self.0.globals().set("Args", Value::Nil)?;
 
         Ok(())
     }
@@ -107,6 +115,44 @@ impl LuaActionManager {
         Ok(())
     }
 
+    /// Invokes the `pixels` function of the motion, passing it the project, the cursor's
+    /// coordinate & layer and the count, & returns the coordinates it covers
+    pub fn invoke_motion(
+        &mut self,
+        motion_name: &str,
+        pixylene: Rc<RefCell<libpixylene::Pixylene>>,
+        cursor: &(types::UCoord, u16),
+        count: usize,
+    ) -> Result<Vec<types::UCoord>, mlua::Error> {
+        use crate::values::{project::Project, types::UCoord};
+
+        let motion = self
+            .0
+            .globals()
+            .get::<_, Table>("motions")?
+            .get::<_, Table>(motion_name)?;
+        let pixels = motion
+            .get::<_, mlua::Function>("pixels")?
+            .call::<_, Vec<UCoord>>((
+                motion.clone(),
+                Project(pixylene),
+                UCoord(cursor.0),
+                cursor.1,
+                count,
+            ))?;
+        Ok(pixels.into_iter().map(|ucoord| ucoord.0).collect())
+    }
+
+    pub fn list_motions(&self) -> Vec<String> {
+        self.0
+            .globals()
+            .get::<_, Table>("motions")
+            .unwrap()
+            .pairs::<String, Table>()
+            .map(|pair| pair.unwrap().0)
+            .collect::<Vec<String>>()
+    }
+
     pub fn list_actions(&self) -> Vec<String> {
         self.0
             .globals()
@@ -122,6 +168,11 @@ impl LuaActionManager {
         Ok(())
     }
 
+    fn add_motions_table(lua_ctx: &mut mlua::Lua) -> Result<(), mlua::Error> {
+        lua_ctx.globals().set("motions", lua_ctx.create_table()?)?;
+        Ok(())
+    }
+
     fn add_types(lua_ctx: &mut mlua::Lua) -> Result<(), mlua::Error> {
         let coord;
         let ucoord;
@@ -211,7 +262,7 @@ mod tests {
         use crate::LuaActionManager;
         use libpixylene::{
             project::Palette,
-            types::{Coord, PCoord},
+            types::{Coord, PCoord, UCoord},
             PixyleneDefaults,
         };
         use pixylene_actions::{Console, LogType};
@@ -244,15 +295,30 @@ mod tests {
             &r#"
             actions['test'] = {
                 count = 1300135,
-                perform = function(self, project, console)
+                perform = function(self, project, console, args)
                     project.focus = { ['coord'] = C(-69,420), ['layer'] = 999 }
                     console:cmdout("haii")
+                    self.args = table.concat(args, ",")
+                end
+            }
+            motions['diagonal'] = {
+                pixels = function(self, project, cursor, layer, count)
+                    local pixels = {}
+                    for i = 0, count - 1 do
+                        pixels[#pixels + 1] = UC(cursor.x + i, cursor.y + i + layer)
+                    end
+                    return pixels
                 end
             }
             "#
             .to_owned(),
         )?;
-        lam.invoke_action("test", pixylene.clone(), console.clone())?;
+        lam.invoke_action(
+            "test",
+            &[String::from("4"), String::from("0.5")],
+            pixylene.clone(),
+            console.clone(),
+        )?;
         assert_eq!(
             pixylene.borrow().project.focus,
             (Coord { x: -69, y: 420 }, 999),
@@ -265,6 +331,49 @@ mod tests {
             console.clone(),
         )?;
         assert_eq!(console.0.borrow().clone(), String::from("| 1300135 |"));
+
+        lam.invoke(
+            &"Console:cmdout(actions['test'].args)".to_owned(),
+            pixylene.clone(),
+            console.clone(),
+        )?;
+        assert_eq!(console.0.borrow().clone(), String::from("4,0.5"));
+
+        assert_eq!(lam.list_motions(), vec![String::from("diagonal")]);
+        assert_eq!(
+            lam.invoke_motion("diagonal", pixylene.clone(), &(UCoord { x: 1, y: 2 }, 1), 2)?,
+            vec![UCoord { x: 1, y: 3 }, UCoord { x: 2, y: 4 }],
+        );
+
+        lam.invoke(
+            &"Project:save('a'); Console:cmdout(table.concat(Project.cursor_registers, ','))"
+                .to_owned(),
+            pixylene.clone(),
+            console.clone(),
+        )?;
+        assert_eq!(console.0.borrow().clone(), String::from("a"));
+
+        pixylene
+            .borrow_mut()
+            .project
+            .canvas
+            .layers
+            .to_true_mut()
+            .unwrap()
+            .new_layer(Some(
+                libpixylene::types::TruePixel::from_hex("#ff0000").unwrap(),
+            ))
+            .unwrap();
+        lam.invoke(
+            &r#"
+            local replaced = Project.canvas:replace(TP.hex('#fa0000'), TP.hex('#0000ff'), 8)
+            Console:cmdout(tostring(replaced))
+            "#
+            .to_owned(),
+            pixylene.clone(),
+            console.clone(),
+        )?;
+        assert_eq!(console.0.borrow().clone(), String::from("100"));
         Ok(())
     }
 }
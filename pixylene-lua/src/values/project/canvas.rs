@@ -8,6 +8,7 @@ use crate::{
 };
 
 use libpixylene::{project, types};
+use pixylene_actions::std_actions::scene::ReplaceIn;
 use std::sync::Arc;
 use tealr::{
     mlu::{
@@ -158,6 +159,102 @@ impl TealData for Canvas {
                 )))
             });
         }
+
+        //Lua interface to replace_color()
+        {
+            mlua_create_named_parameters!(
+                CanvasReplaceArgs with
+                    from: TruePixel,
+                    to: TruePixel,
+                    tolerance: Option<u8>,
+                    within: Option<String>,
+            );
+            methods.document(
+                "Replaces every pixel of a true Canvas that is of a color, or differs from it by \
+                at most the tolerance in every channel, with another color, within \"all\" layers \
+                (default), the focused \"layer\" or the \"selection\" of the Project, returning \
+                how many were replaced",
+            );
+            methods.add_method_mut("replace", |_, this, a: CanvasReplaceArgs| {
+                let (from, to, tolerance) = (a.from.0, a.to.0, a.tolerance.unwrap_or(0));
+                let within = a.within.clone();
+                this.0.do_mut(|canvas| {
+                    let scope = replace_scope(a.within, None)?;
+                    canvas
+                        .replace_color(from, to, tolerance, &scope)
+                        .map_err(|err| ExternalError(Arc::from(BOXED_ERROR(&err.to_string()))))
+                })(|mut pixylene, _| {
+                    let scope = replace_scope(within, Some(&pixylene.project))?;
+                    pixylene
+                        .project
+                        .canvas
+                        .replace_color(from, to, tolerance, &scope)
+                        .map_err(|err| ExternalError(Arc::from(BOXED_ERROR(&err.to_string()))))
+                })
+            });
+        }
+
+        //Lua interface to remap_index()
+        {
+            mlua_create_named_parameters!(
+                CanvasRemapArgs with
+                    from: u8,
+                    to: u8,
+                    within: Option<String>,
+            );
+            methods.document(
+                "Remaps every pixel of an indexed Canvas that is of one palette index to another, \
+                within \"all\" layers (default), the focused \"layer\" or the \"selection\" of \
+                the Project, returning how many were remapped",
+            );
+            methods.add_method_mut("remap", |_, this, a: CanvasRemapArgs| {
+                let within = a.within.clone();
+                this.0.do_mut(|canvas| {
+                    let scope = replace_scope(a.within, None)?;
+                    canvas
+                        .remap_index(a.from, a.to, &scope)
+                        .map_err(|err| ExternalError(Arc::from(BOXED_ERROR(&err.to_string()))))
+                })(|mut pixylene, _| {
+                    let scope = replace_scope(within, Some(&pixylene.project))?;
+                    pixylene
+                        .project
+                        .canvas
+                        .remap_index(a.from, a.to, &scope)
+                        .map_err(|err| ExternalError(Arc::from(BOXED_ERROR(&err.to_string()))))
+                })
+            });
+        }
+
+        //Lua interface to swap_indexes()
+        {
+            mlua_create_named_parameters!(
+                CanvasSwapArgs with
+                    a: u8,
+                    b: u8,
+                    within: Option<String>,
+            );
+            methods.document(
+                "Swaps the pixels of two palette indices of an indexed Canvas with each other, \
+                within \"all\" layers (default), the focused \"layer\" or the \"selection\" of \
+                the Project, returning how many were swapped",
+            );
+            methods.add_method_mut("swap", |_, this, a: CanvasSwapArgs| {
+                let within = a.within.clone();
+                this.0.do_mut(|canvas| {
+                    let scope = replace_scope(a.within, None)?;
+                    canvas
+                        .swap_indexes(a.a, a.b, &scope)
+                        .map_err(|err| ExternalError(Arc::from(BOXED_ERROR(&err.to_string()))))
+                })(|mut pixylene, _| {
+                    let scope = replace_scope(within, Some(&pixylene.project))?;
+                    pixylene
+                        .project
+                        .canvas
+                        .swap_indexes(a.a, a.b, &scope)
+                        .map_err(|err| ExternalError(Arc::from(BOXED_ERROR(&err.to_string()))))
+                })
+            });
+        }
     }
 
     fn add_fields<'lua, F: tealr::mlu::TealDataFields<'lua, Self>>(fields: &mut F) {
@@ -244,6 +341,28 @@ impl TealData for Canvas {
     }
 }
 
+//the pixels that a replacement within "all", "layer" or "selection" covers, only whole Canvases
+//being replaceable when they are not the Project's
+fn replace_scope(
+    within: Option<String>,
+    project: Option<&project::Project>,
+) -> mlua::Result<project::ReplaceScope> {
+    let within = match within {
+        Some(within) => within
+            .parse::<ReplaceIn>()
+            .map_err(|err| ExternalError(Arc::from(BOXED_ERROR(&err))))?,
+        None => ReplaceIn::All,
+    };
+    match (within, project) {
+        (ReplaceIn::All, _) => Ok(project::ReplaceScope::All),
+        (within, Some(project)) => Ok(within.scope(project)),
+        (within, None) => Err(ExternalError(Arc::from(BOXED_ERROR(&format!(
+            "cannot replace within the {} of a Canvas that is not the Project's",
+            within
+        ))))),
+    }
+}
+
 impl ToTypename for Canvas {
     fn to_typename() -> tealr::Type {
         tealr::Type::new_single("Canvas", tealr::KindOfType::External)
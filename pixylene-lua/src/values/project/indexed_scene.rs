@@ -20,7 +20,7 @@ use tealr::{
 /// Lua interface to libpixylene's [`Scene`][S] type over IndexedPixel
 ///
 /// `Note`: While libpixylene's [`Scene`][S] is a grid of optional pixels, this
-/// interface acts as a grid of just pixels.
+/// interface acts as a grid of just pixels, only emptying them when set to nil.
 ///
 /// [S]: project::Scene
 #[derive(Clone)]
@@ -95,17 +95,19 @@ impl TealData for IndexedScene {
             mlua_create_named_parameters!(
                 IndexedSceneSetPixelArgs with
                     coordinate: UCoord,
-                    new_pixel: IndexedPixel,
+                    new_pixel: Option<IndexedPixel>,
+            );
+            methods.document(
+                "Set the pixel at a particular coordinate on the scene, emptying it if nil",
             );
-            methods.document("Set the pixel at a particular coordinate on the scene");
             methods.add_method_mut("set", |_, this, a: IndexedSceneSetPixelArgs| {
                 this.0.do_mut::<_, _, CanvasMismatch<ContextExpired<
                     Result<(), project::SceneError>
                 >>>
-                    (|scene| Ok(Ok(scene.set_pixel(a.coordinate.0, Some(a.new_pixel.0)))))
+                    (|scene| Ok(Ok(scene.set_pixel(a.coordinate.0, a.new_pixel.map(|pixel| pixel.0)))))
                     (|mut pixylene, index| pixylene.project.canvas.layers.to_indexed_mut()
                         .map(|layers| layers.get_layer_mut(*index)
-                            .map(|layer| layer.scene.set_pixel(a.coordinate.0, Some(a.new_pixel.0)))
+                            .map(|layer| layer.scene.set_pixel(a.coordinate.0, a.new_pixel.map(|pixel| pixel.0)))
                             .map_err(|_| ())))
                     .map_err(|_| ExternalError(Arc::from(BOXED_ERROR(CANVAS_MISMATCH_INDEXED))))?
                     .map_err(|_| ExternalError(Arc::from(BOXED_ERROR(LAYER_GONE))))?
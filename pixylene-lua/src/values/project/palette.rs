@@ -138,6 +138,17 @@ impl TealData for Palette {
             });
         }
 
+        //Lua interface to Palette::swap()
+        {
+            methods.document("Swap the equipped & the secondary colors of the Palette");
+            methods.add_method_mut("swap", |_, this, _: ()| {
+                this.0.do_mut(|palette| palette.swap())(|mut pixylene, _| {
+                    pixylene.project.canvas.palette.swap()
+                })
+                .map_err(|err| ExternalError(Arc::from(BOXED_ERROR(&err.to_string()))))
+            });
+        }
+
         methods.generate_help();
     }
 
@@ -155,6 +166,37 @@ impl TealData for Palette {
             })
             .map_err(|err| ExternalError(Arc::from(BOXED_ERROR(&err.to_string()))))
         });
+
+        fields.document("the secondary index of the Palette, nil if none has been chosen");
+        fields.add_field_method_get("secondary", |_, this| {
+            Ok(this.0.do_imt(|palette| palette.get_secondary_index())(
+                |pixylene, _| pixylene.project.canvas.palette.get_secondary_index(),
+            ))
+        });
+        fields.add_field_method_set("secondary", |_, this, index: u8| {
+            this.0.do_mut(|palette| palette.equip_secondary(index))(|mut pixylene, _| {
+                pixylene.project.canvas.palette.equip_secondary(index)
+            })
+            .map_err(|err| ExternalError(Arc::from(BOXED_ERROR(&err.to_string()))))
+        });
+
+        fields.document("the recently equipped indexes of the Palette, most recent first");
+        fields.add_field_method_get("recent", |_, this| {
+            Ok(this.0.do_imt(|palette| {
+                palette
+                    .recent()
+                    .map(|(index, _)| *index)
+                    .collect::<Vec<u8>>()
+            })(|pixylene, _| {
+                pixylene
+                    .project
+                    .canvas
+                    .palette
+                    .recent()
+                    .map(|(index, _)| *index)
+                    .collect::<Vec<u8>>()
+            }))
+        });
     }
 }
 
@@ -2,7 +2,7 @@ use crate::{
     utils::BOXED_ERROR,
     values::{
         project::Canvas,
-        types::{Coord, PCoord, UCoord},
+        types::{Coord, PCoord, TruePixel, UCoord},
     },
     Context,
 };
@@ -101,6 +101,150 @@ impl TealData for Project {
             });
         }
 
+        //Lua interface to add_cursors_at_color()
+        {
+            mlua_create_named_parameters!(
+                ProjectAddAtColorArgs with
+                    color: Option<TruePixel>,
+                    layer: u16,
+            );
+            methods.document(
+                "Adds cursors at every pixel of the layer at given layer index that has the \
+                             provided color (nil for empty pixels), returning how many were added",
+            );
+            methods.add_method_mut("add_at_color", |_, this, a: ProjectAddAtColorArgs| {
+                this.0
+                    .borrow_mut()
+                    .project
+                    .add_cursors_at_color(a.color.map(|color| color.0), a.layer)
+                    .map_err(|err| ExternalError(Arc::from(BOXED_ERROR(&err.to_string()))))
+            });
+        }
+
+        //Lua interface to add_cursors_at_index()
+        {
+            mlua_create_named_parameters!(
+                ProjectAddAtIndexArgs with
+                    index: u8,
+                    layer: u16,
+            );
+            methods.document(
+                "Adds cursors at every pixel of the layer at given layer index that is of the \
+                             provided palette index, returning how many were added",
+            );
+            methods.add_method_mut("add_at_index", |_, this, a: ProjectAddAtIndexArgs| {
+                this.0
+                    .borrow_mut()
+                    .project
+                    .add_cursors_at_index(a.index, a.layer)
+                    .map_err(|err| ExternalError(Arc::from(BOXED_ERROR(&err.to_string()))))
+            });
+        }
+
+        //Lua interface to add_cursors_at_content()
+        {
+            mlua_create_named_parameters!(
+                ProjectAddAtContentArgs with
+                    layer: u16,
+            );
+            methods.document(
+                "Adds cursors at every non-empty pixel of the layer at given layer index, \
+                             returning how many were added",
+            );
+            methods.add_method_mut("add_at_content", |_, this, a: ProjectAddAtContentArgs| {
+                this.0
+                    .borrow_mut()
+                    .project
+                    .add_cursors_at_content(a.layer)
+                    .map_err(|err| ExternalError(Arc::from(BOXED_ERROR(&err.to_string()))))
+            });
+        }
+
+        {
+            methods.document(
+                "Adds cursors at every non-empty pixel of every layer, returning how many \
+                             were added",
+            );
+            methods.add_method_mut("add_at_all_content", |_, this, _: ()| {
+                Ok(this.0.borrow_mut().project.add_cursors_at_all_content())
+            });
+        }
+
+        //Lua interface to invert_cursors()
+        {
+            mlua_create_named_parameters!(
+                ProjectInvertArgs with
+                    layer: u16,
+            );
+            methods.document("Inverts the cursors on the layer at given layer index");
+            methods.add_method_mut("invert", |_, this, a: ProjectInvertArgs| {
+                this.0
+                    .borrow_mut()
+                    .project
+                    .invert_cursors(a.layer)
+                    .map_err(|err| ExternalError(Arc::from(BOXED_ERROR(&err.to_string()))))
+            });
+        }
+
+        {
+            methods.document("Grows the cursors on every layer by one pixel");
+            methods.add_method_mut("grow", |_, this, _: ()| {
+                this.0.borrow_mut().project.grow_cursors();
+                Ok(())
+            });
+        }
+
+        {
+            methods.document("Shrinks the cursors on every layer by one pixel");
+            methods.add_method_mut("shrink", |_, this, _: ()| {
+                this.0.borrow_mut().project.shrink_cursors();
+                Ok(())
+            });
+        }
+
+        {
+            methods.document(
+                "Adds cursors at every coordinate inside the bounding box of the cursors on \
+                             each layer",
+            );
+            methods.add_method_mut("bound", |_, this, _: ()| {
+                this.0.borrow_mut().project.bound_cursors();
+                Ok(())
+            });
+        }
+
+        //Lua interface to save_cursors()
+        {
+            mlua_create_named_parameters!(
+                ProjectSaveArgs with
+                    register: String,
+            );
+            methods.document("Saves the cursors into the register of the provided name");
+            methods.add_method_mut("save", |_, this, a: ProjectSaveArgs| {
+                this.0.borrow_mut().project.save_cursors(&a.register);
+                Ok(())
+            });
+        }
+
+        //Lua interface to restore_cursors()
+        {
+            mlua_create_named_parameters!(
+                ProjectRestoreArgs with
+                    register: String,
+            );
+            methods.document(
+                "Replaces the cursors with the ones saved into the register of the provided \
+                             name",
+            );
+            methods.add_method_mut("restore", |_, this, a: ProjectRestoreArgs| {
+                this.0
+                    .borrow_mut()
+                    .project
+                    .restore_cursors(&a.register)
+                    .map_err(|err| ExternalError(Arc::from(BOXED_ERROR(&err.to_string()))))
+            });
+        }
+
         methods.generate_help();
     }
 
@@ -164,6 +308,17 @@ impl TealData for Project {
             Ok(cursors)
         });
 
+        fields.document("the names of the registers that cursors have been saved into");
+        fields.add_field_method_get("cursor_registers", |_, this| {
+            Ok(this
+                .0
+                .borrow()
+                .project
+                .cursor_registers()
+                .cloned()
+                .collect::<Vec<String>>())
+        });
+
         fields.document(
             "table containing the focussed Layer ('layer') & focussed coordinate on \
                         the Layer ('coord') of the Project",
@@ -1,7 +1,7 @@
 use libpixylene::types::Coord;
 use pixylene_actions::{
-    memento::Action,
-    std_actions::{cursors, layer, palette, project, scene, shapes},
+    memento::{Action, Motion, Operator, Tool},
+    std_actions::{cursors, layer, motions, operators, palette, project, scene, shapes, tools},
     utils::Direction,
 };
 
@@ -13,6 +13,9 @@ use std::collections::HashMap;
 use std::rc::Rc;
 
 pub type ActionPtr = Rc<RefCell<dyn Action>>;
+pub type MotionPtr = Rc<dyn Motion>;
+pub type OperatorPtr = Rc<RefCell<dyn Operator>>;
+pub type ToolPtr = Rc<RefCell<dyn Tool>>;
 
 fn insert_native<T: Action + 'static>(
     action_map: &mut HashMap<String, Rc<RefCell<dyn Action>>>,
@@ -30,6 +33,10 @@ pub fn add_my_native_actions(amp: &mut HashMap<String, ActionPtr>) {
     for i in 1..9 {
         insert_native(amp, &format!("pencil{}", i), scene::Pencil::new(Some(i)));
     }
+    insert_native(amp, "pencil_secondary", scene::Pencil::secondary());
+
+    //Erasers
+    insert_native(amp, "eraser", scene::Eraser::new(None));
 
     //Cursors
     insert_native(
@@ -73,6 +80,15 @@ pub fn add_my_native_actions(amp: &mut HashMap<String, ActionPtr>) {
         cursors::DuplicateCursors::new(Direction::Right, 1),
     );
     insert_native(amp, "cursors_reset", cursors::ResetCursors);
+    insert_native(amp, "cursors_color", cursors::CursorsAtColor);
+    insert_native(amp, "cursors_layer", cursors::CursorsAtContent::new(false));
+    insert_native(amp, "cursors_all", cursors::CursorsAtContent::new(true));
+    insert_native(amp, "cursors_invert", cursors::InvertCursors);
+    insert_native(amp, "cursors_grow", cursors::GrowCursors);
+    insert_native(amp, "cursors_shrink", cursors::ShrinkCursors);
+    insert_native(amp, "cursors_bound", cursors::BoundCursors);
+    insert_native(amp, "cursors_save", cursors::SaveCursors);
+    insert_native(amp, "cursors_restore", cursors::RestoreCursors);
 
     //Layer
     insert_native(amp, "layer_new", layer::New);
@@ -104,14 +120,185 @@ pub fn add_my_native_actions(amp: &mut HashMap<String, ActionPtr>) {
     //View
     insert_native(amp, "zoomin", project::Multiplier::new(1));
     insert_native(amp, "zoomout", project::Multiplier::new(-1));
+    insert_native(
+        amp,
+        "focus_up",
+        project::MoveFocus::new(Coord { x: -1, y: 0 }),
+    );
+    insert_native(
+        amp,
+        "focus_left",
+        project::MoveFocus::new(Coord { x: 0, y: -1 }),
+    );
+    insert_native(
+        amp,
+        "focus_down",
+        project::MoveFocus::new(Coord { x: 1, y: 0 }),
+    );
+    insert_native(
+        amp,
+        "focus_right",
+        project::MoveFocus::new(Coord { x: 0, y: 1 }),
+    );
 
     //Palette
     for i in 1..9 {
         insert_native(amp, &format!("equip{}", i), palette::Equip::new(i));
+        insert_native(
+            amp,
+            &format!("secondary{}", i),
+            palette::EquipSecondary::new(i),
+        );
     }
+    insert_native(amp, "swap", palette::Swap);
+    insert_native(amp, "ramp_add", palette::AddRamp);
+    insert_native(amp, "ramps_clear", palette::ClearRamps);
 
     //Shapes
     insert_native(amp, "circularoutline", shapes::CircularOutline::new(None));
+
+    //Gradients, between the equipped & secondary colors
+    insert_native(
+        amp,
+        "gradient",
+        scene::Gradient::new(None, scene::Dithering::Bayer(4)),
+    );
+    insert_native(
+        amp,
+        "gradient_box",
+        scene::Gradient::new(None, scene::Dithering::Bayer(4)).bounded(),
+    );
+    insert_native(
+        amp,
+        "gradient_smooth",
+        scene::Gradient::new(None, scene::Dithering::Smooth),
+    );
+
+    //Replacing colors, or swapping palette indices, across all layers, the focused one or the
+    //cursors
+    for (suffix, within) in [
+        ("", scene::ReplaceIn::All),
+        ("_layer", scene::ReplaceIn::Layer),
+        ("_selection", scene::ReplaceIn::Selection),
+    ] {
+        insert_native(
+            amp,
+            &format!("replace{}", suffix),
+            scene::Replace::new(within),
+        );
+        insert_native(
+            amp,
+            &format!("swap_indexes{}", suffix),
+            scene::SwapIndexes::new(within),
+        );
+    }
+}
+
+pub fn add_my_motions(mmp: &mut HashMap<String, MotionPtr>) {
+    /* Insert Native Motions Here */
+
+    //Steps
+    mmp.insert(
+        "left".to_owned(),
+        Rc::new(motions::Steps::new(Direction::Left)),
+    );
+    mmp.insert(
+        "down".to_owned(),
+        Rc::new(motions::Steps::new(Direction::Down)),
+    );
+    mmp.insert("up".to_owned(), Rc::new(motions::Steps::new(Direction::Up)));
+    mmp.insert(
+        "right".to_owned(),
+        Rc::new(motions::Steps::new(Direction::Right)),
+    );
+
+    //Edges of canvas
+    mmp.insert(
+        "edge_left".to_owned(),
+        Rc::new(motions::ToEdge::new(Direction::Left)),
+    );
+    mmp.insert(
+        "edge_down".to_owned(),
+        Rc::new(motions::ToEdge::new(Direction::Down)),
+    );
+    mmp.insert(
+        "edge_up".to_owned(),
+        Rc::new(motions::ToEdge::new(Direction::Up)),
+    );
+    mmp.insert(
+        "edge_right".to_owned(),
+        Rc::new(motions::ToEdge::new(Direction::Right)),
+    );
+
+    //Color boundaries
+    mmp.insert(
+        "boundary_left".to_owned(),
+        Rc::new(motions::ToBoundary::new(Direction::Left)),
+    );
+    mmp.insert(
+        "boundary_down".to_owned(),
+        Rc::new(motions::ToBoundary::new(Direction::Down)),
+    );
+    mmp.insert(
+        "boundary_up".to_owned(),
+        Rc::new(motions::ToBoundary::new(Direction::Up)),
+    );
+    mmp.insert(
+        "boundary_right".to_owned(),
+        Rc::new(motions::ToBoundary::new(Direction::Right)),
+    );
+
+    //Objects
+    mmp.insert("region".to_owned(), Rc::new(motions::Region));
+    mmp.insert("row".to_owned(), Rc::new(motions::Row));
+    mmp.insert("column".to_owned(), Rc::new(motions::Column));
+    mmp.insert("layer".to_owned(), Rc::new(motions::WholeLayer));
+}
+
+pub fn add_my_operators(
+    omp: &mut HashMap<String, OperatorPtr>,
+    amp: &mut HashMap<String, ActionPtr>,
+) {
+    /* Insert Native Operators Here */
+
+    omp.insert("fill".to_owned(), Rc::new(RefCell::new(operators::Fill)));
+    omp.insert("erase".to_owned(), Rc::new(RefCell::new(operators::Erase)));
+    omp.insert(
+        "recolor".to_owned(),
+        Rc::new(RefCell::new(operators::Recolor)),
+    );
+    omp.insert(
+        "select".to_owned(),
+        Rc::new(RefCell::new(operators::Select)),
+    );
+
+    //yanked pixels are pasted with an action
+    let clipboard = operators::Clipboard::default();
+    omp.insert(
+        "yank".to_owned(),
+        Rc::new(RefCell::new(operators::Yank::new(clipboard.clone()))),
+    );
+    insert_native(amp, "paste", operators::Paste::new(clipboard));
+}
+
+pub fn add_my_tools(tmp: &mut HashMap<String, ToolPtr>) {
+    /* Insert Native Tools Here */
+
+    tmp.insert("pencil".to_owned(), Rc::new(RefCell::new(tools::Pencil)));
+    tmp.insert("eraser".to_owned(), Rc::new(RefCell::new(tools::Eraser)));
+    tmp.insert(
+        "fill".to_owned(),
+        Rc::new(RefCell::new(tools::Fill::default())),
+    );
+    tmp.insert("line".to_owned(), Rc::new(RefCell::new(tools::Line)));
+    tmp.insert(
+        "lighten".to_owned(),
+        Rc::new(RefCell::new(tools::Shade::new(true))),
+    );
+    tmp.insert(
+        "darken".to_owned(),
+        Rc::new(RefCell::new(tools::Shade::new(false))),
+    );
 }
 
 #[cfg(feature = "lua")]
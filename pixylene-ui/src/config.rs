@@ -1,10 +1,16 @@
-use crate::ui::{Key, KeyMap, ReqUiFnMap, UiFn};
+use crate::ui::{Key, KeyMap, KeySeq, ReqUiFnMap, UiFn};
 
 use crossterm::event::{KeyCode::*, KeyEvent as K, KeyModifiers as KM};
 use dirs::config_dir;
-use libpixylene::{project::Palette, types::UCoord, PixyleneDefaults};
+use libpixylene::{
+    file::SceneEncoding,
+    project::Palette,
+    types::{BlendMode, UCoord},
+    PixyleneDefaults,
+};
+use pixylene_actions::memento::{HistoryLimits, JumpList};
 use serde::Deserialize;
-use std::{collections::HashMap, fs::read_to_string};
+use std::{collections::HashMap, fs::read_to_string, time::Duration};
 use toml::{de::Error, from_str};
 
 /// Configuration (parsed from Config Syntax and evaluated for logic errors)
@@ -18,6 +24,30 @@ pub struct Config {
     pub required_keys: ReqUiFnMap,
     pub every_frame: Vec<UiFn>,
     pub padding: u8,
+    pub compress_projects: bool,
+    pub canvas_encoding: SceneEncoding,
+    pub history_limits: HistoryLimits,
+    pub save_undo_history: bool,
+    pub max_saved_undo_memory: Option<usize>,
+    pub jumplist: bool,
+    pub max_jumps: Option<usize>,
+    pub disable_counts: Vec<String>,
+    pub key_timeout: Duration,
+    pub eyedropper_merged: bool,
+    pub pencils: HashMap<String, PencilConfig>,
+    pub erasers: HashMap<String, Option<u8>>,
+
+    /// Problems with the config that didn't stop it from loading, e.g. overridden default keys
+    pub warnings: Vec<String>,
+}
+
+/// A pencil defined in the config, added as a native action by its name
+#[derive(Debug, Clone)]
+pub struct PencilConfig {
+    pub index: Option<u8>,
+    pub secondary: bool,
+    pub opacity: u8,
+    pub blend_mode: BlendMode,
 }
 
 impl Config {
@@ -27,20 +57,41 @@ impl Config {
             Some(c) => Some(c?),
             None => None,
         };
+        Config::from_syntax(config)
+    }
 
+    /// Creates the Config from a deserialized config.toml, or from the defaults if there is none
+    pub fn from_syntax(config: Option<ConfigSyntax>) -> Result<Self, String> {
         let ConfigSyntax {
             mut required_keys,
             mut default_namespace,
             mut every_frame,
             mut keymap_show_command_names,
             mut padding,
+            mut compress_projects,
+            mut canvas_encoding,
+            mut max_undo_steps,
+            mut max_undo_memory,
+            mut save_undo_history,
+            mut max_saved_undo_memory,
+            mut disable_jumplist,
+            mut max_jumps,
+            mut disable_counts,
+            mut key_timeout,
+            mut eyedropper_merged,
+            mut pencils,
+            mut erasers,
 
             mut defaults,
             keys,
             ..
         } = ConfigSyntax::default();
 
-        let (keymap, possible_namespaces) = get_keys_from_config(&config, keys);
+        let leader = match config.as_ref().and_then(|config| config.leader.clone()) {
+            Some(leader) => leader,
+            None => K::new(Char(' '), KM::empty()).into(),
+        };
+        let (keymap, possible_namespaces, warnings) = get_keys_from_config(&config, keys, &leader)?;
 
         if let Some(config) = config {
             required_keys = config.required_keys;
@@ -48,10 +99,24 @@ impl Config {
             every_frame = config.every_frame;
             keymap_show_command_names = config.keymap_show_command_names;
             padding = config.padding;
+            compress_projects = config.compress_projects;
+            canvas_encoding = config.canvas_encoding;
+            max_undo_steps = config.max_undo_steps;
+            max_undo_memory = config.max_undo_memory;
+            save_undo_history = config.save_undo_history;
+            max_saved_undo_memory = config.max_saved_undo_memory;
+            disable_jumplist = config.disable_jumplist;
+            max_jumps = config.max_jumps;
+            disable_counts = config.disable_counts;
+            key_timeout = config.key_timeout;
+            eyedropper_merged = config.eyedropper_merged;
+            pencils = config.pencils;
+            erasers = config.erasers;
             defaults = config.defaults;
         }
 
         let defaults = parse_defaults(defaults)?;
+        let pencils = parse_pencils(pencils)?;
 
         Ok(Self {
             defaults,
@@ -62,6 +127,26 @@ impl Config {
             required_keys,
             every_frame,
             padding,
+            compress_projects,
+            canvas_encoding,
+            history_limits: HistoryLimits {
+                max_steps: max_undo_steps,
+                max_memory: max_undo_memory,
+            },
+            save_undo_history,
+            max_saved_undo_memory,
+            jumplist: !disable_jumplist,
+            max_jumps: Some(max_jumps.unwrap_or(JumpList::DEFAULT_MAX_JUMPS)),
+            disable_counts,
+            //vim's default timeoutlen
+            key_timeout: Duration::from_millis(key_timeout.unwrap_or(1000)),
+            eyedropper_merged,
+            pencils,
+            erasers: erasers
+                .into_iter()
+                .map(|(name, entry)| (name, entry.amount))
+                .collect(),
+            warnings,
         })
     }
 }
@@ -76,7 +161,7 @@ pub struct KeyXUiFnEntry {
     pub f: Vec<UiFn>,
 }
 
-type KeyXUiFnEntries = HashMap<Key, Vec<UiFn>>;
+type KeyXUiFnEntries = HashMap<KeySeq, Vec<UiFn>>;
 type NamespaceXKeysEntries = HashMap<String, KeyXUiFnEntries>;
 
 #[derive(Debug, Deserialize)]
@@ -91,11 +176,31 @@ pub struct PaletteColorEntry {
     pub c: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct PencilEntry {
+    #[serde(default)]
+    pub index: Option<u8>,
+    #[serde(default)]
+    pub secondary: bool,
+    #[serde(default)]
+    pub opacity: Option<u8>,
+    #[serde(default)]
+    pub blend: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EraserEntry {
+    #[serde(default)]
+    pub amount: Option<u8>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct PixyleneDefaultsConfig {
     pub dimensions: UCoordEntry,
     pub repeat: UCoordEntry,
     pub palette: Vec<PaletteColorEntry>,
+    #[serde(default)]
+    pub ramps: Vec<Vec<u8>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -110,6 +215,34 @@ pub struct ConfigSyntax {
     pub defaults: PixyleneDefaultsConfig,
     pub keymap_show_command_names: bool,
     pub padding: u8,
+    #[serde(default)]
+    pub compress_projects: bool,
+    #[serde(default)]
+    pub canvas_encoding: SceneEncoding,
+    #[serde(default)]
+    pub max_undo_steps: Option<usize>,
+    #[serde(default)]
+    pub max_undo_memory: Option<usize>,
+    #[serde(default)]
+    pub save_undo_history: bool,
+    #[serde(default)]
+    pub max_saved_undo_memory: Option<usize>,
+    #[serde(default)]
+    pub disable_jumplist: bool,
+    #[serde(default)]
+    pub max_jumps: Option<usize>,
+    #[serde(default)]
+    pub disable_counts: Vec<String>,
+    #[serde(default)]
+    pub leader: Option<Key>,
+    #[serde(default)]
+    pub key_timeout: Option<u64>,
+    #[serde(default)]
+    pub eyedropper_merged: bool,
+    #[serde(default)]
+    pub pencils: HashMap<String, PencilEntry>,
+    #[serde(default)]
+    pub erasers: HashMap<String, EraserEntry>,
 }
 
 impl ConfigSyntax {
@@ -198,125 +331,451 @@ impl Default for ConfigSyntax {
                         c: String::from("#deeed6"),
                     },
                 ],
+                ramps: vec![vec![1, 4, 8, 11, 16], vec![3, 9, 14], vec![2, 5, 10, 15]],
             },
-            keys: NamespaceXKeysEntries::from([(
-                "Main".to_owned(),
-                KeyXUiFnEntries::from([
-                    (
-                        K::new(Char('h'), KM::empty()).into(),
-                        vec![UiFn::RunAction {
-                            name: String::from("cursors_left"),
-                        }],
-                    ),
-                    (
-                        K::new(Char('j'), KM::empty()).into(),
-                        vec![UiFn::RunAction {
-                            name: String::from("cursors_down"),
-                        }],
-                    ),
-                    (
-                        K::new(Char('k'), KM::empty()).into(),
-                        vec![UiFn::RunAction {
-                            name: String::from("cursors_up"),
-                        }],
-                    ),
-                    (
-                        K::new(Char('l'), KM::empty()).into(),
-                        vec![UiFn::RunAction {
-                            name: String::from("cursors_right"),
-                        }],
-                    ),
-                    (
-                        K::new(Left, KM::empty()).into(),
-                        vec![UiFn::RunAction {
-                            name: String::from("cursors_left"),
-                        }],
-                    ),
-                    (
-                        K::new(Down, KM::empty()).into(),
-                        vec![UiFn::RunAction {
-                            name: String::from("cursors_down"),
-                        }],
-                    ),
-                    (
-                        K::new(Up, KM::empty()).into(),
-                        vec![UiFn::RunAction {
-                            name: String::from("cursors_up"),
-                        }],
-                    ),
-                    (
-                        K::new(Right, KM::empty()).into(),
-                        vec![UiFn::RunAction {
-                            name: String::from("cursors_right"),
-                        }],
-                    ),
-                    (
-                        K::new(Char('h'), KM::CONTROL).into(),
-                        vec![UiFn::RunAction {
-                            name: String::from("cursors_dup_left"),
-                        }],
-                    ),
-                    (
-                        K::new(Char('j'), KM::CONTROL).into(),
-                        vec![UiFn::RunAction {
-                            name: String::from("cursors_dup_down"),
-                        }],
-                    ),
-                    (
-                        K::new(Char('k'), KM::CONTROL).into(),
-                        vec![UiFn::RunAction {
-                            name: String::from("cursors_dup_up"),
-                        }],
-                    ),
-                    (
-                        K::new(Char('l'), KM::CONTROL).into(),
-                        vec![UiFn::RunAction {
-                            name: String::from("cursors_dup_right"),
-                        }],
-                    ),
-                    (
-                        K::new(Left, KM::CONTROL).into(),
-                        vec![UiFn::RunAction {
-                            name: String::from("cursors_dup_left"),
-                        }],
-                    ),
-                    (
-                        K::new(Down, KM::CONTROL).into(),
-                        vec![UiFn::RunAction {
-                            name: String::from("cursors_dup_down"),
-                        }],
-                    ),
-                    (
-                        K::new(Up, KM::CONTROL).into(),
-                        vec![UiFn::RunAction {
-                            name: String::from("cursors_dup_up"),
-                        }],
-                    ),
-                    (
-                        K::new(Right, KM::CONTROL).into(),
-                        vec![UiFn::RunAction {
-                            name: String::from("cursors_dup_right"),
-                        }],
-                    ),
-                    (
-                        K::new(Char('r'), KM::empty()).into(),
-                        vec![UiFn::RunAction {
-                            name: String::from("cursors_reset"),
-                        }],
-                    ),
-                    (
-                        K::new(Enter, KM::empty()).into(),
-                        vec![UiFn::RunAction {
-                            name: String::from("pencil"),
-                        }],
-                    ),
-                    (K::new(Char('u'), KM::empty()).into(), vec![UiFn::Undo]),
-                    (K::new(Char('r'), KM::CONTROL).into(), vec![UiFn::Redo]),
-                    (K::new(Char('c'), KM::CONTROL).into(), vec![UiFn::ForceQuit]),
-                ]),
-            )]),
+            keys: NamespaceXKeysEntries::from([
+                (
+                    "Main".to_owned(),
+                    KeyXUiFnEntries::from([
+                        (
+                            K::new(Char('h'), KM::empty()).into(),
+                            vec![UiFn::RunAction {
+                                name: String::from("cursors_left"),
+                                args: Vec::new(),
+                            }],
+                        ),
+                        (
+                            K::new(Char('j'), KM::empty()).into(),
+                            vec![UiFn::RunAction {
+                                name: String::from("cursors_down"),
+                                args: Vec::new(),
+                            }],
+                        ),
+                        (
+                            K::new(Char('k'), KM::empty()).into(),
+                            vec![UiFn::RunAction {
+                                name: String::from("cursors_up"),
+                                args: Vec::new(),
+                            }],
+                        ),
+                        (
+                            K::new(Char('l'), KM::empty()).into(),
+                            vec![UiFn::RunAction {
+                                name: String::from("cursors_right"),
+                                args: Vec::new(),
+                            }],
+                        ),
+                        (
+                            K::new(Left, KM::empty()).into(),
+                            vec![UiFn::RunAction {
+                                name: String::from("cursors_left"),
+                                args: Vec::new(),
+                            }],
+                        ),
+                        (
+                            K::new(Down, KM::empty()).into(),
+                            vec![UiFn::RunAction {
+                                name: String::from("cursors_down"),
+                                args: Vec::new(),
+                            }],
+                        ),
+                        (
+                            K::new(Up, KM::empty()).into(),
+                            vec![UiFn::RunAction {
+                                name: String::from("cursors_up"),
+                                args: Vec::new(),
+                            }],
+                        ),
+                        (
+                            K::new(Right, KM::empty()).into(),
+                            vec![UiFn::RunAction {
+                                name: String::from("cursors_right"),
+                                args: Vec::new(),
+                            }],
+                        ),
+                        (
+                            K::new(Char('h'), KM::CONTROL).into(),
+                            vec![UiFn::RunAction {
+                                name: String::from("cursors_dup_left"),
+                                args: Vec::new(),
+                            }],
+                        ),
+                        (
+                            K::new(Char('j'), KM::CONTROL).into(),
+                            vec![UiFn::RunAction {
+                                name: String::from("cursors_dup_down"),
+                                args: Vec::new(),
+                            }],
+                        ),
+                        (
+                            K::new(Char('k'), KM::CONTROL).into(),
+                            vec![UiFn::RunAction {
+                                name: String::from("cursors_dup_up"),
+                                args: Vec::new(),
+                            }],
+                        ),
+                        (
+                            K::new(Char('l'), KM::CONTROL).into(),
+                            vec![UiFn::RunAction {
+                                name: String::from("cursors_dup_right"),
+                                args: Vec::new(),
+                            }],
+                        ),
+                        (
+                            K::new(Left, KM::CONTROL).into(),
+                            vec![UiFn::RunAction {
+                                name: String::from("cursors_dup_left"),
+                                args: Vec::new(),
+                            }],
+                        ),
+                        (
+                            K::new(Down, KM::CONTROL).into(),
+                            vec![UiFn::RunAction {
+                                name: String::from("cursors_dup_down"),
+                                args: Vec::new(),
+                            }],
+                        ),
+                        (
+                            K::new(Up, KM::CONTROL).into(),
+                            vec![UiFn::RunAction {
+                                name: String::from("cursors_dup_up"),
+                                args: Vec::new(),
+                            }],
+                        ),
+                        (
+                            K::new(Right, KM::CONTROL).into(),
+                            vec![UiFn::RunAction {
+                                name: String::from("cursors_dup_right"),
+                                args: Vec::new(),
+                            }],
+                        ),
+                        (
+                            K::new(Char('r'), KM::empty()).into(),
+                            vec![UiFn::RunAction {
+                                name: String::from("cursors_reset"),
+                                args: Vec::new(),
+                            }],
+                        ),
+                        (
+                            K::new(Enter, KM::empty()).into(),
+                            vec![UiFn::RunAction {
+                                name: String::from("pencil"),
+                                args: Vec::new(),
+                            }],
+                        ),
+                        (K::new(Char('u'), KM::empty()).into(), vec![UiFn::Undo]),
+                        (K::new(Char('r'), KM::CONTROL).into(), vec![UiFn::Redo]),
+                        (
+                            K::new(Char('-'), KM::empty()).into(),
+                            vec![UiFn::UndoEarlier],
+                        ),
+                        (K::new(Char('+'), KM::empty()).into(), vec![UiFn::UndoLater]),
+                        (K::new(Char('o'), KM::CONTROL).into(), vec![UiFn::JumpBack]),
+                        //terminals send Ctrl-i as Tab
+                        (K::new(Tab, KM::empty()).into(), vec![UiFn::JumpForward]),
+                        (
+                            K::new(Char('q'), KM::empty()).into(),
+                            vec![UiFn::RecordMacro { register: None }],
+                        ),
+                        (
+                            K::new(Char('@'), KM::empty()).into(),
+                            vec![UiFn::PlayMacro {
+                                register: None,
+                                count: None,
+                            }],
+                        ),
+                        (
+                            K::new(Char('d'), KM::empty()).into(),
+                            vec![UiFn::Operator {
+                                name: String::from("erase"),
+                                count: None,
+                            }],
+                        ),
+                        (
+                            K::new(Char('y'), KM::empty()).into(),
+                            vec![UiFn::Operator {
+                                name: String::from("yank"),
+                                count: None,
+                            }],
+                        ),
+                        (
+                            K::new(Char('f'), KM::empty()).into(),
+                            vec![UiFn::Operator {
+                                name: String::from("fill"),
+                                count: None,
+                            }],
+                        ),
+                        (
+                            K::new(Char('c'), KM::empty()).into(),
+                            vec![UiFn::Operator {
+                                name: String::from("recolor"),
+                                count: None,
+                            }],
+                        ),
+                        (
+                            K::new(Char('s'), KM::empty()).into(),
+                            vec![UiFn::Operator {
+                                name: String::from("select"),
+                                count: None,
+                            }],
+                        ),
+                        (
+                            K::new(Char('p'), KM::empty()).into(),
+                            vec![UiFn::RunAction {
+                                name: String::from("paste"),
+                                args: Vec::new(),
+                            }],
+                        ),
+                        (
+                            K::new(Char('i'), KM::empty()).into(),
+                            vec![UiFn::Eyedropper { x: None, y: None }],
+                        ),
+                        (
+                            K::new(Char('x'), KM::empty()).into(),
+                            vec![UiFn::RunAction {
+                                name: String::from("swap"),
+                                args: Vec::new(),
+                            }],
+                        ),
+                        (
+                            K::new(Char('X'), KM::empty()).into(),
+                            vec![UiFn::RunAction {
+                                name: String::from("pencil_secondary"),
+                                args: Vec::new(),
+                            }],
+                        ),
+                        (K::new(Char('t'), KM::empty()).into(), vec![UiFn::TogglePen]),
+                        (
+                            K::new(Char('b'), KM::empty()).into(),
+                            vec![UiFn::Tool {
+                                name: String::from("pencil"),
+                            }],
+                        ),
+                        (
+                            K::new(Char('e'), KM::empty()).into(),
+                            vec![UiFn::Tool {
+                                name: String::from("eraser"),
+                            }],
+                        ),
+                        (
+                            K::new(Char('g'), KM::empty()).into(),
+                            vec![UiFn::Tool {
+                                name: String::from("fill"),
+                            }],
+                        ),
+                        (
+                            K::new(Char('L'), KM::empty()).into(),
+                            vec![UiFn::Tool {
+                                name: String::from("line"),
+                            }],
+                        ),
+                        (
+                            K::new(Char('v'), KM::empty()).into(),
+                            vec![UiFn::Visual { point: false }],
+                        ),
+                        (
+                            K::new(Char('v'), KM::CONTROL).into(),
+                            vec![UiFn::Visual { point: true }],
+                        ),
+                        (K::new(Char('c'), KM::CONTROL).into(), vec![UiFn::ForceQuit]),
+                    ]),
+                ),
+                (
+                    "Visual".to_owned(),
+                    KeyXUiFnEntries::from([
+                        (
+                            K::new(Char('h'), KM::empty()).into(),
+                            vec![UiFn::RunAction {
+                                name: String::from("focus_left"),
+                                args: Vec::new(),
+                            }],
+                        ),
+                        (
+                            K::new(Char('j'), KM::empty()).into(),
+                            vec![UiFn::RunAction {
+                                name: String::from("focus_down"),
+                                args: Vec::new(),
+                            }],
+                        ),
+                        (
+                            K::new(Char('k'), KM::empty()).into(),
+                            vec![UiFn::RunAction {
+                                name: String::from("focus_up"),
+                                args: Vec::new(),
+                            }],
+                        ),
+                        (
+                            K::new(Char('l'), KM::empty()).into(),
+                            vec![UiFn::RunAction {
+                                name: String::from("focus_right"),
+                                args: Vec::new(),
+                            }],
+                        ),
+                        (
+                            K::new(Left, KM::empty()).into(),
+                            vec![UiFn::RunAction {
+                                name: String::from("focus_left"),
+                                args: Vec::new(),
+                            }],
+                        ),
+                        (
+                            K::new(Down, KM::empty()).into(),
+                            vec![UiFn::RunAction {
+                                name: String::from("focus_down"),
+                                args: Vec::new(),
+                            }],
+                        ),
+                        (
+                            K::new(Up, KM::empty()).into(),
+                            vec![UiFn::RunAction {
+                                name: String::from("focus_up"),
+                                args: Vec::new(),
+                            }],
+                        ),
+                        (
+                            K::new(Right, KM::empty()).into(),
+                            vec![UiFn::RunAction {
+                                name: String::from("focus_right"),
+                                args: Vec::new(),
+                            }],
+                        ),
+                        (K::new(Enter, KM::empty()).into(), vec![UiFn::VisualConfirm]),
+                    ]),
+                ),
+                (
+                    "Motion".to_owned(),
+                    KeyXUiFnEntries::from([
+                        (
+                            K::new(Char('h'), KM::empty()).into(),
+                            vec![UiFn::Motion {
+                                name: String::from("left"),
+                                count: None,
+                            }],
+                        ),
+                        (
+                            K::new(Char('j'), KM::empty()).into(),
+                            vec![UiFn::Motion {
+                                name: String::from("down"),
+                                count: None,
+                            }],
+                        ),
+                        (
+                            K::new(Char('k'), KM::empty()).into(),
+                            vec![UiFn::Motion {
+                                name: String::from("up"),
+                                count: None,
+                            }],
+                        ),
+                        (
+                            K::new(Char('l'), KM::empty()).into(),
+                            vec![UiFn::Motion {
+                                name: String::from("right"),
+                                count: None,
+                            }],
+                        ),
+                        (
+                            K::new(Char('h'), KM::CONTROL).into(),
+                            vec![UiFn::Motion {
+                                name: String::from("edge_left"),
+                                count: None,
+                            }],
+                        ),
+                        (
+                            K::new(Char('j'), KM::CONTROL).into(),
+                            vec![UiFn::Motion {
+                                name: String::from("edge_down"),
+                                count: None,
+                            }],
+                        ),
+                        (
+                            K::new(Char('k'), KM::CONTROL).into(),
+                            vec![UiFn::Motion {
+                                name: String::from("edge_up"),
+                                count: None,
+                            }],
+                        ),
+                        (
+                            K::new(Char('l'), KM::CONTROL).into(),
+                            vec![UiFn::Motion {
+                                name: String::from("edge_right"),
+                                count: None,
+                            }],
+                        ),
+                        (
+                            K::new(Char('w'), KM::empty()).into(),
+                            vec![UiFn::Motion {
+                                name: String::from("boundary_right"),
+                                count: None,
+                            }],
+                        ),
+                        (
+                            K::new(Char('b'), KM::empty()).into(),
+                            vec![UiFn::Motion {
+                                name: String::from("boundary_left"),
+                                count: None,
+                            }],
+                        ),
+                        (
+                            K::new(Char('}'), KM::empty()).into(),
+                            vec![UiFn::Motion {
+                                name: String::from("boundary_down"),
+                                count: None,
+                            }],
+                        ),
+                        (
+                            K::new(Char('{'), KM::empty()).into(),
+                            vec![UiFn::Motion {
+                                name: String::from("boundary_up"),
+                                count: None,
+                            }],
+                        ),
+                        (
+                            K::new(Char('o'), KM::empty()).into(),
+                            vec![UiFn::Motion {
+                                name: String::from("region"),
+                                count: None,
+                            }],
+                        ),
+                        (
+                            K::new(Char('r'), KM::empty()).into(),
+                            vec![UiFn::Motion {
+                                name: String::from("row"),
+                                count: None,
+                            }],
+                        ),
+                        (
+                            K::new(Char('c'), KM::empty()).into(),
+                            vec![UiFn::Motion {
+                                name: String::from("column"),
+                                count: None,
+                            }],
+                        ),
+                        (
+                            K::new(Char('a'), KM::empty()).into(),
+                            vec![UiFn::Motion {
+                                name: String::from("layer"),
+                                count: None,
+                            }],
+                        ),
+                    ]),
+                ),
+            ]),
             keymap_show_command_names: true,
             padding: 1,
+            compress_projects: false,
+            canvas_encoding: SceneEncoding::Verbose,
+            max_undo_steps: None,
+            max_undo_memory: None,
+            save_undo_history: false,
+            max_saved_undo_memory: None,
+            disable_jumplist: false,
+            max_jumps: None,
+            disable_counts: Vec::new(),
+            leader: None,
+            key_timeout: None,
+            eyedropper_merged: false,
+            pencils: HashMap::new(),
+            erasers: HashMap::new(),
         }
     }
 }
@@ -371,9 +830,67 @@ fn parse_config() -> Option<Result<ConfigSyntax, String>> {
     }
 }
 
+fn parse_pencils(
+    pencils: HashMap<String, PencilEntry>,
+) -> Result<HashMap<String, PencilConfig>, String> {
+    use colored::Colorize;
+
+    pencils
+        .into_iter()
+        .map(|(name, entry)| {
+            let blend_mode = match entry.blend {
+                Some(blend) => blend.parse().map_err(|err| {
+                    format!(
+                        "{}{}\n{}",
+                        "Config File Error: ".red().bold(),
+                        format!("pencils.{}.blend", name).italic(),
+                        err,
+                    )
+                })?,
+                None => BlendMode::Normal,
+            };
+            Ok((
+                name,
+                PencilConfig {
+                    index: entry.index,
+                    secondary: entry.secondary,
+                    opacity: entry.opacity.unwrap_or(255),
+                    blend_mode,
+                },
+            ))
+        })
+        .collect()
+}
+
 fn parse_defaults(defaults: PixyleneDefaultsConfig) -> Result<PixyleneDefaults, String> {
     use colored::Colorize;
 
+    let mut palette = Palette::from(
+        &defaults
+            .palette
+            .iter()
+            .map(|entry| (entry.id, entry.c.as_str()))
+            .collect::<Vec<(u8, &str)>>(),
+    )
+    .map_err(|err| {
+        format!(
+            "{}{}\n{}",
+            "Config File Error: ".red().bold(),
+            "defaults.palette".italic(),
+            err,
+        )
+    })?;
+    for ramp in defaults.ramps {
+        palette.add_ramp(ramp).map_err(|err| {
+            format!(
+                "{}{}\n{}",
+                "Config File Error: ".red().bold(),
+                "defaults.ramps".italic(),
+                err,
+            )
+        })?;
+    }
+
     Ok(PixyleneDefaults {
         dim: UCoord {
             x: defaults.dimensions.x,
@@ -401,31 +918,21 @@ fn parse_defaults(defaults: PixyleneDefaultsConfig) -> Result<PixyleneDefaults,
                 err,
             )
         })?,
-        palette: Palette::from(
-            &defaults
-                .palette
-                .iter()
-                .map(|entry| (entry.id, entry.c.as_str()))
-                .collect::<Vec<(u8, &str)>>(),
-        )
-        .map_err(|err| {
-            format!(
-                "{}{}\n{}",
-                "Config File Error: ".red().bold(),
-                "defaults.palette".italic(),
-                err,
-            )
-        })?,
+        palette,
     })
 }
 
 fn get_keys_from_config(
     config: &Option<ConfigSyntax>,
     default_keys: NamespaceXKeysEntries,
-) -> (KeyMap, HashMap<String, ()>) {
+    leader: &Key,
+) -> Result<(KeyMap, HashMap<String, ()>, Vec<String>), String> {
+    use colored::Colorize;
+
     let mut keymap = HashMap::new();
     keymap.insert(None, HashMap::new());
     let mut possible_namespaces = HashMap::new();
+    let mut warnings = Vec::new();
 
     //if no user config or user config doesn't want new_keys
     if !(config.is_some() && config.as_ref().unwrap().new_keys) {
@@ -438,8 +945,8 @@ fn get_keys_from_config(
                 let mut map = HashMap::new();
                 _ = keys
                     .into_iter()
-                    .map(|(key, fns)| {
-                        map.insert(key, fns);
+                    .map(|(keys, fns)| {
+                        map.insert(keys.with_leader(leader), fns);
                     })
                     .collect::<Vec<()>>();
                 keymap.insert(Some(namespace), map);
@@ -456,12 +963,30 @@ fn get_keys_from_config(
                 let mut map = HashMap::new();
                 _ = keys
                     .iter()
-                    .map(|(key, fns)| {
-                        map.insert(key.clone(), fns.clone());
+                    .map(|(keys, fns)| {
+                        map.insert(keys.clone().with_leader(leader), fns.clone());
                     })
                     .collect::<Vec<()>>();
 
                 if let Some(existing_group) = keymap.get_mut(&Some(namespace.clone())) {
+                    //user keys shadow default keys that they equal, are a prefix of or start with, which is
+                    //reported unless they are bound to the same functions
+                    existing_group.retain(|existing: &KeySeq, existing_fns| {
+                        let Some((keys, fns)) = map.iter().find(|(keys, _)| {
+                            *keys == existing
+                                || keys.starts_with(existing)
+                                || existing.starts_with(keys)
+                        }) else {
+                            return true;
+                        };
+                        if keys != existing || fns != existing_fns {
+                            warnings.push(format!(
+                                "keys.{}: '{}' overrides the default binding of '{}'",
+                                namespace, keys, existing,
+                            ));
+                        }
+                        false
+                    });
                     existing_group.extend(map);
                 } else {
                     keymap.insert(Some(namespace.to_string()), map);
@@ -470,5 +995,50 @@ fn get_keys_from_config(
             .collect::<Vec<()>>();
     }
 
-    (keymap, possible_namespaces)
+    let error = |namespace: &Option<String>, message: String| {
+        format!(
+            "{}{}\n{}",
+            "Config File Error: ".red().bold(),
+            format!("keys.{}", namespace.as_ref().map_or("", |n| n.as_str())).italic(),
+            message,
+        )
+    };
+
+    //a sequence bound to functions may not also start a longer sequence
+    for (namespace, keys) in &keymap {
+        for prefix in keys.keys() {
+            if let Some(longer) = keys.keys().find(|keys| keys.starts_with(prefix)) {
+                return Err(error(
+                    namespace,
+                    format!(
+                        "'{}' is bound but is also the start of '{}', one of them has to be \
+                        changed",
+                        prefix, longer,
+                    ),
+                ));
+            }
+        }
+    }
+
+    //nor across the overlay & a namespace, since overlay keys are looked up first
+    let overlay = keymap.get(&None).unwrap();
+    for (namespace, keys) in keymap.iter().filter(|(namespace, _)| namespace.is_some()) {
+        for keys in keys.keys() {
+            if let Some(conflict) = overlay
+                .keys()
+                .find(|overlay| overlay.starts_with(keys) || keys.starts_with(overlay))
+            {
+                return Err(error(
+                    namespace,
+                    format!(
+                        "'{}' and the overlay key '{}' start with one another, one of them has to \
+                        be changed",
+                        keys, conflict,
+                    ),
+                ));
+            }
+        }
+    }
+
+    Ok((keymap, possible_namespaces, warnings))
 }
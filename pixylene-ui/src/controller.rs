@@ -1,24 +1,30 @@
 use crate::{
-    actions::{self, ActionPtr},
+    actions::{self, ActionPtr, MotionPtr, OperatorPtr, ToolPtr},
     config::Config,
-    ui::{Key, KeyInfo, Rectangle, ReqUiFnMap, Statusline, UiFn, UserInterface},
-    utils::{deparse, parse_cmd},
+    ui::{Key, KeyInfo, KeySeq, Rectangle, ReqUiFnMap, Statusline, UiFn, UserInterface},
+    utils::{deparse, parse_cmd, parse_list},
 };
 
 use libpixylene::{
-    project::{Layer, LayersType, OPixel},
+    file::{Downsampler, ProjectFile, SceneEncoding},
+    project::{Layer, LayersType, OPixel, Pattern},
     types::{Coord, PCoord, PCoordContainer, TruePixel, UCoord},
-    Pixylene,
+    Pixylene, PixyleneError,
+};
+use pixylene_actions::{
+    memento::{cover, ActionManager, JumpList, Operation, Stroke, ToolOptions},
+    std_actions::{palette, scene},
+    Args, Console, LogType, Param,
 };
-use pixylene_actions::{memento::ActionManager, Console, LogType};
 
 use clap::Subcommand;
 use std::{
     cell::RefCell,
     collections::{hash_map::Iter, HashMap},
-    path::PathBuf,
+    path::{Path, PathBuf},
     process::exit,
     rc::Rc,
+    time::{Instant, SystemTime},
 };
 
 const SPLASH_LOGO: &str = r#"
@@ -41,6 +47,18 @@ const SPLASH_LOGO: &str = r#"
  type  :lk            - to list the required keys & keys in the default namespace
  type  :q             - to quit
 "#;
+
+//namespace entered while an operator waits for a motion, if it has been defined
+const MOTION_NAMESPACE: &str = "Motion";
+
+//namespace entered in visual mode, if it has been defined
+const VISUAL_NAMESPACE: &str = "Visual";
+
+//tool that sessions start with
+const DEFAULT_TOOL: &str = "pencil";
+
+//counts typed past this are capped to it
+const MAX_COUNT: usize = 999;
 // type  :help                      - if you are new!
 // type  :import foo.png            - to start editing 'foo.png'
 // type  :e foo.json                - to edit a previously saved canvas file 'foo.json'
@@ -65,6 +83,10 @@ pub enum StartType {
         path: PathBuf,
         width: Option<u32>,
         height: Option<u32>,
+        /// Downsample with exact source colors instead of resizing, to the detected pixel grid if
+        /// no dimensions are given (mode, center)
+        #[clap(long, short)]
+        downsampler: Option<Downsampler>,
         /*/*todo*/colorscheme: Option<Colorscheme>,*/
     },
 }
@@ -72,7 +94,8 @@ pub enum StartType {
 pub struct PixyleneSession {
     name: String,
     pixylene: Rc<RefCell<Pixylene>>,
-    last_action_name: Option<String>,
+    /// The name & arguments of the last action that changed the canvas
+    last_action: Option<(String, Vec<String>)>,
 
     canvas_file_path: Option<PathBuf>,
     project_file_path: Option<PathBuf>,
@@ -80,7 +103,17 @@ pub struct PixyleneSession {
     modified: bool,
 
     native_action_map: HashMap<String, ActionPtr>,
+    motion_map: HashMap<String, MotionPtr>,
+    operator_map: HashMap<String, OperatorPtr>,
     native_action_manager: ActionManager,
+    jump_list: Option<JumpList>,
+
+    tool_map: HashMap<String, ToolPtr>,
+    /// The name of the current tool
+    tool: String,
+    tool_options: ToolOptions,
+    /// The stroke of the current tool while the pen is down
+    stroke: Option<Stroke>,
 
     #[cfg(feature = "lua")]
     lua_action_map: HashMap<String, ()>,
@@ -93,17 +126,31 @@ pub struct Controller {
     config: Config,
     namespace: String,
     running: bool,
+    count: Option<usize>,
+    //name & count of the operator waiting for a motion, & the namespace to return to
+    operator: Option<(String, usize, String)>,
+    //anchor of the rectangle (none in point selection), & the namespace to return to
+    visual: Option<(Option<Coord>, String)>,
+    pending_keys: Vec<Key>,
+    key_time: Instant,
 
     //sessions
     sessions: Vec<PixyleneSession>,
     sel_session: u8, //1-based index
 
+    //macros
+    macros_path: Option<PathBuf>,
+    registers: HashMap<char, Vec<UiFn>>,
+    recording: Option<(char, Vec<UiFn>)>,
+    playing: Vec<char>,
+
     //window boundaries
     b_console: Rectangle,
     b_camera: Rectangle,
     b_statusline: Rectangle,
 }
 
+#[derive(Clone)]
 struct ControllerLite {
     b_console: Rectangle,
     discard_command: Key,
@@ -128,7 +175,17 @@ impl Console for ControllerLite {
 }
 
 impl Controller {
-    pub fn new(target: Rc<RefCell<dyn UserInterface>>, mut config: Config) -> Self {
+    pub fn new(target: Rc<RefCell<dyn UserInterface>>, config: Config) -> Self {
+        Self::with_macros_path(target, config, macros_path())
+    }
+
+    //loads & saves macros at the given path instead of the one in the config directory, none
+    //keeping them only for this run
+    fn with_macros_path(
+        target: Rc<RefCell<dyn UserInterface>>,
+        mut config: Config,
+        macros_path: Option<PathBuf>,
+    ) -> Self {
         target.borrow_mut().initialize();
 
         if config.keymap.get(&None).is_none() {
@@ -145,15 +202,43 @@ impl Controller {
             size: current_dim,
         });
 
+        let registers = match macros_path.as_deref().map(load_macros) {
+            Some(Ok(registers)) => registers,
+            Some(Err(err)) => {
+                target
+                    .borrow_mut()
+                    .console_out(&err, &LogType::Warning, &b_console);
+                HashMap::new()
+            }
+            None => HashMap::new(),
+        };
+        if !config.warnings.is_empty() {
+            target.borrow_mut().console_out(
+                &config.warnings.join("; "),
+                &LogType::Warning,
+                &b_console,
+            );
+        }
+
         Self {
             target,
             namespace: config.default_namespace.clone(),
             config,
             running: true,
+            count: None,
+            operator: None,
+            visual: None,
+            pending_keys: Vec::new(),
+            key_time: Instant::now(),
 
             sessions: Vec::new(),
             sel_session: 0,
 
+            macros_path,
+            registers,
+            recording: None,
+            playing: Vec::new(),
+
             b_console,
             b_camera,
             b_statusline,
@@ -167,6 +252,7 @@ impl Controller {
         if !self.target.borrow_mut().refresh() || !self.running {
             return false;
         }
+        self.expire_keys();
 
         if self.sessions.len() > 0 {
             for func in self.config.every_frame.clone() {
@@ -267,6 +353,193 @@ impl Controller {
         self.target.borrow_mut().clear(&self.b_console);
     }
 
+    //appends an executed function to the macro being recorded, interactive functions record
+    //what they resolve to instead
+    fn record(&mut self, func: &UiFn) {
+        use UiFn::*;
+
+        if !self.playing.is_empty() {
+            return;
+        }
+        if let Some((_, funcs)) = &mut self.recording {
+            match func {
+                OpenCanvasSpecify
+                | OpenProjectSpecify
+                | RunCommandSpecify
+                | RunActionSpecify
+                | RecordMacro { .. }
+                | EditMacro { .. }
+                | PlayMacro { register: None, .. } => (),
+                #[cfg(feature = "lua")]
+                RunLuaSpecify => (),
+                _ => funcs.push(func.clone()),
+            }
+        }
+    }
+
+    fn cancel_visual(&mut self) {
+        if let Some((_, namespace)) = self.visual.take() {
+            self.namespace = namespace;
+        }
+    }
+
+    //lifts the pen of the session, committing its stroke as a single step
+    fn lift_pen(&mut self) {
+        if self.sessions.len() == 0 {
+            return;
+        }
+        let session = &mut self.sessions[usize::from(self.sel_session) - 1];
+        if session.stroke.take().is_some() {
+            if session.native_action_manager.commit(
+                &session.pixylene.borrow().project.canvas,
+                &format!("stroke {}", session.tool),
+            ) {
+                session.modified = true;
+            }
+            if let Some(jump_list) = &mut session.jump_list {
+                jump_list.record(&session.pixylene.borrow().project);
+            }
+        }
+    }
+
+    //records the view of the session as a jump before leaving it for another session
+    fn mark_jump(&mut self) {
+        if self.sessions.len() == 0 {
+            return;
+        }
+        let session = &mut self.sessions[usize::from(self.sel_session) - 1];
+        if let Some(jump_list) = &mut session.jump_list {
+            jump_list.mark(&session.pixylene.borrow().project);
+        }
+    }
+
+    fn cancel_operator(&mut self) {
+        if let Some((_, _, namespace)) = self.operator.take() {
+            self.namespace = namespace;
+        }
+    }
+
+    //pending keys of a sequence are dropped if the rest of it isn't typed in time
+    fn expire_keys(&mut self) {
+        if !self.pending_keys.is_empty() && self.key_time.elapsed() > self.config.key_timeout {
+            self.pending_keys.clear();
+            self.count = None;
+        }
+    }
+
+    //digits are read as a count prefix over their bindings like in vim, 0 only continuing a count
+    //so that it stays bindable, unless counts are disabled in the namespace or a sequence is pending
+    fn count_digit(&self, key: &Key) -> Option<usize> {
+        let digit = key
+            .to_string()
+            .parse::<usize>()
+            .ok()
+            .filter(|digit| *digit < 10)?;
+        if !self.pending_keys.is_empty()
+            || self.config.disable_counts.contains(&self.namespace)
+            || (digit == 0 && self.count.is_none())
+        {
+            return None;
+        }
+        Some(digit)
+    }
+
+    //returns whether the function runs an action that takes a count itself
+    fn takes_count(&self, func: &UiFn) -> bool {
+        let (UiFn::RunAction { name, .. } | UiFn::RunNativeAction { name, .. }) = func else {
+            return false;
+        };
+        let Some(session) = self
+            .sessions
+            .get(usize::from(self.sel_session).wrapping_sub(1))
+        else {
+            return false;
+        };
+        session.native_action_map.get(name).is_some_and(|action| {
+            action
+                .borrow()
+                .params()
+                .iter()
+                .any(|param| param.name == Param::COUNT.name)
+        })
+    }
+
+    //runs the functions bound to a key count times, a single function that can take the count
+    //itself runs once with it
+    fn run_counted(&mut self, funcs: Vec<UiFn>, count: Option<usize>) {
+        let counted = match (count, funcs.as_slice()) {
+            (Some(count), [func]) if self.takes_count(func) => match func.clone() {
+                UiFn::RunAction { name, mut args } => {
+                    args.push(format!("{}={}", Param::COUNT.name, count));
+                    Some(UiFn::RunAction { name, args })
+                }
+                UiFn::RunNativeAction { name, mut args } => {
+                    args.push(format!("{}={}", Param::COUNT.name, count));
+                    Some(UiFn::RunNativeAction { name, args })
+                }
+                _ => None,
+            },
+            (Some(count), [func]) => func.with_count(count),
+            _ => None,
+        };
+        match counted {
+            Some(func) => {
+                self.record(&func);
+                _ = self.perform_ui(&func);
+            }
+            None => {
+                for _ in 0..count.unwrap_or(1) {
+                    for func in &funcs {
+                        self.record(func);
+                        _ = self.perform_ui(func);
+                    }
+                }
+            }
+        }
+    }
+
+    fn register(&self, register: &Option<char>, message: &str) -> Option<char> {
+        let register = match register {
+            Some(register) => *register,
+            None => {
+                let input = self.console_in(message)?;
+                let mut chars = input.trim().chars();
+                match (chars.next(), chars.next()) {
+                    (Some(register), None) => register,
+                    _ => {
+                        self.console_out("a register is a single character", &LogType::Error);
+                        return None;
+                    }
+                }
+            }
+        };
+        if register.is_ascii_alphanumeric() {
+            Some(register)
+        } else {
+            self.console_out(
+                &format!("invalid register '{}', use a letter or digit", register),
+                &LogType::Error,
+            );
+            None
+        }
+    }
+
+    fn save_macros(&self) {
+        let saved = match &self.macros_path {
+            Some(path) => save_macros(path, &self.registers),
+            None => Err("could not find the config directory".to_owned()),
+        };
+        if let Err(err) = saved {
+            self.console_out(&err, &LogType::Error);
+        }
+    }
+
+    fn new_jump_list(&self, pixylene: &Pixylene) -> Option<JumpList> {
+        self.config
+            .jumplist
+            .then(|| JumpList::new(&pixylene.project, self.config.max_jumps))
+    }
+
     fn sel_session(&self) -> Result<usize, ()> {
         if self.sessions.len() == 0 {
             self.console_out(
@@ -350,6 +623,30 @@ impl Controller {
         };
 
         actions::add_my_native_actions(&mut native_action_map);
+        for (name, pencil) in &self.config.pencils {
+            let base = match pencil.secondary {
+                true => scene::Pencil::secondary(),
+                false => scene::Pencil::new(pencil.index),
+            };
+            native_action_map.insert(
+                name.clone(),
+                Rc::new(RefCell::new(
+                    base.with_options(pencil.opacity, pencil.blend_mode),
+                )),
+            );
+        }
+        for (name, amount) in &self.config.erasers {
+            native_action_map.insert(
+                name.clone(),
+                Rc::new(RefCell::new(scene::Eraser::new(*amount))),
+            );
+        }
+        let mut motion_map: HashMap<String, MotionPtr> = HashMap::new();
+        actions::add_my_motions(&mut motion_map);
+        let mut operator_map: HashMap<String, OperatorPtr> = HashMap::new();
+        actions::add_my_operators(&mut operator_map, &mut native_action_map);
+        let mut tool_map: HashMap<String, ToolPtr> = HashMap::new();
+        actions::add_my_tools(&mut tool_map);
 
         match start_type {
             StartType::New {
@@ -394,17 +691,26 @@ impl Controller {
                 }
                 initialize_project(&mut pixylene);
 
-                let native_action_manager = ActionManager::new(&pixylene.project.canvas);
+                let native_action_manager =
+                    ActionManager::new(&pixylene.project.canvas, self.config.history_limits);
+                let jump_list = self.new_jump_list(&pixylene);
                 self.sessions.push(PixyleneSession {
                     name: String::from("new"),
                     pixylene: Rc::new(RefCell::new(pixylene)),
-                    last_action_name: None,
+                    last_action: None,
                     canvas_file_path: None,
                     project_file_path: None,
                     modified: false,
 
                     native_action_map,
+                    motion_map,
+                    operator_map,
                     native_action_manager,
+                    jump_list,
+                    tool_map,
+                    tool: String::from(DEFAULT_TOOL),
+                    tool_options: ToolOptions::default(),
+                    stroke: None,
 
                     #[cfg(feature = "lua")]
                     lua_action_map,
@@ -413,22 +719,38 @@ impl Controller {
                 });
                 self.sel_session += 1;
             }
+            StartType::Canvas { path } if ProjectFile::is_json_path(path) => {
+                //human-readable project files are opened as projects so that focus & cursors are
+                //restored
+                self.new_session(&StartType::Project { path: path.clone() }, from_args);
+            }
             StartType::Canvas { path } => {
                 match Pixylene::open_canvas(&path, &self.config.defaults) {
                     Ok(mut pixylene) => {
                         pixylene.project.out_dim = self.b_camera.size;
                         initialize_project(&mut pixylene);
-                        let native_action_manager = ActionManager::new(&pixylene.project.canvas);
+                        let native_action_manager = ActionManager::new(
+                            &pixylene.project.canvas,
+                            self.config.history_limits,
+                        );
+                        let jump_list = self.new_jump_list(&pixylene);
                         self.sessions.push(PixyleneSession {
                             name: path.display().to_string(),
                             pixylene: Rc::new(RefCell::new(pixylene)),
-                            last_action_name: None,
+                            last_action: None,
                             canvas_file_path: Some(path.clone()),
                             project_file_path: None,
                             modified: false,
 
                             native_action_map,
+                            motion_map,
+                            operator_map,
                             native_action_manager,
+                            jump_list,
+                            tool_map,
+                            tool: String::from(DEFAULT_TOOL),
+                            tool_options: ToolOptions::default(),
+                            stroke: None,
 
                             #[cfg(feature = "lua")]
                             lua_action_map,
@@ -451,20 +773,47 @@ impl Controller {
                     }
                 }
             }
-            StartType::Project { path } => match Pixylene::open_project(&path) {
-                Ok(mut pixylene) => {
+            StartType::Project { path } => match Pixylene::open_project_with_history(&path) {
+                Ok((mut pixylene, history)) => {
                     pixylene.project.out_dim = self.b_camera.size;
-                    let native_action_manager = ActionManager::new(&pixylene.project.canvas);
+                    let native_action_manager = match history.and_then(|history| {
+                        ActionManager::from_history(
+                            &pixylene.project.canvas,
+                            history,
+                            self.config.history_limits,
+                        )
+                        .ok_or_else(|| {
+                            self.console_out(
+                                "the undo history saved in the project doesn't fit its canvas, \
+                                starting a new one",
+                                &LogType::Warning,
+                            );
+                        })
+                        .ok()
+                    }) {
+                        Some(action_manager) => action_manager,
+                        None => {
+                            ActionManager::new(&pixylene.project.canvas, self.config.history_limits)
+                        }
+                    };
+                    let jump_list = self.new_jump_list(&pixylene);
                     self.sessions.push(PixyleneSession {
                         name: path.display().to_string(),
                         pixylene: Rc::new(RefCell::new(pixylene)),
-                        last_action_name: None,
-                        canvas_file_path: None,
+                        last_action: None,
+                        canvas_file_path: ProjectFile::is_json_path(path).then(|| path.clone()),
                         project_file_path: Some(path.clone()),
                         modified: false,
 
                         native_action_map,
+                        motion_map,
+                        operator_map,
                         native_action_manager,
+                        jump_list,
+                        tool_map,
+                        tool: String::from(DEFAULT_TOOL),
+                        tool_options: ToolOptions::default(),
+                        stroke: None,
 
                         #[cfg(feature = "lua")]
                         lua_action_map,
@@ -490,6 +839,7 @@ impl Controller {
                 path,
                 width,
                 height,
+                downsampler,
             } => {
                 let mut resize = None;
                 if let Some(width) = width {
@@ -511,21 +861,32 @@ impl Controller {
                         }
                     }
                 }
-                match Pixylene::import(&path, resize, &self.config.defaults) {
+                match Pixylene::import(&path, resize, *downsampler, &self.config.defaults) {
                     Ok(mut pixylene) => {
                         pixylene.project.out_dim = self.b_camera.size;
                         initialize_project(&mut pixylene);
-                        let native_action_manager = ActionManager::new(&pixylene.project.canvas);
+                        let native_action_manager = ActionManager::new(
+                            &pixylene.project.canvas,
+                            self.config.history_limits,
+                        );
+                        let jump_list = self.new_jump_list(&pixylene);
                         self.sessions.push(PixyleneSession {
                             name: path.display().to_string(),
                             pixylene: Rc::new(RefCell::new(pixylene)),
-                            last_action_name: None,
+                            last_action: None,
                             canvas_file_path: None,
                             project_file_path: None,
                             modified: false,
 
                             native_action_map,
+                            motion_map,
+                            operator_map,
                             native_action_manager,
+                            jump_list,
+                            tool_map,
+                            tool: String::from(DEFAULT_TOOL),
+                            tool_options: ToolOptions::default(),
+                            stroke: None,
 
                             #[cfg(feature = "lua")]
                             lua_action_map,
@@ -566,6 +927,40 @@ impl Controller {
     fn perform_ui(&mut self, func: &UiFn) -> Result<(), ()> {
         use UiFn::*;
 
+        //a stroke is committed before moving through the history, leaving the session or changing
+        //the canvas otherwise, so that it is undone & saved as a whole, apart from other changes
+        //(lua may change the canvas with any action, & so always lifts the pen)
+        let changes_canvas = match func {
+            RunNativeAction { name, .. } => self
+                .sessions
+                .get(usize::from(self.sel_session).wrapping_sub(1))
+                .and_then(|session| session.native_action_map.get(name))
+                .map_or(false, |action| action.borrow().changes_canvas()),
+            Motion { .. } | Eyedropper { .. } => true,
+            #[cfg(feature = "lua")]
+            RunLuaAction { .. } | RunLua { .. } => true,
+            _ => false,
+        };
+        if changes_canvas
+            || matches!(
+                func,
+                Quit | ForceQuit
+                    | GoToSession { .. }
+                    | GoToNextSession
+                    | GoToPrevSession
+                    | SaveCanvas
+                    | SaveProject
+                    | Export { .. }
+                    | Undo
+                    | Redo
+                    | UndoJump { .. }
+                    | UndoEarlier
+                    | UndoLater
+            )
+        {
+            self.lift_pen();
+        }
+
         match func {
             //Sessions
             New {
@@ -590,9 +985,11 @@ impl Controller {
                 let input = self.console_in("open canvas file: ");
                 match input {
                     Some(input) => {
-                        _ = self.perform_ui(&OpenCanvas {
+                        let func = OpenCanvas {
                             path: PathBuf::from(input),
-                        });
+                        };
+                        self.record(&func);
+                        _ = self.perform_ui(&func);
                     }
                     None => (),
                 }
@@ -604,9 +1001,11 @@ impl Controller {
                 let input = self.console_in("open project file: ");
                 match input {
                     Some(input) => {
-                        _ = self.perform_ui(&OpenProject {
+                        let func = OpenProject {
                             path: PathBuf::from(input),
-                        });
+                        };
+                        self.record(&func);
+                        _ = self.perform_ui(&func);
                     }
                     None => (),
                 }
@@ -615,12 +1014,14 @@ impl Controller {
                 path,
                 width,
                 height,
+                downsampler,
             } => {
                 self.new_session(
                     &StartType::Import {
                         path: path.clone(),
                         width: *width,
                         height: *height,
+                        downsampler: *downsampler,
                     },
                     false,
                 );
@@ -655,6 +1056,7 @@ impl Controller {
                     self.console_out("there are no sessions open", &LogType::Error);
                 }
                 if *index < num_sessions {
+                    self.mark_jump();
                     self.sel_session = *index;
                 } else {
                     self.console_out(
@@ -668,6 +1070,7 @@ impl Controller {
                 match s.checked_add(1) {
                     Some(new) => {
                         if usize::from(new) <= self.sessions.len() {
+                            self.mark_jump();
                             self.sel_session = new.try_into().unwrap(); //cant fail because
                                                                         //sel_session can never be
                                                                         //increased past 256
@@ -683,6 +1086,7 @@ impl Controller {
             GoToPrevSession => {
                 let s = self.sel_session()? + 1;
                 if s - 1 > 0 {
+                    self.mark_jump();
                     self.sel_session = (s - 1).try_into().unwrap(); //cant fail because
                                                                     //sel_session can never be
                                                                     //increased past 256
@@ -696,18 +1100,27 @@ impl Controller {
                 let s = self.sel_session()?;
                 let mut did_save = false;
                 match &self.sessions[s].canvas_file_path {
-                    Some(path) => match self.sessions[s].pixylene.borrow().save_canvas(&path) {
-                        Ok(()) => {
-                            self.console_out(
-                                &format!("saved to {}", path.display()),
-                                &LogType::Info,
-                            );
-                            did_save = true;
-                        }
-                        Err(err) => {
-                            self.console_out(&format!("failed to save: {}", err), &LogType::Error);
+                    Some(path) => {
+                        match save_canvas_file(
+                            &self.sessions[s].pixylene.borrow(),
+                            &path,
+                            self.config.canvas_encoding,
+                        ) {
+                            Ok(()) => {
+                                self.console_out(
+                                    &format!("saved to {}", path.display()),
+                                    &LogType::Info,
+                                );
+                                did_save = true;
+                            }
+                            Err(err) => {
+                                self.console_out(
+                                    &format!("failed to save: {}", err),
+                                    &LogType::Error,
+                                );
+                            }
                         }
-                    },
+                    }
                     None => {
                         let mut new_canvas_file_path: Option<PathBuf> = None;
                         match self.console_in("save path (.json): ") {
@@ -715,7 +1128,11 @@ impl Controller {
                                 self.console_out("saving...", &LogType::Info);
                                 let mut path = PathBuf::from(input.clone());
                                 path.set_extension("json");
-                                match self.sessions[s].pixylene.borrow().save_canvas(&path) {
+                                match save_canvas_file(
+                                    &self.sessions[s].pixylene.borrow(),
+                                    &path,
+                                    self.config.canvas_encoding,
+                                ) {
                                     Ok(()) => {
                                         self.console_out(
                                             &format!("saved to {}", path.display()),
@@ -747,7 +1164,7 @@ impl Controller {
                 let s = self.sel_session()?;
                 let mut did_save = false;
                 match &self.sessions[s].project_file_path {
-                    Some(path) => match self.sessions[s].pixylene.borrow().save_project(&path) {
+                    Some(path) => match save_project_file(&self.sessions[s], &path, &self.config) {
                         Ok(()) => {
                             self.console_out(
                                 &format!("saved to {}", path.display()),
@@ -766,7 +1183,7 @@ impl Controller {
                                 self.console_out("saving...", &LogType::Info);
                                 let mut path = PathBuf::from(input.clone());
                                 path.set_extension("pixylene");
-                                match self.sessions[s].pixylene.borrow().save_project(&path) {
+                                match save_project_file(&self.sessions[s], &path, &self.config) {
                                     Ok(()) => {
                                         self.console_out(
                                             &format!("saved to {}", path.display()),
@@ -794,14 +1211,19 @@ impl Controller {
                     self.sessions[s].modified = false;
                 }
             }
-            Export => {
+            Export { upscaler } => {
                 let s = self.sel_session()?;
                 //todo: instead of taking scaling factor, let user know canvas dimensions and then
                 //ask for both export width and height
+                let scale_prompt = match upscaler {
+                    Some(upscaler) => format!("scaling factor (after {}): ", upscaler),
+                    None => String::from("scaling factor: "),
+                };
                 match self.console_in("export path (.png): ") {
-                    Some(path) => match self.console_in("scaling factor: ") {
+                    Some(path) => match self.console_in(&scale_prompt) {
                         Some(input) => match str::parse::<u16>(&input) {
                             Ok(scale_up) => {
+                                let upscale = upscaler.map(|u| u.factor()).unwrap_or(1);
                                 let resize = PCoord::new(scale_up as u32, scale_up as u32)
                                     .map(|scale| {
                                         PCoordContainer::<u32>::from(
@@ -814,7 +1236,8 @@ impl Controller {
                                                 .dim(),
                                         )
                                         .0
-                                        .mul(scale)
+                                        .mul(PCoord::new(upscale, upscale).unwrap())
+                                        .and_then(|dim| dim.mul(scale))
                                     })
                                     .map_err(|_| {
                                         self.console_out(
@@ -831,11 +1254,11 @@ impl Controller {
                                 let mut path = PathBuf::from(path.clone());
                                 path.set_extension("png");
                                 self.console_out("exporting...", &LogType::Info);
-                                match self.sessions[s]
-                                    .pixylene
-                                    .borrow()
-                                    .export(Some(resize), &path)
-                                {
+                                match self.sessions[s].pixylene.borrow().export(
+                                    *upscaler,
+                                    Some(resize),
+                                    &path,
+                                ) {
                                     Ok(()) => {
                                         self.console_out(
                                             &format!("exported to {}", path.display()),
@@ -884,145 +1307,724 @@ impl Controller {
 
                 native_action_manager.redo(&mut pixylene.borrow_mut().project.canvas);
             }
+            UndoJump { seq } => {
+                let s = self.sel_session()?;
+                let PixyleneSession {
+                    ref mut native_action_manager,
+                    ref mut pixylene,
+                    ..
+                } = &mut self.sessions[s];
 
-            EnterNamespace { name } => {
-                if let Some(name) = name {
-                    if let Some(_) = self.config.possible_namespaces.get(name) {
-                        self.namespace = name.clone();
-                    } else {
-                        self.console_out(
-                            &format!("namespace '{}' doesn't exist", name),
-                            &LogType::Error,
-                        );
-                    }
-                } else {
-                    self.namespace = self.config.default_namespace.clone();
-                }
-            }
-            EnterDefaultNamespace => {
-                _ = self.perform_ui(&EnterNamespace {
-                    name: Some(self.config.default_namespace.clone()),
-                });
-            }
-            RunKey { key } => {
-                //special required keys
-                if *key == self.config.required_keys.force_quit {
-                    _ = self.perform_ui(&ForceQuit);
-                } else if *key == self.config.required_keys.start_command {
-                    _ = self.perform_ui(&RunCommandSpecify);
-                } else {
-                    //overlay keys (always triggerable)
-                    if let Some(funcs) = self
-                        .config
-                        .keymap
-                        .get(&None)
-                        .expect("None is always a key in keymap")
-                        .get(&key)
-                    {
-                        for func in (*funcs).clone() {
-                            _ = self.perform_ui(&func);
-                        }
-                    }
-                    //namespace keys
-                    else if let Some(funcs) = self
-                        .config
-                        .keymap
-                        .get(&Some(self.namespace.clone()))
-                        .expect("Namespace is always in possible namespaces in keymap")
-                        .get(&key)
-                    {
-                        for func in (*funcs).clone() {
-                            _ = self.perform_ui(&func);
-                        }
-                    } else {
-                        self.console_out(&format!("unmapped key: {:?}", key), &LogType::Warning);
-                    }
+                if !native_action_manager.jump(&mut pixylene.borrow_mut().project.canvas, *seq) {
+                    self.console_out(
+                        &format!("undo state {} doesn't exist", seq),
+                        &LogType::Error,
+                    );
                 }
             }
+            UndoEarlier => {
+                let s = self.sel_session()?;
+                let PixyleneSession {
+                    ref mut native_action_manager,
+                    ref mut pixylene,
+                    ..
+                } = &mut self.sessions[s];
 
-            RunCommandSpecify => {
-                self.console_clear();
-                if let Some(cmd) = self.console_in(":") {
-                    _ = self.perform_ui(&RunCommand { cmd });
-                } else {
-                    self.console_clear();
-                }
-            }
-            RunCommand { cmd } => {
-                //todo: ignore --help, -help, -h, & help
-                //todo: exlude separator ',' when error of arguments not provided
-                parse_cmd(cmd)
-                    .map(|uifn| {
-                        _ = self.perform_ui(&uifn);
-                    })
-                    .unwrap_or_else(|err| {
-                        self.console_out(
-                            &format!(
-                                "{}",
-                                err.lines()
-                                    .filter(|s| s.len() > 0)
-                                    .take_while(
-                                        |s| !s.contains("--help") && !s.contains("Commands:")
-                                    )
-                                    .map(|s| s.trim().to_owned())
-                                    .reduce(|a, b| a + ", " + &b)
-                                    .unwrap_or("".to_owned())
-                            ),
-                            &LogType::Error,
-                        );
-                    });
+                native_action_manager.earlier(&mut pixylene.borrow_mut().project.canvas);
             }
+            UndoLater => {
+                let s = self.sel_session()?;
+                let PixyleneSession {
+                    ref mut native_action_manager,
+                    ref mut pixylene,
+                    ..
+                } = &mut self.sessions[s];
 
-            RunActionSpecify => {
-                _ = self.sel_session()?;
-                if let Some(action_name) = self.console_in("action: ") {
-                    _ = self.perform_ui(&RunAction { name: action_name });
-                } else {
-                    self.console_clear();
-                }
+                native_action_manager.later(&mut pixylene.borrow_mut().project.canvas);
             }
 
-            RunNativeAction { name } => {
+            //Jumplist
+            JumpBack => {
                 let s = self.sel_session()?;
-
-                let Self {
-                    sessions,
-                    target,
-                    b_console,
-                    b_camera,
+                let PixyleneSession {
+                    ref mut jump_list,
+                    ref mut pixylene,
                     ..
-                } = self;
+                } = &mut self.sessions[s];
 
+                match jump_list {
+                    Some(jump_list) => {
+                        jump_list.jump_back(&mut pixylene.borrow_mut().project);
+                    }
+                    None => self.console_out("the jumplist is disabled", &LogType::Warning),
+                }
+            }
+            JumpForward => {
+                let s = self.sel_session()?;
                 let PixyleneSession {
+                    ref mut jump_list,
                     ref mut pixylene,
-                    ref mut native_action_map,
-                    ref mut native_action_manager,
-                    ref mut last_action_name,
-                    ref mut modified,
                     ..
-                } = &mut sessions[s];
-
-                let visible_target = ControllerLite {
-                    b_console: *b_console,
-                    discard_command: self.config.required_keys.discard_command.clone(),
-                    target: target.clone(),
-                };
-
-                match native_action_map.get(&name.clone()) {
-                    Some(action) => {
-                        target.borrow_mut().clear(&b_console);
+                } = &mut self.sessions[s];
 
-                        let performed = native_action_manager.perform(
-                            &mut pixylene.borrow_mut().project,
-                            &visible_target,
-                            action.clone(),
-                        );
+                match jump_list {
+                    Some(jump_list) => {
+                        jump_list.jump_forward(&mut pixylene.borrow_mut().project);
+                    }
+                    None => self.console_out("the jumplist is disabled", &LogType::Warning),
+                }
+            }
 
-                        match performed {
-                            Ok(()) => {
-                                if native_action_manager.commit(&pixylene.borrow().project.canvas) {
-                                    *last_action_name = Some(name.clone());
-                                    *modified = true;
+            RecordMacro { register } => match self.recording.take() {
+                Some((register, funcs)) => {
+                    self.console_out(
+                        &format!(
+                            "recorded {} functions into register '{}'",
+                            funcs.len(),
+                            register
+                        ),
+                        &LogType::Success,
+                    );
+                    self.registers.insert(register, funcs);
+                    self.save_macros();
+                }
+                None => {
+                    if let Some(register) = self.register(register, "record into register: ") {
+                        self.recording = Some((register, Vec::new()));
+                    }
+                }
+            },
+            PlayMacro { register, count } => {
+                let resolved = match self.register(register, "play register: ") {
+                    Some(resolved) => resolved,
+                    None => return Ok(()),
+                };
+                if register.is_none() {
+                    self.record(&PlayMacro {
+                        register: Some(resolved),
+                        count: *count,
+                    });
+                }
+                if self.playing.contains(&resolved) {
+                    self.console_out(
+                        &format!("register '{}' cannot play itself", resolved),
+                        &LogType::Error,
+                    );
+                    return Err(());
+                }
+                match self.registers.get(&resolved).cloned() {
+                    Some(funcs) => {
+                        self.playing.push(resolved);
+                        'playback: for _ in 0..count.unwrap_or(1) {
+                            for func in &funcs {
+                                if self.perform_ui(func).is_err() {
+                                    break 'playback;
+                                }
+                            }
+                        }
+                        self.playing.pop();
+                    }
+                    None => self.console_out(
+                        &format!("register '{}' is empty", resolved),
+                        &LogType::Warning,
+                    ),
+                }
+            }
+            EditMacro { register } => {
+                let register = match self.register(&Some(*register), "") {
+                    Some(register) => register,
+                    None => return Ok(()),
+                };
+                let contents = deparse(self.registers.get(&register).unwrap_or(&Vec::new()));
+                self.target.borrow_mut().clear_all();
+                self.target.borrow_mut().draw_paragraph(
+                    vec!["".into(), format!(" {} : {}", register, contents).into()],
+                    &self.b_camera,
+                );
+                let input = self.console_in(&format!(
+                    "new contents of register '{}' (empty to keep): ",
+                    register
+                ));
+                self.target.borrow_mut().clear_all();
+                match input {
+                    Some(input) if !input.trim().is_empty() => match parse_list(&input) {
+                        Ok(funcs) => {
+                            if funcs.is_empty() {
+                                self.registers.remove(&register);
+                            } else {
+                                self.registers.insert(register, funcs);
+                            }
+                            self.save_macros();
+                        }
+                        Err(err) => self.console_out(
+                            &format!("malformed macro: {}", err.message()),
+                            &LogType::Error,
+                        ),
+                    },
+                    _ => (),
+                }
+            }
+
+            Operator { name, count } => {
+                let s = self.sel_session()?;
+                if !self.sessions[s].operator_map.contains_key(name) {
+                    self.console_out(
+                        &format!("operator '{}' was not found", name),
+                        &LogType::Error,
+                    );
+                    return Err(());
+                }
+                self.cancel_operator();
+                let namespace = self.namespace.clone();
+                if self
+                    .config
+                    .possible_namespaces
+                    .contains_key(MOTION_NAMESPACE)
+                {
+                    self.namespace = MOTION_NAMESPACE.to_owned();
+                }
+                self.operator = Some((name.clone(), count.unwrap_or(1), namespace));
+            }
+            Motion { name, count } => {
+                let s = self.sel_session()?;
+                let (operator, operator_count, namespace) = match self.operator.take() {
+                    Some(operator) => operator,
+                    None => {
+                        self.console_out(
+                            &format!("no operator is waiting for motion '{}'", name),
+                            &LogType::Warning,
+                        );
+                        return Ok(());
+                    }
+                };
+                self.namespace = namespace;
+                let count = operator_count.saturating_mul(count.unwrap_or(1));
+
+                let Self {
+                    sessions,
+                    target,
+                    b_console,
+                    ..
+                } = self;
+                let session = &mut sessions[s];
+
+                let pixels = match session.motion_map.get(name) {
+                    Some(motion) => {
+                        cover(motion.as_ref(), &session.pixylene.borrow().project, count)
+                            .map_err(|err| err.to_string())
+                    }
+                    #[cfg(feature = "lua")]
+                    None if session
+                        .lua_action_manager
+                        .as_ref()
+                        .is_some_and(|manager| manager.list_motions().contains(name)) =>
+                    {
+                        let cursors = session
+                            .pixylene
+                            .borrow()
+                            .project
+                            .cursors()
+                            .map(|cursor| cursor.clone())
+                            .collect::<Vec<(UCoord, u16)>>();
+                        let manager = session.lua_action_manager.as_mut().unwrap(); //wont fail
+                                                                                    //because of
+                                                                                    //match guard
+                                                                                    //same as memento::cover, each pixel only once
+                        let mut covered = std::collections::HashSet::new();
+                        let mut pixels: Vec<(UCoord, u16)> = Vec::new();
+                        let mut result = Ok(());
+                        for cursor in cursors {
+                            match manager.invoke_motion(
+                                name,
+                                session.pixylene.clone(),
+                                &cursor,
+                                count,
+                            ) {
+                                Ok(coords) => {
+                                    for coord in coords {
+                                        if covered.insert((coord, cursor.1)) {
+                                            pixels.push((coord, cursor.1));
+                                        }
+                                    }
+                                }
+                                Err(err) => {
+                                    result = Err(err.to_string().replace("\n", ", "));
+                                    break;
+                                }
+                            }
+                        }
+                        result.map(|()| pixels)
+                    }
+                    None => Err(format!("motion '{}' was not found", name)),
+                };
+
+                let visible_target = ControllerLite {
+                    b_console: *b_console,
+                    discard_command: self.config.required_keys.discard_command.clone(),
+                    target: target.clone(),
+                };
+                let performed = pixels.and_then(|pixels| {
+                    let operation = Operation::new(session.operator_map[&operator].clone(), pixels);
+                    session
+                        .native_action_manager
+                        .perform(
+                            &mut session.pixylene.borrow_mut().project,
+                            &visible_target,
+                            Rc::new(RefCell::new(operation)),
+                            &Args::default(),
+                        )
+                        .map_err(|err| err.to_string())
+                });
+                match performed {
+                    Ok(()) => {
+                        if commit(
+                            &mut session.native_action_manager,
+                            &mut session.stroke,
+                            &mut session.pixylene.borrow_mut().project,
+                            &format!("{} {}", operator, name),
+                            &visible_target,
+                        ) {
+                            session.modified = true;
+                        }
+                        if let Some(jump_list) = &mut session.jump_list {
+                            jump_list.record(&session.pixylene.borrow().project);
+                        }
+                    }
+                    Err(err) => {
+                        target
+                            .borrow_mut()
+                            .console_out(&err, &LogType::Error, &b_console);
+                    }
+                }
+            }
+
+            Visual { point } => {
+                let s = self.sel_session()?;
+                self.cancel_visual();
+                let namespace = self.namespace.clone();
+                if self
+                    .config
+                    .possible_namespaces
+                    .contains_key(VISUAL_NAMESPACE)
+                {
+                    self.namespace = VISUAL_NAMESPACE.to_owned();
+                }
+                let anchor = self.sessions[s].pixylene.borrow().project.focus.0;
+                self.visual = Some(((!point).then_some(anchor), namespace));
+            }
+            VisualConfirm => {
+                let s = self.sel_session()?;
+                let anchor = match &self.visual {
+                    Some((anchor, _)) => *anchor,
+                    None => {
+                        self.console_out("not in visual mode", &LogType::Warning);
+                        return Ok(());
+                    }
+                };
+                let session = &mut self.sessions[s];
+                let mut pixylene = session.pixylene.borrow_mut();
+                let layer = pixylene.project.focus.1;
+                match anchor {
+                    //point selection toggles the cursor at the focus & stays in visual mode
+                    None => {
+                        let focus = pixylene.project.focus.0;
+                        let toggled = match visual_rectangle(focus, focus, &pixylene.project) {
+                            Some((corner, _)) => pixylene
+                                .project
+                                .toggle_cursor_at(&(corner, layer))
+                                .map_err(|err| err.to_string()),
+                            None => Err("focus is outside the canvas".to_owned()),
+                        };
+                        drop(pixylene);
+                        if let Err(err) = toggled {
+                            self.console_out(&err, &LogType::Error);
+                        }
+                    }
+                    //rectangle selection adds the cursors of the rectangle & leaves visual mode
+                    Some(anchor) => {
+                        let focus = pixylene.project.focus.0;
+                        if let Some((start, end)) =
+                            visual_rectangle(anchor, focus, &pixylene.project)
+                        {
+                            for x in start.x..=end.x {
+                                for y in start.y..=end.y {
+                                    let cursor = (UCoord { x, y }, layer);
+                                    if !pixylene.project.is_cursor_at(&cursor).unwrap_or(true) {
+                                        _ = pixylene.project.toggle_cursor_at(&cursor);
+                                    }
+                                }
+                            }
+                        }
+                        if let Some(jump_list) = &mut session.jump_list {
+                            jump_list.record(&pixylene.project);
+                        }
+                        drop(pixylene);
+                        self.cancel_visual();
+                    }
+                }
+            }
+
+            Eyedropper { x, y } => {
+                let s = self.sel_session()?;
+                let coord = match (x, y) {
+                    (Some(x), Some(y)) => Some(UCoord { x: *x, y: *y }),
+                    (None, None) => None,
+                    _ => {
+                        self.console_out(
+                            "both x & y are needed to pick a coordinate",
+                            &LogType::Error,
+                        );
+                        return Err(());
+                    }
+                };
+
+                let Self {
+                    sessions,
+                    target,
+                    b_console,
+                    ..
+                } = self;
+                let session = &mut sessions[s];
+
+                let visible_target = ControllerLite {
+                    b_console: *b_console,
+                    discard_command: self.config.required_keys.discard_command.clone(),
+                    target: target.clone(),
+                };
+                let performed = session.native_action_manager.perform(
+                    &mut session.pixylene.borrow_mut().project,
+                    &visible_target,
+                    Rc::new(RefCell::new(palette::Eyedropper::new(
+                        coord,
+                        self.config.eyedropper_merged,
+                    ))),
+                    &Args::default(),
+                );
+                match performed {
+                    Ok(()) => {
+                        if commit(
+                            &mut session.native_action_manager,
+                            &mut session.stroke,
+                            &mut session.pixylene.borrow_mut().project,
+                            "eyedropper",
+                            &visible_target,
+                        ) {
+                            session.modified = true;
+                        }
+                    }
+                    Err(err) => {
+                        target.borrow_mut().console_out(
+                            &err.to_string(),
+                            &LogType::Error,
+                            &b_console,
+                        );
+                    }
+                }
+            }
+
+            Tool { name } => {
+                let s = self.sel_session()?;
+                if self.sessions[s].tool_map.contains_key(name) {
+                    self.lift_pen();
+                    self.sessions[s].tool = name.clone();
+                } else {
+                    self.console_out(&format!("tool '{}' was not found", name), &LogType::Error);
+                }
+            }
+
+            ToolOption { option, value } => {
+                let s = self.sel_session()?;
+                let session = &mut self.sessions[s];
+                match session.tool_options.set(option, value) {
+                    Ok(()) => {
+                        //a stroke carries on with the new options
+                        if let Some(stroke) = &mut session.stroke {
+                            stroke.options = session.tool_options.clone();
+                        }
+                    }
+                    Err(err) => {
+                        self.console_out(&err.to_string(), &LogType::Error);
+                    }
+                }
+            }
+
+            ToolPattern => {
+                let s = self.sel_session()?;
+                let session = &mut self.sessions[s];
+                let pattern = pattern_at_cursors(&session.pixylene.borrow().project);
+                match pattern {
+                    Ok(pattern) => {
+                        session.tool_options.pattern = Some(pattern);
+                        if let Some(stroke) = &mut session.stroke {
+                            stroke.options = session.tool_options.clone();
+                        }
+                    }
+                    Err(err) => {
+                        self.console_out(&err, &LogType::Error);
+                    }
+                }
+            }
+
+            PenDown => {
+                let s = self.sel_session()?;
+                let Self {
+                    sessions,
+                    target,
+                    b_console,
+                    ..
+                } = self;
+                let session = &mut sessions[s];
+                if session.stroke.is_some() {
+                    return Ok(());
+                }
+
+                let visible_target = ControllerLite {
+                    b_console: *b_console,
+                    discard_command: self.config.required_keys.discard_command.clone(),
+                    target: target.clone(),
+                };
+                match Stroke::new(
+                    session.tool_map[&session.tool].clone(), //wont fail because only tools in
+                    //the map can be chosen
+                    session.tool_options.clone(),
+                    &mut session.pixylene.borrow_mut().project,
+                    &visible_target,
+                ) {
+                    Ok(stroke) => {
+                        session.stroke = Some(stroke);
+                    }
+                    Err(err) => {
+                        target.borrow_mut().console_out(
+                            &err.to_string(),
+                            &LogType::Error,
+                            &b_console,
+                        );
+                    }
+                }
+            }
+
+            PenUp => {
+                self.sel_session()?;
+                self.lift_pen();
+            }
+
+            TogglePen => {
+                let s = self.sel_session()?;
+                if self.sessions[s].stroke.is_some() {
+                    self.lift_pen();
+                } else {
+                    _ = self.perform_ui(&PenDown);
+                }
+            }
+
+            EnterNamespace { name } => {
+                if let Some(name) = name {
+                    if let Some(_) = self.config.possible_namespaces.get(name) {
+                        self.namespace = name.clone();
+                    } else {
+                        self.console_out(
+                            &format!("namespace '{}' doesn't exist", name),
+                            &LogType::Error,
+                        );
+                    }
+                } else {
+                    self.namespace = self.config.default_namespace.clone();
+                }
+            }
+            EnterDefaultNamespace => {
+                _ = self.perform_ui(&EnterNamespace {
+                    name: Some(self.config.default_namespace.clone()),
+                });
+            }
+            RunKey { key } => {
+                self.expire_keys();
+                //special required keys
+                if *key == self.config.required_keys.force_quit {
+                    _ = self.perform_ui(&ForceQuit);
+                } else if *key == self.config.required_keys.start_command {
+                    self.count = None;
+                    //pending keys are kept for the command so that :lk can list their completions
+                    _ = self.perform_ui(&RunCommandSpecify);
+                    self.pending_keys.clear();
+                } else if (self.count.is_some()
+                    || self.operator.is_some()
+                    || self.visual.is_some()
+                    || !self.pending_keys.is_empty())
+                    && *key == self.config.required_keys.discard_command
+                {
+                    //discards what is pending, leaving visual mode only once nothing else is
+                    if self.count.is_none()
+                        && self.operator.is_none()
+                        && self.pending_keys.is_empty()
+                    {
+                        self.cancel_visual();
+                    }
+                    self.count = None;
+                    self.pending_keys.clear();
+                    self.cancel_operator();
+                } else if let Some(digit) = self.count_digit(key) {
+                    self.count = Some(
+                        self.count
+                            .unwrap_or(0)
+                            .saturating_mul(10)
+                            .saturating_add(digit)
+                            .min(MAX_COUNT),
+                    );
+                } else {
+                    self.pending_keys.push(key.clone());
+                    self.key_time = Instant::now();
+                    let keys = KeySeq::from(self.pending_keys.clone());
+
+                    //overlay keys (always triggerable), then namespace keys
+                    let bound = [None, Some(self.namespace.clone())]
+                        .iter()
+                        .filter_map(|namespace| self.config.keymap.get(namespace))
+                        .find_map(|map| match map.get(&keys) {
+                            Some(funcs) => Some(Some(funcs.clone())),
+                            None => map
+                                .keys()
+                                .any(|bound| bound.starts_with(&keys))
+                                .then_some(None),
+                        });
+                    match bound {
+                        Some(Some(funcs)) => {
+                            self.pending_keys.clear();
+                            let count = self.count.take();
+                            self.run_counted(funcs, count);
+                        }
+                        //wait for the rest of the sequence
+                        Some(None) => (),
+                        None => {
+                            self.pending_keys.clear();
+                            self.count = None;
+                            self.cancel_operator();
+                            self.console_out(&format!("unmapped key: {}", keys), &LogType::Warning);
+                        }
+                    }
+                }
+            }
+
+            RunCommandSpecify => {
+                self.console_clear();
+                if let Some(cmd) = self.console_in(":") {
+                    if let Ok(uifn) = parse_cmd(&cmd) {
+                        self.record(&uifn);
+                    }
+                    _ = self.perform_ui(&RunCommand { cmd });
+                } else {
+                    self.console_clear();
+                }
+            }
+            RunCommand { cmd } => {
+                //todo: ignore --help, -help, -h, & help
+                //todo: exlude separator ',' when error of arguments not provided
+                parse_cmd(cmd)
+                    .map(|uifn| {
+                        _ = self.perform_ui(&uifn);
+                    })
+                    .unwrap_or_else(|err| {
+                        self.console_out(
+                            &format!(
+                                "{}",
+                                err.lines()
+                                    .filter(|s| s.len() > 0)
+                                    .take_while(
+                                        |s| !s.contains("--help") && !s.contains("Commands:")
+                                    )
+                                    .map(|s| s.trim().to_owned())
+                                    .reduce(|a, b| a + ", " + &b)
+                                    .unwrap_or("".to_owned())
+                            ),
+                            &LogType::Error,
+                        );
+                    });
+            }
+
+            RunActionSpecify => {
+                _ = self.sel_session()?;
+                if let Some(input) = self.console_in("action: ") {
+                    match shlex::split(&input) {
+                        Some(mut words) if !words.is_empty() => {
+                            let name = words.remove(0);
+                            let func = RunAction { name, args: words };
+                            self.record(&func);
+                            _ = self.perform_ui(&func);
+                        }
+                        _ => self.console_out("malformed input", &LogType::Error),
+                    }
+                } else {
+                    self.console_clear();
+                }
+            }
+
+            RunNativeAction { name, args } => {
+                let s = self.sel_session()?;
+
+                let Self {
+                    sessions,
+                    target,
+                    b_console,
+                    b_camera,
+                    ..
+                } = self;
+
+                let PixyleneSession {
+                    ref mut pixylene,
+                    ref mut native_action_map,
+                    ref mut native_action_manager,
+                    ref mut stroke,
+                    ref mut jump_list,
+                    ref mut last_action,
+                    ref mut modified,
+                    ..
+                } = &mut sessions[s];
+
+                let visible_target = ControllerLite {
+                    b_console: *b_console,
+                    discard_command: self.config.required_keys.discard_command.clone(),
+                    target: target.clone(),
+                };
+
+                match native_action_map.get(&name.clone()) {
+                    Some(action) => {
+                        target.borrow_mut().clear(&b_console);
+
+                        let params = action.borrow().params();
+                        let performed = Args::parse(params, args).and_then(|args| {
+                            native_action_manager.perform(
+                                &mut pixylene.borrow_mut().project,
+                                &visible_target,
+                                action.clone(),
+                                &args,
+                            )
+                        });
+
+                        match performed {
+                            Ok(()) => {
+                                let (changes_canvas, is_step) = {
+                                    let action = action.borrow();
+                                    (action.changes_canvas(), action.is_step())
+                                };
+                                //the canvas is only diffed after actions that can change it,
+                                //though any action moves a stroke along
+                                if (changes_canvas || stroke.is_some())
+                                    && commit(
+                                        native_action_manager,
+                                        stroke,
+                                        &mut pixylene.borrow_mut().project,
+                                        &name,
+                                        &visible_target,
+                                    )
+                                {
+                                    *last_action = Some((name.clone(), args.clone()));
+                                    *modified = true;
+                                }
+                                if let Some(jump_list) = jump_list {
+                                    if is_step {
+                                        jump_list.follow(&pixylene.borrow().project);
+                                    } else {
+                                        jump_list.record(&pixylene.borrow().project);
+                                    }
                                 }
                             }
                             Err(err) => {
@@ -1066,7 +2068,7 @@ impl Controller {
             }
 
             #[cfg(feature = "lua")]
-            RunLuaAction { name } => {
+            RunLuaAction { name, args } => {
                 let s = self.sel_session()?;
 
                 let Self {
@@ -1080,9 +2082,11 @@ impl Controller {
                 let PixyleneSession {
                     ref mut pixylene,
                     ref mut native_action_manager,
+                    ref mut stroke,
+                    ref mut jump_list,
                     ref mut lua_action_map,
                     ref mut lua_action_manager,
-                    ref mut last_action_name,
+                    ref mut last_action,
                     ref mut modified,
                     ..
                 } = &mut sessions[s];
@@ -1105,13 +2109,26 @@ impl Controller {
                             //shouldn't contain any actions
                             //at all (check lua_action_map
                             //in fn new_session)
-                            .invoke_action(&name, pixylene.clone(), Rc::new(visible_target))
-                        {
+                            .invoke_action(
+                                &name,
+                                args,
+                                pixylene.clone(),
+                                Rc::new(visible_target.clone()),
+                            ) {
                             Ok(()) => {
-                                if native_action_manager.commit(&pixylene.borrow().project.canvas) {
-                                    *last_action_name = Some(name.clone());
+                                if commit(
+                                    native_action_manager,
+                                    stroke,
+                                    &mut pixylene.borrow_mut().project,
+                                    &name,
+                                    &visible_target,
+                                ) {
+                                    *last_action = Some((name.clone(), args.clone()));
                                     *modified = true;
                                 }
+                                if let Some(jump_list) = jump_list {
+                                    jump_list.record(&pixylene.borrow().project);
+                                }
                             }
                             Err(err) => {
                                 use colored::Colorize;
@@ -1153,7 +2170,7 @@ impl Controller {
                 }
             }
 
-            RunAction { name } => {
+            RunAction { name, args } => {
                 let s = self.sel_session()?;
 
                 let Self {
@@ -1168,17 +2185,26 @@ impl Controller {
                 #[cfg(feature = "lua")]
                 {
                     if session.native_action_map.get(name).is_some() {
-                        let _ = self.perform_ui(&RunNativeAction { name: name.clone() });
+                        let _ = self.perform_ui(&RunNativeAction {
+                            name: name.clone(),
+                            args: args.clone(),
+                        });
                         return Ok(());
                     } else if session.lua_action_map.get(name).is_some() {
-                        let _ = self.perform_ui(&RunLuaAction { name: name.clone() });
+                        let _ = self.perform_ui(&RunLuaAction {
+                            name: name.clone(),
+                            args: args.clone(),
+                        });
                         return Ok(());
                     }
                 }
                 #[cfg(not(feature = "lua"))]
                 {
                     if session.native_action_map.get(name).is_some() {
-                        let _ = self.perform_ui(&RunNativeAction { name: name.clone() });
+                        let _ = self.perform_ui(&RunNativeAction {
+                            name: name.clone(),
+                            args: args.clone(),
+                        });
                         return Ok(());
                     }
                 }
@@ -1196,9 +2222,10 @@ impl Controller {
 
             RunLastAction => {
                 let s = self.sel_session()?;
-                if let Some(action_name) = &self.sessions[s].last_action_name {
+                if let Some((name, args)) = &self.sessions[s].last_action {
                     _ = self.perform_ui(&RunAction {
-                        name: action_name.clone(),
+                        name: name.clone(),
+                        args: args.clone(),
                     });
                 } else {
                     self.console_out("no previous action to repeat", &LogType::Warning);
@@ -1209,7 +2236,9 @@ impl Controller {
             RunLuaSpecify => {
                 _ = self.sel_session()?;
                 if let Some(statement) = self.console_in("lua statement: ") {
-                    _ = self.perform_ui(&RunLua { statement });
+                    let func = RunLua { statement };
+                    self.record(&func);
+                    _ = self.perform_ui(&func);
                 } else {
                     self.console_clear();
                 }
@@ -1235,6 +2264,8 @@ impl Controller {
                 let PixyleneSession {
                     ref mut pixylene,
                     ref mut native_action_manager,
+                    ref mut stroke,
+                    ref mut jump_list,
                     ref mut lua_action_manager,
                     ref mut modified,
                     ..
@@ -1252,12 +2283,21 @@ impl Controller {
                 match lua_action_manager
                     .as_mut()
                     .unwrap() //cant fail because checked like 2 lines ago
-                    .invoke(statement, pixylene.clone(), Rc::new(visible_target))
+                    .invoke(statement, pixylene.clone(), Rc::new(visible_target.clone()))
                 {
                     Ok(()) => {
-                        if native_action_manager.commit(&pixylene.borrow().project.canvas) {
+                        if commit(
+                            native_action_manager,
+                            stroke,
+                            &mut pixylene.borrow_mut().project,
+                            statement,
+                            &visible_target,
+                        ) {
                             *modified = true;
                         }
+                        if let Some(jump_list) = jump_list {
+                            jump_list.record(&pixylene.borrow().project);
+                        }
                     }
                     Err(err) => {
                         use colored::Colorize;
@@ -1291,8 +2331,38 @@ impl Controller {
                     session.pixylene.borrow().project.out_dim,
                     match session.pixylene.borrow().project.render_layer() {
                         //Focus is in the bounds of selected session's project's canvas
-                        //Send the project-rendered pixels
-                        Ok(o_pixels) => o_pixels,
+                        //Send the project-rendered pixels, previewing the visual rectangle as
+                        //cursors
+                        Ok(mut o_pixels) => {
+                            let project = &session.pixylene.borrow().project;
+                            if let Some((Some(anchor), _)) = &self.visual {
+                                if let Some((start, end)) =
+                                    visual_rectangle(*anchor, project.focus.0, project)
+                                {
+                                    for o_pixel in o_pixels.iter_mut() {
+                                        match o_pixel {
+                                            OPixel::Filled {
+                                                scene_coord,
+                                                has_cursor,
+                                                ..
+                                            }
+                                            | OPixel::Empty {
+                                                scene_coord,
+                                                has_cursor,
+                                            } => {
+                                                if (start.x..=end.x).contains(&scene_coord.x)
+                                                    && (start.y..=end.y).contains(&scene_coord.y)
+                                                {
+                                                    *has_cursor = true;
+                                                }
+                                            }
+                                            OPixel::OutOfScene => (),
+                                        }
+                                    }
+                                }
+                            }
+                            o_pixels
+                        }
 
                         //Focus is not in the bounds of selected session's project's canvas
                         //Send a dummy project pixel to indicate empty
@@ -1318,7 +2388,7 @@ impl Controller {
                 self.console_in("press ENTER to stop previewing project");
             }
 
-            PrintCanvasJson => {
+            PrintCanvasJson { encoding } => {
                 let s = self.sel_session()?;
                 let session = &mut self.sessions[s];
                 self.target.borrow_mut().draw_paragraph(
@@ -1327,7 +2397,7 @@ impl Controller {
                         .borrow()
                         .project
                         .canvas
-                        .to_json()
+                        .to_json_encoded(encoding.unwrap_or(self.config.canvas_encoding))
                         .unwrap_or_else(|err| err.to_string())
                         .into()],
                     &self.b_camera,
@@ -1371,11 +2441,84 @@ impl Controller {
                 self.target.borrow_mut().clear_all();
             }
 
+            ListUndoBranches => {
+                use colored::{ColoredString, Colorize};
+                let s = self.sel_session()?;
+                let action_manager = &self.sessions[s].native_action_manager;
+                let current = action_manager.state();
+                let branches = action_manager.branches();
+                let mut paragraph: Vec<ColoredString> = vec![
+                    "".into(),
+                    "Undo Branches".underline().bright_yellow(),
+                    format!(
+                        " {:<10} {:<10} {:<10} {}",
+                        "number", "changes", "when", "action"
+                    )
+                    .into(),
+                ];
+                for branch in &branches {
+                    let line = format!(
+                        " {:<10} {:<10} {:<10} {}",
+                        branch.seq.to_string().bright_magenta(),
+                        branch.changes,
+                        time_ago(branch.time),
+                        branch.name,
+                    );
+                    paragraph.push(if branch.seq == current.seq {
+                        format!("{} (current)", line).bright_white()
+                    } else {
+                        line.into()
+                    });
+                }
+                if !branches.iter().any(|branch| branch.seq == current.seq) {
+                    paragraph.push(
+                        format!(
+                            " at {} with {} changes, {} ({})",
+                            current.seq,
+                            current.changes,
+                            time_ago(current.time),
+                            current.name,
+                        )
+                        .into(),
+                    );
+                }
+                self.target.borrow_mut().clear_all();
+                self.target
+                    .borrow_mut()
+                    .draw_paragraph(paragraph, &self.b_camera);
+                let _ = self.console_in("press ENTER to exit listing undo branches");
+                self.target.borrow_mut().clear_all();
+            }
+
+            ListMacros => {
+                use colored::{ColoredString, Colorize};
+                let mut registers = self.registers.iter().collect::<Vec<_>>();
+                registers.sort_by_key(|(register, _)| **register);
+                let mut paragraph: Vec<ColoredString> =
+                    vec!["".into(), "Macros".underline().bright_yellow()];
+                for (register, funcs) in registers {
+                    paragraph.push(
+                        format!(
+                            " {} : {}",
+                            register.to_string().bright_magenta(),
+                            deparse(funcs)
+                        )
+                        .into(),
+                    );
+                }
+                self.target.borrow_mut().clear_all();
+                self.target
+                    .borrow_mut()
+                    .draw_paragraph(paragraph, &self.b_camera);
+                let _ = self.console_in("press ENTER to exit listing macros");
+                self.target.borrow_mut().clear_all();
+            }
+
             ListKeybindMap { namespace } => {
                 use colored::{ColoredString, Colorize};
                 let half_width = self.target.borrow().get_size().y() as usize / 2;
                 let print_namespace_simple =
-                    |keys: Iter<Key, Vec<UiFn>>, paragraph: &mut Vec<ColoredString>| {
+                    |keys: Iter<KeySeq, Vec<UiFn>>, paragraph: &mut Vec<ColoredString>| {
                         let _ = keys
                             .map(|(key, ui_fns)| {
                                 paragraph.push(
@@ -1398,7 +2541,7 @@ impl Controller {
                     };
 
                 let print_namespace_compact =
-                    |mut keys: Iter<Key, Vec<UiFn>>, paragraph: &mut Vec<ColoredString>| loop {
+                    |mut keys: Iter<KeySeq, Vec<UiFn>>, paragraph: &mut Vec<ColoredString>| loop {
                         let mut line = String::new();
                         if let Some((key, ui_fns)) = keys.next() {
                             line.push_str(&format!(
@@ -1438,6 +2581,25 @@ impl Controller {
 
                 self.target.borrow_mut().clear_all();
 
+                //keys that complete the pending keys
+                if !self.pending_keys.is_empty() {
+                    let pending = KeySeq::from(self.pending_keys.clone());
+                    let completions = [None, Some(self.namespace.clone())]
+                        .iter()
+                        .filter_map(|namespace| self.config.keymap.get(namespace))
+                        .flatten()
+                        .filter(|(keys, _)| keys.starts_with(&pending))
+                        .map(|(keys, funcs)| (keys.clone(), funcs.clone()))
+                        .collect::<HashMap<KeySeq, Vec<UiFn>>>();
+                    paragraph.push(ColoredString::from(""));
+                    paragraph.push(
+                        format!("Completions of '{pending}'")
+                            .underline()
+                            .bright_yellow(),
+                    );
+                    print_namespace_simple(completions.iter(), &mut paragraph);
+                }
+
                 match namespace {
                     //list required keys and keys in default namespace
                     None => {
@@ -1551,6 +2713,57 @@ impl Controller {
                     statusline.push(divider.clone());
                 }
 
+                if self.count.is_some() || self.operator.is_some() || !self.pending_keys.is_empty()
+                {
+                    //Pending operator, count & keys
+                    let pending = self
+                        .operator
+                        .as_ref()
+                        .map(|(operator, ..)| operator.clone())
+                        .into_iter()
+                        .chain(self.count.map(|count| count.to_string()))
+                        .chain(
+                            (!self.pending_keys.is_empty())
+                                .then(|| KeySeq::from(self.pending_keys.clone()).to_string()),
+                        )
+                        .collect::<Vec<String>>()
+                        .join(" ");
+                    statusline.push(padding.clone());
+                    statusline.push(divider.clone());
+                    statusline.push(pending.on_truecolor(60, 60, 60).bright_white());
+                    statusline.push(divider.clone());
+                }
+
+                if let Some((anchor, _)) = &self.visual {
+                    //Visual mode with the dimensions of the rectangle
+                    let project = &session.pixylene.borrow().project;
+                    let visual = match anchor {
+                        Some(anchor) => match visual_rectangle(*anchor, project.focus.0, project) {
+                            Some((start, end)) => {
+                                format!("visual {}x{}", end.x - start.x + 1, end.y - start.y + 1)
+                            }
+                            None => "visual 0x0".to_owned(),
+                        },
+                        None => "visual point".to_owned(),
+                    };
+                    statusline.push(padding.clone());
+                    statusline.push(divider.clone());
+                    statusline.push(visual.on_truecolor(60, 60, 60).bright_white());
+                    statusline.push(divider.clone());
+                }
+
+                if let Some((register, _)) = &self.recording {
+                    //Macro being recorded
+                    statusline.push(padding.clone());
+                    statusline.push(divider.clone());
+                    statusline.push(
+                        format!("recording @{}", register)
+                            .on_truecolor(60, 60, 60)
+                            .bright_red(),
+                    );
+                    statusline.push(divider.clone());
+                }
+
                 statusline.push(padding.clone());
 
                 {
@@ -1669,16 +2882,54 @@ impl Controller {
 
                 statusline.push(padding.clone());
 
+                {
+                    //Tool & its options
+                    statusline.push(divider.clone());
+                    statusline.push(
+                        format!("Tool: {} {}", session.tool, session.tool_options)
+                            .on_truecolor(60, 60, 60)
+                            .bright_white(),
+                    );
+                    if session.stroke.is_some() {
+                        statusline.push(spacing.clone());
+                        statusline.push("pen down".on_truecolor(60, 60, 60).bright_yellow());
+                    }
+                    statusline.push(divider.clone());
+                }
+
+                statusline.push(padding.clone());
+
+                {
+                    //Undo state
+                    statusline.push(divider.clone());
+                    let action_manager = &session.native_action_manager;
+                    let branches = action_manager.num_branches();
+                    statusline.push(
+                        format!(
+                            "Undo {}/{}{}",
+                            action_manager.state().seq,
+                            action_manager.last_seq(),
+                            if branches > 1 {
+                                format!(", {} branches", branches)
+                            } else {
+                                String::new()
+                            },
+                        )
+                        .on_truecolor(60, 60, 60)
+                        .bright_white(),
+                    );
+                    statusline.push(divider.clone());
+                }
+                statusline.push(padding.clone());
+
                 {
                     //Palette
                     statusline.push(divider.clone());
                     statusline.push("Palette: ".on_truecolor(60, 60, 60).bright_white());
-                    let mut colors_summary = session
-                        .pixylene
-                        .borrow()
-                        .project
-                        .canvas
-                        .palette
+                    let pixylene = session.pixylene.borrow();
+                    let palette = &pixylene.project.canvas.palette;
+                    let secondary = palette.get_secondary_index();
+                    let mut colors_summary = palette
                         .colors()
                         .map(|(a, b, c)| (a.clone(), b.clone(), c))
                         .take(16)
@@ -1690,6 +2941,11 @@ impl Controller {
                                 .on_truecolor(color.r, color.g, color.b)
                                 .white()
                                 .underline()
+                        } else if Some(index) == secondary {
+                            format!(" {: <3}", index)
+                                .on_truecolor(color.r, color.g, color.b)
+                                .white()
+                                .italic()
                         } else {
                             format!(" {: <3}", index)
                                 .on_truecolor(color.r, color.g, color.b)
@@ -1697,6 +2953,23 @@ impl Controller {
                         });
                     }
                     statusline.push(divider.clone());
+
+                    //Recent colors
+                    let recent = palette
+                        .recent()
+                        .map(|(a, b)| (a.clone(), b.clone()))
+                        .collect::<Vec<(u8, TruePixel)>>();
+                    if recent.len() > 0 {
+                        statusline.push("Recent: ".on_truecolor(60, 60, 60).bright_white());
+                        for (index, color) in recent {
+                            statusline.push(
+                                format!(" {: <3}", index)
+                                    .on_truecolor(color.r, color.g, color.b)
+                                    .white(),
+                            );
+                        }
+                        statusline.push(divider.clone());
+                    }
                 }
 
                 self.target
@@ -1708,6 +2981,150 @@ impl Controller {
     }
 }
 
+//commits the canvas as the result of the named function, returning whether it changed, unless the
+//pen is down, in which case the function has only moved the cursors (as the pen is lifted before
+//any other) & the tool is applied along their moves, the stroke being committed as a whole when the
+//pen is lifted
+fn commit(
+    native_action_manager: &mut ActionManager,
+    stroke: &mut Option<Stroke>,
+    project: &mut libpixylene::project::Project,
+    name: &str,
+    console: &dyn Console,
+) -> bool {
+    match stroke {
+        Some(stroke) => {
+            if let Err(err) = stroke.extend(project, console) {
+                console.cmdout(&err.to_string(), &LogType::Error);
+            }
+            false
+        }
+        None => native_action_manager.commit(&project.canvas, name),
+    }
+}
+
+//takes a custom pattern from the present pixels of the focused layer within the bounding box of
+//the cursors on it
+fn pattern_at_cursors(project: &libpixylene::project::Project) -> Result<Pattern, String> {
+    let layer = project.focus.1;
+    let (start, end) = project.cursor_bounds().remove(&layer).ok_or_else(|| {
+        String::from("need cursors on the focused layer around the pixels to take the pattern from")
+    })?;
+    //wont fail because the bounding box has at least one pixel
+    let dim = PCoord::new(end.x - start.x + 1, end.y - start.y + 1).unwrap();
+    match &project.canvas.layers {
+        LayersType::True(layers) => layers
+            .get_layer(layer)
+            .map_err(|err| err.to_string())
+            .and_then(|layer| {
+                Pattern::from_region(&layer.scene, start, dim).map_err(|err| err.to_string())
+            }),
+        LayersType::Indexed(layers) => layers
+            .get_layer(layer)
+            .map_err(|err| err.to_string())
+            .and_then(|layer| {
+                Pattern::from_region(&layer.scene, start, dim).map_err(|err| err.to_string())
+            }),
+    }
+}
+
+fn macros_path() -> Option<PathBuf> {
+    let mut path = dirs::config_dir()?;
+    path.push("pixylene");
+    path.push("macros");
+    path.set_extension("toml");
+    Some(path)
+}
+
+// macros are stored as one line per register, in the same format as they are listed
+fn load_macros(path: &Path) -> Result<HashMap<char, Vec<UiFn>>, String> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        //macros file not present
+        _ => return Ok(HashMap::new()),
+    };
+    let table = toml::from_str::<toml::Table>(&contents)
+        .map_err(|err| format!("Error in macros file: {}", err.message()))?;
+    let mut registers = HashMap::new();
+    for (register, funcs) in table {
+        let mut chars = register.chars();
+        match (chars.next(), chars.next()) {
+            (Some(register), None) => {
+                registers.insert(
+                    register,
+                    parse_list(&funcs.to_string()).map_err(|err| {
+                        format!(
+                            "Error in macros file, register '{}': {}",
+                            register,
+                            err.message()
+                        )
+                    })?,
+                );
+            }
+            _ => {
+                return Err(format!(
+                    "Error in macros file: invalid register '{}'",
+                    register
+                ))
+            }
+        }
+    }
+    Ok(registers)
+}
+
+fn save_macros(path: &Path, registers: &HashMap<char, Vec<UiFn>>) -> Result<(), String> {
+    let mut registers = registers.iter().collect::<Vec<_>>();
+    registers.sort_by_key(|(register, _)| **register);
+    let contents = registers
+        .iter()
+        .map(|(register, funcs)| format!("{} = {}\n", register, deparse(funcs)))
+        .collect::<String>();
+    if let Some(dir) = path.parent() {
+        _ = std::fs::create_dir_all(dir);
+    }
+    std::fs::write(path, contents).map_err(|err| format!("failed to save macros: {}", err))
+}
+
+// returns how long ago the time was, in the largest whole unit
+fn time_ago(time: SystemTime) -> String {
+    let secs = time.elapsed().map(|elapsed| elapsed.as_secs()).unwrap_or(0);
+    match secs {
+        0..=59 => format!("{}s ago", secs),
+        60..=3599 => format!("{}m ago", secs / 60),
+        3600..=86399 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86400),
+    }
+}
+
+//the corners of the rectangle between the two coordinates that lies in the canvas, if any
+fn visual_rectangle(
+    anchor: Coord,
+    focus: Coord,
+    project: &libpixylene::project::Project,
+) -> Option<(UCoord, UCoord)> {
+    let dim = project.canvas.layers.dim();
+    let start = Coord {
+        x: anchor.x.min(focus.x).max(0),
+        y: anchor.y.min(focus.y).max(0),
+    };
+    let end = Coord {
+        x: anchor.x.max(focus.x).min(i32::from(dim.x()) - 1),
+        y: anchor.y.max(focus.y).min(i32::from(dim.y()) - 1),
+    };
+    (start.x <= end.x && start.y <= end.y).then(|| {
+        (
+            UCoord {
+                x: start.x as u16,
+                y: start.y as u16,
+            },
+            UCoord {
+                x: end.x as u16,
+                y: end.y as u16,
+            },
+        )
+    })
+}
+
 // returns boundaries of camera, statusline and console respectively
 fn compute_boundaries(window: &PCoord, padding: u8) -> (Rectangle, Rectangle, Rectangle) {
     (
@@ -1742,6 +3159,37 @@ fn compute_boundaries(window: &PCoord, padding: u8) -> (Rectangle, Rectangle, Re
     )
 }
 
+/// Saves the session to a project file, along with its undo history if configured to
+fn save_project_file(
+    session: &PixyleneSession,
+    path: &PathBuf,
+    config: &Config,
+) -> Result<(), PixyleneError> {
+    let history = config.save_undo_history.then(|| {
+        session
+            .native_action_manager
+            .history(config.max_saved_undo_memory)
+    });
+    session.pixylene.borrow().save_project_with_history(
+        path,
+        config.compress_projects,
+        history.as_ref(),
+    )
+}
+
+/// Saves to a canvas file, or to a human-readable project file if the path has its extension
+fn save_canvas_file(
+    pixylene: &Pixylene,
+    path: &PathBuf,
+    encoding: SceneEncoding,
+) -> Result<(), PixyleneError> {
+    if ProjectFile::is_json_path(path) {
+        pixylene.save_project(path, false)
+    } else {
+        pixylene.save_canvas(path, encoding)
+    }
+}
+
 fn initialize_project(pixylene: &mut Pixylene) {
     let dim = pixylene.project.canvas.layers.dim();
 
@@ -1764,3 +3212,449 @@ fn initialize_project(pixylene: &mut Pixylene) {
         .unwrap(); //cant fail because x,y less than dim and we know there is at
                    //least 1 layer because we created it
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A target that draws nothing & has no keys, for driving the controller by its functions
+    struct Headless;
+
+    impl UserInterface for Headless {
+        fn initialize(&mut self) {}
+        fn finalize(&mut self) {}
+        fn refresh(&mut self) -> bool {
+            true
+        }
+        fn get_key(&self) -> Option<KeyInfo> {
+            None
+        }
+        fn get_size(&self) -> PCoord {
+            PCoord::new(40, 80).unwrap()
+        }
+        fn draw_camera(&mut self, _: PCoord, _: Vec<OPixel>, _: bool, _: &Rectangle) {}
+        fn draw_paragraph(&mut self, _: Vec<colored::ColoredString>, _: &Rectangle) {}
+        fn draw_statusline(&mut self, _: &Statusline, _: &Rectangle) {}
+        fn console_in(&mut self, _: &str, _: &Key, _: &Rectangle) -> Option<String> {
+            None
+        }
+        fn console_out(&mut self, _: &str, _: &LogType, _: &Rectangle) {}
+        fn clear(&mut self, _: &Rectangle) {}
+        fn clear_all(&mut self) {}
+    }
+
+    /// A controller with the default config, no macros file & a session of an 8x8 canvas with no
+    /// cursors & the focus at the given coordinate
+    fn controller(focus: Coord) -> Controller {
+        let config = Config::from_syntax(None).unwrap();
+        let mut controller =
+            Controller::with_macros_path(Rc::new(RefCell::new(Headless)), config, None);
+        controller.new_session(
+            &StartType::New {
+                width: Some(8),
+                height: Some(8),
+                indexed: false,
+            },
+            false,
+        );
+        let mut pixylene = controller.sessions[0].pixylene.borrow_mut();
+        _ = pixylene.project.clear_cursors().count();
+        pixylene.project.focus = (focus, 0);
+        drop(pixylene);
+        controller
+    }
+
+    fn cursors(controller: &Controller) -> Vec<(u16, u16)> {
+        let mut cursors = controller.sessions[0]
+            .pixylene
+            .borrow()
+            .project
+            .cursors()
+            .map(|(coord, _)| (coord.x, coord.y))
+            .collect::<Vec<(u16, u16)>>();
+        cursors.sort();
+        cursors
+    }
+
+    fn step(controller: &mut Controller, name: &str, count: usize) {
+        controller
+            .perform_ui(&UiFn::RunNativeAction {
+                name: name.to_owned(),
+                args: vec![format!("count={}", count)],
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn visual_rectangle_clamps() {
+        let controller = controller(Coord { x: 0, y: 0 });
+        let project = &controller.sessions[0].pixylene.borrow().project;
+        let corners = |anchor: (i32, i32), focus: (i32, i32)| {
+            visual_rectangle(
+                Coord {
+                    x: anchor.0,
+                    y: anchor.1,
+                },
+                Coord {
+                    x: focus.0,
+                    y: focus.1,
+                },
+                project,
+            )
+            .map(|(start, end)| ((start.x, start.y), (end.x, end.y)))
+        };
+        assert_eq!(corners((1, 5), (3, 2)), Some(((1, 2), (3, 5))));
+        assert_eq!(corners((3, 2), (1, 5)), Some(((1, 2), (3, 5))));
+        assert_eq!(corners((-2, 3), (2, 10)), Some(((0, 3), (2, 7))));
+        assert_eq!(corners((4, 4), (4, 4)), Some(((4, 4), (4, 4))));
+        assert_eq!(corners((8, 0), (12, 7)), None);
+        assert_eq!(corners((-3, -1), (-1, -5)), None);
+    }
+
+    #[test]
+    fn visual_mode_rectangle() {
+        let mut controller = controller(Coord { x: 1, y: 1 });
+        let namespace = controller.namespace.clone();
+        controller
+            .perform_ui(&UiFn::Visual { point: false })
+            .unwrap();
+        assert_eq!(controller.namespace, VISUAL_NAMESPACE);
+
+        //stepping the focus isn't committed
+        step(&mut controller, "focus_down", 2);
+        step(&mut controller, "focus_right", 9);
+        assert_eq!(controller.sessions[0].native_action_manager.last_seq(), 0);
+
+        //the rectangle is clipped to the canvas & selecting it leaves visual mode
+        controller.perform_ui(&UiFn::VisualConfirm).unwrap();
+        assert_eq!(
+            cursors(&controller),
+            (1..4)
+                .flat_map(|x| (1..8).map(move |y| (x, y)))
+                .collect::<Vec<(u16, u16)>>()
+        );
+        assert!(controller.visual.is_none());
+        assert_eq!(controller.namespace, namespace);
+        assert!(controller.perform_ui(&UiFn::VisualConfirm).is_ok());
+        assert_eq!(cursors(&controller).len(), 21);
+    }
+
+    #[test]
+    fn visual_mode_point() {
+        let mut controller = controller(Coord { x: 2, y: 2 });
+        controller
+            .perform_ui(&UiFn::Visual { point: true })
+            .unwrap();
+
+        //points are toggled at the focus, staying in visual mode
+        controller.perform_ui(&UiFn::VisualConfirm).unwrap();
+        step(&mut controller, "focus_down", 3);
+        controller.perform_ui(&UiFn::VisualConfirm).unwrap();
+        step(&mut controller, "focus_right", 1);
+        controller.perform_ui(&UiFn::VisualConfirm).unwrap();
+        assert_eq!(cursors(&controller), vec![(2, 2), (5, 2), (5, 3)]);
+        controller.perform_ui(&UiFn::VisualConfirm).unwrap();
+        assert_eq!(cursors(&controller), vec![(2, 2), (5, 2)]);
+        assert!(controller.visual.is_some());
+
+        //a point outside the canvas isn't selected
+        step(&mut controller, "focus_up", 7);
+        controller.perform_ui(&UiFn::VisualConfirm).unwrap();
+        assert_eq!(cursors(&controller), vec![(2, 2), (5, 2)]);
+
+        //entering visual mode again starts over
+        controller
+            .perform_ui(&UiFn::Visual { point: false })
+            .unwrap();
+        assert!(matches!(controller.visual, Some((Some(_), _))));
+    }
+
+    #[test]
+    fn pen_lifts_before_changes() {
+        let mut controller = controller(Coord { x: 0, y: 0 });
+        let drawn = |controller: &Controller| {
+            let project = &controller.sessions[0].pixylene.borrow().project;
+            let scene = &project.canvas.layers.to_true().unwrap()[0].scene;
+            (0..8)
+                .filter(|y| scene.get_pixel(UCoord { x: 0, y: *y }).unwrap().is_some())
+                .collect::<Vec<u16>>()
+        };
+        controller.sessions[0]
+            .pixylene
+            .borrow_mut()
+            .project
+            .toggle_cursor_at(&(UCoord { x: 0, y: 0 }, 0))
+            .unwrap();
+
+        //moving the cursors carries the stroke along without committing it
+        controller.perform_ui(&UiFn::PenDown).unwrap();
+        step(&mut controller, "cursors_right", 2);
+        assert!(controller.sessions[0].stroke.is_some());
+        assert_eq!(controller.sessions[0].native_action_manager.last_seq(), 0);
+        assert_eq!(drawn(&controller), vec![0, 1, 2]);
+
+        //changing the canvas otherwise commits the stroke first, & the change apart from it
+        controller
+            .perform_ui(&UiFn::RunNativeAction {
+                name: String::from("eraser"),
+                args: Vec::new(),
+            })
+            .unwrap();
+        assert!(controller.sessions[0].stroke.is_none());
+        assert_eq!(controller.sessions[0].native_action_manager.last_seq(), 2);
+        assert_eq!(drawn(&controller), vec![0, 1]);
+        controller.perform_ui(&UiFn::Undo).unwrap();
+        assert_eq!(drawn(&controller), vec![0, 1, 2]);
+        controller.perform_ui(&UiFn::Undo).unwrap();
+        assert_eq!(drawn(&controller), Vec::<u16>::new());
+    }
+
+    fn focus(controller: &Controller) -> (i32, i32) {
+        let focus = controller.sessions[0].pixylene.borrow().project.focus.0;
+        (focus.x, focus.y)
+    }
+
+    fn native(name: &str) -> UiFn {
+        UiFn::RunNativeAction {
+            name: name.to_owned(),
+            args: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn macro_replays_recording() {
+        let mut controller = controller(Coord { x: 0, y: 0 });
+        let record = UiFn::RecordMacro {
+            register: Some('a'),
+        };
+
+        controller.run_counted(vec![record.clone()], None);
+        controller.run_counted(vec![native("focus_down")], None);
+        controller.run_counted(vec![native("focus_right")], Some(2));
+        controller.run_counted(vec![record], None);
+        assert!(controller.recording.is_none());
+        assert_eq!(
+            controller.registers.get(&'a'),
+            Some(&vec![
+                native("focus_down"),
+                UiFn::RunNativeAction {
+                    name: String::from("focus_right"),
+                    args: vec![String::from("count=2")],
+                },
+            ])
+        );
+        assert_eq!(focus(&controller), (1, 2));
+
+        //playing performs the same functions, & records nothing while another macro is recorded
+        controller.recording = Some(('b', Vec::new()));
+        controller
+            .perform_ui(&UiFn::PlayMacro {
+                register: Some('a'),
+                count: None,
+            })
+            .unwrap();
+        assert_eq!(focus(&controller), (2, 4));
+        assert_eq!(controller.recording, Some(('b', Vec::new())));
+    }
+
+    #[test]
+    fn macro_count_multiplies_playback() {
+        let mut controller = controller(Coord { x: 0, y: 0 });
+        controller
+            .registers
+            .insert('a', vec![native("focus_down"), native("focus_right")]);
+
+        controller.run_counted(
+            vec![UiFn::PlayMacro {
+                register: Some('a'),
+                count: None,
+            }],
+            Some(3),
+        );
+        assert_eq!(focus(&controller), (3, 3));
+        controller
+            .perform_ui(&UiFn::PlayMacro {
+                register: Some('a'),
+                count: Some(2),
+            })
+            .unwrap();
+        assert_eq!(focus(&controller), (5, 5));
+    }
+
+    #[test]
+    fn macros_save_and_load() {
+        let path = std::env::temp_dir().join("pixylene_test_macros.toml");
+        _ = std::fs::remove_file(&path);
+        let new = || {
+            Controller::with_macros_path(
+                Rc::new(RefCell::new(Headless)),
+                Config::from_syntax(None).unwrap(),
+                Some(path.clone()),
+            )
+        };
+
+        let mut controller = new();
+        assert!(controller.registers.is_empty());
+        controller.registers.insert(
+            'a',
+            vec![
+                native("focus_down"),
+                UiFn::RunCommand {
+                    cmd: String::from("echo \"quoted\" 'text' = [1]"),
+                },
+                UiFn::PlayMacro {
+                    register: Some('b'),
+                    count: Some(2),
+                },
+            ],
+        );
+        controller
+            .registers
+            .insert('b', vec![UiFn::Undo, native("focus_right")]);
+        controller.save_macros();
+
+        assert_eq!(new().registers, controller.registers);
+        assert_eq!(load_macros(&path), Ok(controller.registers));
+        _ = std::fs::remove_file(&path);
+    }
+
+    fn key(key: &str) -> Key {
+        key.parse().unwrap()
+    }
+
+    #[test]
+    fn key_sequences_parse() {
+        use crate::ui::SeqKey;
+
+        assert_eq!(
+            "g  g".parse::<KeySeq>(),
+            Ok(KeySeq::from(vec![key("g"), key("g")]))
+        );
+        let seq = "<leader> l n".parse::<KeySeq>().unwrap();
+        assert_eq!(
+            seq,
+            KeySeq(vec![
+                SeqKey::Leader,
+                SeqKey::Key(key("l")),
+                SeqKey::Key(key("n"))
+            ])
+        );
+        assert_eq!(seq.to_string(), "<leader> l n");
+        assert_eq!(
+            seq.with_leader(&key(",")),
+            KeySeq::from(vec![key(","), key("l"), key("n")])
+        );
+        assert!("".parse::<KeySeq>().is_err());
+        assert!(" ".parse::<KeySeq>().is_err());
+
+        let g = KeySeq::from(vec![key("g")]);
+        let gg = KeySeq::from(vec![key("g"), key("g")]);
+        assert!(gg.starts_with(&g));
+        assert!(!g.starts_with(&gg));
+        assert!(!g.starts_with(&g));
+    }
+
+    #[test]
+    fn key_sequence_conflicts() {
+        use crate::config::ConfigSyntax;
+
+        let config = |keys: &[&str]| {
+            let mut syntax = ConfigSyntax::default();
+            syntax.leader = Some(key(","));
+            syntax.keys = HashMap::from([(
+                syntax.default_namespace.clone(),
+                keys.iter()
+                    .map(|keys| (keys.parse::<KeySeq>().unwrap(), vec![UiFn::Undo]))
+                    .collect(),
+            )]);
+            Config::from_syntax(Some(syntax))
+        };
+
+        let namespace = Some(ConfigSyntax::default().default_namespace);
+        let keymap = config(&["<leader> x", "z z"]).unwrap().keymap;
+        assert!(keymap[&namespace].contains_key(&KeySeq::from(vec![key(","), key("x")])));
+        assert!(keymap[&namespace].contains_key(&KeySeq::from(vec![key("z"), key("z")])));
+
+        //a bound sequence may not start another one, also after the leader is expanded
+        assert!(config(&["z", "z z"])
+            .is_err_and(|err| err.contains("'z' is bound but is also the start of 'z z'")));
+        assert!(config(&[", x", "<leader> x y"])
+            .is_err_and(|err| err.contains("is bound but is also the start of")));
+    }
+
+    #[test]
+    fn pending_keys_expire() {
+        let mut controller = controller(Coord { x: 0, y: 0 });
+        let namespace = Some(controller.namespace.clone());
+        controller
+            .config
+            .keymap
+            .get_mut(&namespace)
+            .unwrap()
+            .insert("z z".parse().unwrap(), vec![native("focus_down")]);
+        let press = |controller: &mut Controller, pressed: &str| {
+            controller
+                .perform_ui(&UiFn::RunKey { key: key(pressed) })
+                .unwrap();
+        };
+
+        //a prefix waits for the rest of its sequence
+        press(&mut controller, "z");
+        assert_eq!(controller.pending_keys, vec![key("z")]);
+        assert_eq!(focus(&controller), (0, 0));
+        press(&mut controller, "z");
+        assert!(controller.pending_keys.is_empty());
+        assert_eq!(focus(&controller), (1, 0));
+
+        //but is dropped along with its count once the timeout passes
+        press(&mut controller, "3");
+        press(&mut controller, "z");
+        controller.key_time -= controller.config.key_timeout + std::time::Duration::from_millis(1);
+        controller.expire_keys();
+        assert!(controller.pending_keys.is_empty());
+        assert_eq!(controller.count, None);
+        press(&mut controller, "z");
+        assert_eq!(controller.pending_keys, vec![key("z")]);
+        assert_eq!(focus(&controller), (1, 0));
+    }
+
+    #[test]
+    fn count_maps_to_function() {
+        let motion = UiFn::Motion {
+            name: String::from("down"),
+            count: None,
+        };
+        assert_eq!(
+            motion.with_count(4),
+            Some(UiFn::Motion {
+                name: String::from("down"),
+                count: Some(4),
+            })
+        );
+        assert_eq!(
+            UiFn::Operator {
+                name: String::from("delete"),
+                count: Some(2),
+            }
+            .with_count(3),
+            Some(UiFn::Operator {
+                name: String::from("delete"),
+                count: Some(3),
+            })
+        );
+        assert_eq!(
+            UiFn::PlayMacro {
+                register: Some('a'),
+                count: None,
+            }
+            .with_count(5),
+            Some(UiFn::PlayMacro {
+                register: Some('a'),
+                count: Some(5),
+            })
+        );
+        //a count isn't a session index, so going to a session is repeated like anything else
+        assert_eq!(UiFn::GoToSession { index: 1 }.with_count(5), None);
+        assert_eq!(UiFn::Undo.with_count(5), None);
+    }
+}
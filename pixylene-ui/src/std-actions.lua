@@ -212,6 +212,56 @@ actions['equip'] = {
     end
 }
 
+-- draws the equipped color at every cursor, its opacity & blend-mode being configurable from
+-- keybinds, e.g. { l={ s="actions.lua_pencil.opacity = 128" } }
+actions['lua_pencil'] = {
+    opacity = 255,
+    blend_mode = BlendMode.NORMAL,
+    perform = function(self, project, console)
+        local indexed = project.canvas.indexed
+        local col
+        if indexed then
+            col = IP(project.canvas.palette.equipped)
+        else
+            col = project.canvas.palette:get()
+            col = TP(col.red, col.green, col.blue, math.floor(col.alpha * self.opacity / 255))
+        end
+        for _, cursor in pairs(project.cursors) do
+            local scene = indexed
+                and project.canvas.layers['indexed']:get(cursor.layer).scene
+                or  project.canvas.layers['true']:get(cursor.layer).scene
+            scene:set(cursor.coord, indexed
+                and col
+                or self.blend_mode:blend(col, scene:get(cursor.coord)))
+        end
+    end
+}
+
+-- empties the pixel at every cursor, or only takes 'amount' (0-255) out of 255 of its alpha away on
+-- true-color canvases
+actions['lua_eraser'] = {
+    amount = nil,
+    perform = function(self, project, console)
+        for _, cursor in pairs(project.cursors) do
+            if project.canvas.indexed then
+                project.canvas.layers['indexed']:get(cursor.layer).scene:set(cursor.coord, nil)
+            else
+                local scene = project.canvas.layers['true']:get(cursor.layer).scene
+                local c = scene:get(cursor.coord)
+                local alpha = 0
+                if c and self.amount then
+                    alpha = math.floor(c.alpha * (255 - self.amount) / 255)
+                end
+                if alpha > 0 then
+                    scene:set(cursor.coord, TP(c.red, c.green, c.blue, alpha))
+                else
+                    scene:set(cursor.coord, nil)
+                end
+            end
+        end
+    end
+}
+
 actions['fill'] = {
     -- https://www.geeksforgeeks.org/flood-fill-algorithm-implement-fill-paint
     equal = function(c1, c2)
@@ -12,18 +12,20 @@ use libpixylene::{
     types::{PCoord, UCoord},
 };
 use pixylene_actions::LogType;
-use std::{cell::RefCell, collections::HashMap, io::Write, rc::Rc};
+use std::{cell::RefCell, collections::HashMap, io::Write, rc::Rc, time::Duration};
 
 /// Pixylene UI's Target for the [`crossterm`](crossterm) terminal manipulation library
 /// [Crossterm repository](https://github.com/crossterm-rs/crossterm)
 struct TargetCrossterm {
     bound: HashMap<(u16, u16), UCoord>,
+    key_timeout: Duration,
 }
 
 impl TargetCrossterm {
-    pub fn new() -> Self {
+    pub fn new(key_timeout: Duration) -> Self {
         Self {
             bound: HashMap::new(),
+            key_timeout,
         }
     }
 }
@@ -162,12 +164,15 @@ impl UserInterface for TargetCrossterm {
     fn get_key(&self) -> Option<KeyInfo> {
         #[allow(unused_imports)]
         use event::{
-            read, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
-            MouseEventKind,
+            poll, read, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton,
+            MouseEvent, MouseEventKind,
         };
 
         loop {
-            //blocking read
+            //returns without a key once the timeout passes so that pending keys can expire
+            if !poll(self.key_timeout).unwrap() {
+                return None;
+            }
             match read().unwrap() {
                 Event::Key(key_event) => {
                     if key_event.kind == KeyEventKind::Press {
@@ -181,10 +186,19 @@ impl UserInterface for TargetCrossterm {
                         }
                     }
                 }
-                #[cfg(feature = "lua")]
                 Event::Mouse(MouseEvent {
                     kind, column, row, ..
                 }) => {
+                    //picks the color of the clicked pixel
+                    if kind == MouseEventKind::Down(MouseButton::Right) {
+                        if let Some(scene_coord) = self.bound.get(&(row, column)) {
+                            return Some(KeyInfo::UiFn(pixylene_ui::ui::UiFn::Eyedropper {
+                                x: Some(scene_coord.x),
+                                y: Some(scene_coord.y),
+                            }));
+                        }
+                    }
+                    #[cfg(feature = "lua")]
                     if kind == MouseEventKind::Down(MouseButton::Left) {
                         if let Some(scene_coord) = self.bound.get(&(row, column)) {
                             return Some(KeyInfo::UiFn(pixylene_ui::ui::UiFn::RunLua {
@@ -394,8 +408,8 @@ Project:toggle(UC({}, {}), Project.focus.layer)
 
 fn main() -> Result<(), ()> {
     let cli = Cli::parse();
-    let target = TargetCrossterm::new();
     let config = Config::from_config_toml().map_err(|err| eprintln!("{}", err))?;
+    let target = TargetCrossterm::new(config.key_timeout);
 
     let mut pixylene_tui = Controller::new(Rc::new(RefCell::new(target)), config);
     if let Some(command) = cli.command {
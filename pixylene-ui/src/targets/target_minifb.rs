@@ -7,11 +7,15 @@ use pixylene_ui::{
 
 use clap::Parser;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use libpixylene::{project::OPixel, types::PCoord};
-use minifb::{KeyRepeat, Scale, Window, WindowOptions};
+use libpixylene::{
+    project::OPixel,
+    types::{PCoord, UCoord},
+};
+use minifb::{KeyRepeat, MouseButton, MouseMode, Scale, Window, WindowOptions};
 use minifb_fonts::font5x8;
 use pixylene_actions::LogType;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::rc::Rc;
 
 const NOWIN: &str = "No Minifb Window found in Target, something is wrong.";
@@ -22,11 +26,30 @@ const PIXELFACTOR: u16 = 8;
 const FONT_WIDTH: u8 = 5;
 const FONT_HEIGHT: u8 = 8;
 
-pub struct TargetMinifb(/*window*/ Option<Window>, /*buffer*/ Vec<u32>);
+pub struct TargetMinifb(
+    /*window*/ Option<Window>,
+    /*buffer*/ Vec<u32>,
+    /*scene coordinates of the drawn camera cells*/ HashMap<(u16, u16), UCoord>,
+    /*whether the right mouse button was down last frame*/ Cell<bool>,
+);
 
 impl TargetMinifb {
     pub fn new() -> Self {
-        TargetMinifb(None, Vec::new())
+        TargetMinifb(None, Vec::new(), HashMap::new(), Cell::new(false))
+    }
+
+    /// Returns the scene coordinate of the pixel right-clicked this frame, if any
+    fn right_clicked(&self) -> Option<UCoord> {
+        let window = self.0.as_ref().expect(NOWIN);
+        let down = window.get_mouse_down(MouseButton::Right);
+        //only pressing the button clicks, holding it doesn't
+        if self.3.replace(down) || !down {
+            return None;
+        }
+        let (x, y) = window.get_mouse_pos(MouseMode::Discard)?;
+        self.2
+            .get(&(y as u16 / PIXELFACTOR, x as u16 / PIXELFACTOR))
+            .copied()
     }
 
     /// Converts vector returned by [`get_keys_pressed`](minifb::Window::get_keys_pressed) into a
@@ -294,6 +317,13 @@ impl UserInterface for TargetMinifb {
     }
 
     fn get_key(&self) -> Option<KeyInfo> {
+        //picks the color of the right-clicked pixel
+        if let Some(scene_coord) = self.right_clicked() {
+            return Some(KeyInfo::UiFn(ui::UiFn::Eyedropper {
+                x: Some(scene_coord.x),
+                y: Some(scene_coord.y),
+            }));
+        }
         let window = self.0.as_ref().expect(NOWIN);
         let key =
             Self::key_to_crossterm(window.get_keys(), window.get_keys_pressed(KeyRepeat::Yes));
@@ -319,10 +349,15 @@ impl UserInterface for TargetMinifb {
         boundary: &Rectangle,
     ) {
         let ref mut framebuffer = self.1;
+        self.2.clear();
 
         for i in 0..dim.x() {
             for j in 0..dim.y() {
                 let scene_index = usize::from(i) * usize::from(dim.y()) + usize::from(j);
+                if let Some(OPixel::Filled { scene_coord, .. }) = buffer.get(scene_index) {
+                    self.2
+                        .insert((boundary.start.x + i, boundary.start.y + j), *scene_coord);
+                }
                 for s in 0..PIXELFACTOR {
                     for t in 0..PIXELFACTOR {
                         let out_index =
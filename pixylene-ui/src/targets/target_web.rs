@@ -1,10 +1,14 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use libpixylene::{
+    file::SceneEncoding,
     project::{OPixel, Palette},
     types::PCoord,
     PixyleneDefaults,
 };
-use pixylene_actions::LogType;
+use pixylene_actions::{
+    memento::{HistoryLimits, JumpList},
+    LogType,
+};
 use pixylene_ui::{
     config::Config,
     controller::{Controller, StartType},
@@ -210,6 +214,8 @@ impl UserInterface for TargetWeb {
         self.0.refresh()
     }
 
+    //todo: the page only passes keys, so pixels can't be right-clicked to pick their color with
+    //the eyedropper like in the other targets
     fn get_key(&self) -> Option<KeyInfo> {
         //if let Ok(key) = Wrap::try_from(self.0.get_key()) {
         //    key.0.map(|key| KeyInfo::Key(key))
@@ -334,97 +340,113 @@ pub fn start() {
                         keybind!(
                             (Char('h'), KeyModifiers::empty()),
                             RunAction {
-                                name: "cursors_left".to_owned()
+                                name: "cursors_left".to_owned(),
+                                args: Vec::new(),
                             }
                         ),
                         keybind!(
                             (Char('h'), KeyModifiers::empty()),
                             RunAction {
-                                name: String::from("cursors_left")
+                                name: String::from("cursors_left"),
+                                args: Vec::new(),
                             }
                         ),
                         keybind!(
                             (Char('j'), KeyModifiers::empty()),
                             RunAction {
-                                name: String::from("cursors_down")
+                                name: String::from("cursors_down"),
+                                args: Vec::new(),
                             }
                         ),
                         keybind!(
                             (Char('k'), KeyModifiers::empty()),
                             RunAction {
-                                name: String::from("cursors_up")
+                                name: String::from("cursors_up"),
+                                args: Vec::new(),
                             }
                         ),
                         keybind!(
                             (Char('l'), KeyModifiers::empty()),
                             RunAction {
-                                name: String::from("cursors_right")
+                                name: String::from("cursors_right"),
+                                args: Vec::new(),
                             }
                         ),
                         keybind!(
                             (Left, KeyModifiers::empty()),
                             RunAction {
-                                name: String::from("cursors_left")
+                                name: String::from("cursors_left"),
+                                args: Vec::new(),
                             }
                         ),
                         keybind!(
                             (Down, KeyModifiers::empty()),
                             RunAction {
-                                name: String::from("cursors_down")
+                                name: String::from("cursors_down"),
+                                args: Vec::new(),
                             }
                         ),
                         keybind!(
                             (Up, KeyModifiers::empty()),
                             RunAction {
-                                name: String::from("cursors_up")
+                                name: String::from("cursors_up"),
+                                args: Vec::new(),
                             }
                         ),
                         keybind!(
                             (Right, KeyModifiers::empty()),
                             RunAction {
-                                name: String::from("cursors_right")
+                                name: String::from("cursors_right"),
+                                args: Vec::new(),
                             }
                         ),
                         keybind!(
                             (Char('H'), KeyModifiers::empty()),
                             RunAction {
-                                name: String::from("cursors_dup_left")
+                                name: String::from("cursors_dup_left"),
+                                args: Vec::new(),
                             }
                         ),
                         keybind!(
                             (Char('J'), KeyModifiers::empty()),
                             RunAction {
-                                name: String::from("cursors_dup_down")
+                                name: String::from("cursors_dup_down"),
+                                args: Vec::new(),
                             }
                         ),
                         keybind!(
                             (Char('K'), KeyModifiers::empty()),
                             RunAction {
-                                name: String::from("cursors_dup_up")
+                                name: String::from("cursors_dup_up"),
+                                args: Vec::new(),
                             }
                         ),
                         keybind!(
                             (Char('L'), KeyModifiers::empty()),
                             RunAction {
-                                name: String::from("cursors_dup_right")
+                                name: String::from("cursors_dup_right"),
+                                args: Vec::new(),
                             }
                         ),
                         keybind!(
                             (Char('R'), KeyModifiers::empty()),
                             RunAction {
-                                name: String::from("cursors_reset")
+                                name: String::from("cursors_reset"),
+                                args: Vec::new(),
                             }
                         ),
                         keybind!(
                             (Char('i'), KeyModifiers::empty()),
                             RunAction {
-                                name: String::from("zoomin")
+                                name: String::from("zoomin"),
+                                args: Vec::new(),
                             }
                         ),
                         keybind!(
                             (Char('o'), KeyModifiers::empty()),
                             RunAction {
-                                name: String::from("zoomout")
+                                name: String::from("zoomout"),
+                                args: Vec::new(),
                             }
                         ),
                         keybind!((Char('u'), KeyModifiers::empty()), Undo),
@@ -432,109 +454,127 @@ pub fn start() {
                         keybind!(
                             (Enter, KeyModifiers::empty()),
                             RunAction {
-                                name: String::from("pencil")
+                                name: String::from("pencil"),
+                                args: Vec::new(),
                             }
                         ),
                         keybind!(
                             (Char('1'), KeyModifiers::empty()),
                             RunAction {
-                                name: String::from("pencil1")
+                                name: String::from("pencil1"),
+                                args: Vec::new(),
                             }
                         ),
                         keybind!(
                             (Char('2'), KeyModifiers::empty()),
                             RunAction {
-                                name: String::from("pencil2")
+                                name: String::from("pencil2"),
+                                args: Vec::new(),
                             }
                         ),
                         keybind!(
                             (Char('3'), KeyModifiers::empty()),
                             RunAction {
-                                name: String::from("pencil3")
+                                name: String::from("pencil3"),
+                                args: Vec::new(),
                             }
                         ),
                         keybind!(
                             (Char('4'), KeyModifiers::empty()),
                             RunAction {
-                                name: String::from("pencil4")
+                                name: String::from("pencil4"),
+                                args: Vec::new(),
                             }
                         ),
                         keybind!(
                             (Char('5'), KeyModifiers::empty()),
                             RunAction {
-                                name: String::from("pencil5")
+                                name: String::from("pencil5"),
+                                args: Vec::new(),
                             }
                         ),
                         keybind!(
                             (Char('6'), KeyModifiers::empty()),
                             RunAction {
-                                name: String::from("pencil6")
+                                name: String::from("pencil6"),
+                                args: Vec::new(),
                             }
                         ),
                         keybind!(
                             (Char('7'), KeyModifiers::empty()),
                             RunAction {
-                                name: String::from("pencil7")
+                                name: String::from("pencil7"),
+                                args: Vec::new(),
                             }
                         ),
                         keybind!(
                             (Char('8'), KeyModifiers::empty()),
                             RunAction {
-                                name: String::from("pencil8")
+                                name: String::from("pencil8"),
+                                args: Vec::new(),
                             }
                         ),
                         keybind!(
                             (Char('!'), KeyModifiers::empty()),
                             RunAction {
-                                name: String::from("equip1")
+                                name: String::from("equip1"),
+                                args: Vec::new(),
                             }
                         ),
                         keybind!(
                             (Char('@'), KeyModifiers::empty()),
                             RunAction {
-                                name: String::from("equip2")
+                                name: String::from("equip2"),
+                                args: Vec::new(),
                             }
                         ),
                         keybind!(
                             (Char('#'), KeyModifiers::empty()),
                             RunAction {
-                                name: String::from("equip3")
+                                name: String::from("equip3"),
+                                args: Vec::new(),
                             }
                         ),
                         keybind!(
                             (Char('$'), KeyModifiers::empty()),
                             RunAction {
-                                name: String::from("equip4")
+                                name: String::from("equip4"),
+                                args: Vec::new(),
                             }
                         ),
                         keybind!(
                             (Char('%'), KeyModifiers::empty()),
                             RunAction {
-                                name: String::from("equip5")
+                                name: String::from("equip5"),
+                                args: Vec::new(),
                             }
                         ),
                         keybind!(
                             (Char('^'), KeyModifiers::empty()),
                             RunAction {
-                                name: String::from("equip6")
+                                name: String::from("equip6"),
+                                args: Vec::new(),
                             }
                         ),
                         keybind!(
                             (Char('&'), KeyModifiers::empty()),
                             RunAction {
-                                name: String::from("equip7")
+                                name: String::from("equip7"),
+                                args: Vec::new(),
                             }
                         ),
                         keybind!(
                             (Char('*'), KeyModifiers::empty()),
                             RunAction {
-                                name: String::from("equip8")
+                                name: String::from("equip8"),
+                                args: Vec::new(),
                             }
                         ),
                         keybind!(
                             (Char('c'), KeyModifiers::empty()),
                             RunAction {
-                                name: String::from("circularoutline")
+                                name: String::from("circularoutline"),
+                                args: Vec::new(),
                             }
                         ),
                     ]),
@@ -546,6 +586,22 @@ pub fn start() {
                 },
                 every_frame: vec![UiFn::PreviewFocusLayer, UiFn::DrawStatusline],
                 padding: 0,
+                compress_projects: false,
+                canvas_encoding: SceneEncoding::Verbose,
+                history_limits: HistoryLimits {
+                    max_steps: None,
+                    max_memory: None,
+                },
+                save_undo_history: false,
+                max_saved_undo_memory: None,
+                jumplist: true,
+                max_jumps: Some(JumpList::DEFAULT_MAX_JUMPS),
+                disable_counts: Vec::new(),
+                key_timeout: std::time::Duration::from_millis(1000),
+                eyedropper_merged: false,
+                pencils: HashMap::new(),
+                erasers: HashMap::new(),
+                warnings: Vec::new(),
             },
         ));
     });
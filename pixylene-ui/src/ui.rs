@@ -1,5 +1,6 @@
 use clap::Subcommand;
 use libpixylene::{
+    file::{Downsampler, SceneEncoding, Upscaler},
     project::OPixel,
     types::{PCoord, UCoord},
 };
@@ -134,6 +135,93 @@ impl Into<keymap::KeyMap> for Key {
     }
 }
 
+/// A sequence of [`Keys`](Key) pressed one after the other to trigger a keybind, written as keys
+/// separated by spaces, e.g. `"g g"` or `"<leader> l n"`
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+pub struct KeySeq(pub Vec<SeqKey>);
+
+/// A single key of a [`KeySeq`]
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+pub enum SeqKey {
+    Key(Key),
+    /// Stands for the key configured as the leader
+    Leader,
+}
+
+impl KeySeq {
+    /// Returns this sequence with any [`Leader`](SeqKey::Leader) replaced by the given key
+    pub fn with_leader(self, leader: &Key) -> KeySeq {
+        KeySeq(
+            self.0
+                .into_iter()
+                .map(|key| match key {
+                    SeqKey::Leader => SeqKey::Key(leader.clone()),
+                    key => key,
+                })
+                .collect(),
+        )
+    }
+
+    /// Whether this sequence is longer than and starts with the given one
+    pub fn starts_with(&self, prefix: &KeySeq) -> bool {
+        self.0.len() > prefix.0.len() && self.0.starts_with(&prefix.0)
+    }
+}
+
+impl std::str::FromStr for KeySeq {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let keys = s
+            .split_whitespace()
+            .map(|key| match key {
+                "<leader>" => Ok(SeqKey::Leader),
+                key => key
+                    .parse::<Key>()
+                    .map(|key| SeqKey::Key(key))
+                    .map_err(|err| err.to_string()),
+            })
+            .collect::<Result<Vec<SeqKey>, String>>()?;
+        if keys.is_empty() {
+            Err(String::from("empty key sequence"))
+        } else {
+            Ok(KeySeq(keys))
+        }
+    }
+}
+
+impl std::fmt::Display for KeySeq {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let keys = self
+            .0
+            .iter()
+            .map(|key| match key {
+                SeqKey::Key(key) => key.to_string(),
+                SeqKey::Leader => String::from("<leader>"),
+            })
+            .collect::<Vec<String>>();
+        write!(f, "{}", keys.join(" "))
+    }
+}
+
+impl<'de> Deserialize<'de> for KeySeq {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        string::deserialize(deserializer)
+    }
+}
+
+impl From<Vec<Key>> for KeySeq {
+    fn from(item: Vec<Key>) -> KeySeq {
+        KeySeq(item.into_iter().map(|key| SeqKey::Key(key)).collect())
+    }
+}
+
+impl From<crossterm::event::KeyEvent> for KeySeq {
+    fn from(item: crossterm::event::KeyEvent) -> KeySeq {
+        KeySeq(vec![SeqKey::Key(Key::from(item))])
+    }
+}
+
 #[derive(Copy, Clone)]
 pub struct Rectangle {
     pub start: UCoord,
@@ -143,9 +231,9 @@ pub struct Rectangle {
 /// The Statusline
 pub type Statusline = Vec<colored::ColoredString>;
 
-/// The map of namespace-names to secondary maps of [`Keys`](Key) to the ordered
+/// The map of namespace-names to secondary maps of [`Key Sequences`](KeySeq) to the ordered
 /// sequence of [`UiFns`](UiFn) they will execute when pressed
-pub type KeyMap = HashMap<Option<String>, HashMap<Key, Vec<UiFn>>>;
+pub type KeyMap = HashMap<Option<String>, HashMap<KeySeq, Vec<UiFn>>>;
 
 #[derive(Debug, Clone, Eq, Hash, PartialEq, Deserialize, Serialize, Subcommand)]
 pub enum UiFn {
@@ -183,6 +271,9 @@ pub enum UiFn {
         path: PathBuf,
         width: Option<u32>,
         height: Option<u32>,
+        #[serde(alias = "d")]
+        #[clap(long, short)]
+        downsampler: Option<Downsampler>,
     },
 
     //#[serde(alias = "Import")]
@@ -218,7 +309,11 @@ pub enum UiFn {
 
     #[serde(alias = "export")]
     //not needed: #[command(visible_alias = "export")]
-    Export,
+    Export {
+        #[serde(alias = "u")]
+        #[clap(long, short)]
+        upscaler: Option<Upscaler>,
+    },
 
     #[serde(alias = "undo")]
     //not needed: #[command(visible_alias = "undo")]
@@ -228,6 +323,135 @@ pub enum UiFn {
     //not needed: #[command(visible_alias = "redo")]
     Redo,
 
+    #[serde(alias = "undoJump")]
+    #[command(visible_alias = "uj")]
+    UndoJump {
+        #[serde(alias = "s")]
+        seq: usize,
+    },
+
+    #[serde(alias = "earlier")]
+    //not needed: #[command(visible_alias = "earlier")]
+    UndoEarlier,
+
+    #[serde(alias = "later")]
+    //not needed: #[command(visible_alias = "later")]
+    UndoLater,
+
+    #[serde(alias = "undolist")]
+    #[command(visible_alias = "undolist")]
+    ListUndoBranches,
+
+    #[serde(alias = "jumpBack")]
+    #[command(visible_alias = "jb")]
+    JumpBack,
+
+    #[serde(alias = "jumpForward")]
+    #[command(visible_alias = "jf")]
+    JumpForward,
+
+    #[serde(alias = "rec")]
+    #[command(visible_alias = "rec")]
+    RecordMacro {
+        #[serde(alias = "r")]
+        register: Option<char>,
+    },
+
+    #[serde(alias = "play")]
+    #[command(visible_alias = "play")]
+    PlayMacro {
+        #[serde(alias = "r")]
+        register: Option<char>,
+        #[serde(alias = "c")]
+        count: Option<usize>,
+    },
+
+    #[serde(alias = "em")]
+    #[command(visible_alias = "em")]
+    EditMacro {
+        #[serde(alias = "r")]
+        register: char,
+    },
+
+    #[serde(alias = "lm")]
+    #[command(visible_alias = "lm")]
+    ListMacros,
+
+    #[serde(alias = "op")]
+    #[command(visible_alias = "op")]
+    Operator {
+        #[serde(alias = "n")]
+        name: String,
+        #[serde(alias = "c")]
+        #[clap(long, short)]
+        count: Option<usize>,
+    },
+
+    #[serde(alias = "mo")]
+    #[command(visible_alias = "mo")]
+    Motion {
+        #[serde(alias = "n")]
+        name: String,
+        #[serde(alias = "c")]
+        #[clap(long, short)]
+        count: Option<usize>,
+    },
+
+    #[serde(alias = "v")]
+    #[command(visible_alias = "v")]
+    Visual {
+        #[serde(alias = "p", default)]
+        #[clap(long, short, action)]
+        point: bool,
+    },
+
+    #[serde(alias = "vc")]
+    #[command(visible_alias = "vc")]
+    VisualConfirm,
+
+    #[serde(alias = "eye")]
+    #[command(visible_alias = "eye")]
+    Eyedropper {
+        #[serde(alias = "x")]
+        #[clap(long, short)]
+        x: Option<u16>,
+        #[serde(alias = "y")]
+        #[clap(long, short)]
+        y: Option<u16>,
+    },
+
+    #[serde(alias = "tool")]
+    //not needed: #[command(visible_alias = "tool")]
+    Tool {
+        #[serde(alias = "n")]
+        name: String,
+    },
+
+    #[serde(alias = "to")]
+    #[command(visible_alias = "to")]
+    ToolOption {
+        #[serde(alias = "o")]
+        option: String,
+        #[serde(alias = "v")]
+        value: String,
+    },
+
+    #[serde(alias = "tp")]
+    #[command(visible_alias = "tp")]
+    ToolPattern,
+
+    #[serde(alias = "pd")]
+    #[command(visible_alias = "pd")]
+    PenDown,
+
+    #[serde(alias = "pu")]
+    #[command(visible_alias = "pu")]
+    PenUp,
+
+    #[serde(alias = "pen")]
+    #[command(visible_alias = "pen")]
+    TogglePen,
+
     #[serde(alias = "ns")]
     #[command(visible_alias = "ns")]
     EnterNamespace {
@@ -259,6 +483,8 @@ pub enum UiFn {
     RunNativeAction {
         #[serde(alias = "n")]
         name: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        args: Vec<String>,
     },
 
     #[cfg(feature = "lua")]
@@ -267,6 +493,8 @@ pub enum UiFn {
     RunLuaAction {
         #[serde(alias = "n")]
         name: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        args: Vec<String>,
     },
 
     #[serde(alias = "a")]
@@ -274,6 +502,8 @@ pub enum UiFn {
     RunAction {
         #[serde(alias = "n")]
         name: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        args: Vec<String>,
     },
 
     #[serde(alias = "A")]
@@ -311,7 +541,11 @@ pub enum UiFn {
 
     #[serde(alias = "pc")]
     #[command(visible_alias = "pc")]
-    PrintCanvasJson,
+    PrintCanvasJson {
+        #[serde(alias = "e")]
+        #[clap(long, short)]
+        encoding: Option<SceneEncoding>,
+    },
 
     #[serde(alias = "ln")]
     #[command(visible_alias = "ln")]
@@ -329,6 +563,28 @@ pub enum UiFn {
     ListCommands,
 }
 
+impl UiFn {
+    /// Returns this function with a count prefix taken as its own argument, if it has one that
+    /// fits, instead of it being repeated that many times
+    pub fn with_count(&self, count: usize) -> Option<UiFn> {
+        match self {
+            UiFn::PlayMacro { register, .. } => Some(UiFn::PlayMacro {
+                register: *register,
+                count: Some(count),
+            }),
+            UiFn::Operator { name, .. } => Some(UiFn::Operator {
+                name: name.clone(),
+                count: Some(count),
+            }),
+            UiFn::Motion { name, .. } => Some(UiFn::Motion {
+                name: name.clone(),
+                count: Some(count),
+            }),
+            _ => None,
+        }
+    }
+}
+
 /// The mapping of [`Keys`](Key) to functions mandatorily required by the app.
 #[derive(Debug, Deserialize)]
 pub struct ReqUiFnMap {
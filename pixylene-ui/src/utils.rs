@@ -35,6 +35,15 @@ pub fn _parse_toml(string: &str) -> Result<UiFn, toml::de::Error> {
     toml::from_str(&format!("u = {}", string)).map(|container: Container| container.u)
 }
 
+pub fn parse_list(string: &str) -> Result<Vec<UiFn>, toml::de::Error> {
+    #[derive(Deserialize)]
+    struct Container {
+        u: Vec<UiFn>,
+    }
+
+    toml::from_str(&format!("u = {}", string)).map(|container: Container| container.u)
+}
+
 pub fn deparse(uifns: &Vec<UiFn>) -> String {
     #[derive(Serialize)]
     struct Container {